@@ -52,6 +52,12 @@ pub enum CliCommand {
         /// The timelock delay of the DAO's created proposals.
         #[clap(long)]
         timelock_delay_seconds: i64,
+        /// The share of votes, in basis points, the winning side of a proposal must reach. 0 is a simple majority.
+        #[clap(long, default_value_t = 0)]
+        approval_threshold_bps: u16,
+        /// If set, the quorum is this share, in basis points, of the total voting power of the locker.
+        #[clap(long, default_value_t = 0)]
+        quorum_bps: u16,
         /// The timelock delay of the DAO's created proposals.
         #[clap(long)]
         voter_program: Pubkey,
@@ -71,6 +77,12 @@ pub enum CliCommand {
         /// The timelock delay of the DAO's created proposals.
         #[clap(long)]
         timelock_delay_seconds: i64,
+        /// The share of votes, in basis points, the winning side of a proposal must reach. 0 is a simple majority.
+        #[clap(long, default_value_t = 0)]
+        approval_threshold_bps: u16,
+        /// If set, the quorum is this share, in basis points, of the total voting power of the locker.
+        #[clap(long, default_value_t = 0)]
+        quorum_bps: u16,
         /// The timelock delay of the DAO's created proposals.
         #[clap(long)]
         voter_program: Pubkey,
@@ -78,11 +90,29 @@ pub enum CliCommand {
     CreateDummyProposal {
         #[clap(long)]
         base: Pubkey,
+        /// Name of the proposal class, if the proposal does not use the governor's parameters.
+        #[clap(long)]
+        proposal_class: Option<String>,
     },
     CancelProposal {
         #[clap(long)]
         proposal: Pubkey,
     },
+    /// Creates the params of a proposal created before proposals had them
+    CreateProposalParams {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Closes a finished proposal and its metadata, returning the rent
+    CloseProposal {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Closes every vote of a proposal which may be closed, returning the rent to voters
+    CloseVotes {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
     QueueProposal {
         #[clap(long)]
         proposal: Pubkey,
@@ -91,6 +121,47 @@ pub enum CliCommand {
         #[clap(long)]
         proposal: Pubkey,
     },
+    /// Vetoes a proposal, signed by the veto authority of the governor
+    VetoProposal {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Records the execution or expiry of a queued proposal's transaction
+    SyncProposal {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Refunds the deposit of a finished proposal to the proposer, or slashes it to the treasury
+    SettleProposalDeposit {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Tallies a multi-option proposal and prints the winning option
+    TallyProposal {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Tops up a reward of a draft or active proposal in any token, from the wallet's ATA
+    FundProposalReward {
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        reward_mint: Pubkey,
+        #[clap(long)]
+        amount: u64,
+    },
+    /// Refunds the unclaimed rewards of a proposal to the ATAs of their sponsors
+    RefundProposalRewards {
+        #[clap(long)]
+        proposal: Pubkey,
+    },
+    /// Claims voting rewards on behalf of voters, paid to each voter's ATA
+    BatchClaimReward {
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        voters: Vec<Pubkey>,
+    },
     CreateProposalMeta {
         #[clap(long)]
         proposal: Pubkey,
@@ -5,12 +5,17 @@ use anchor_client::solana_client::rpc_filter::Memcmp;
 use anchor_client::solana_client::rpc_filter::RpcFilterType;
 use anyhow::Result;
 
+use anchor_client::anchor_lang::InstructionData;
+use anchor_client::anchor_lang::ToAccountMetas;
 use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signer::keypair::*;
 use anchor_client::solana_sdk::signer::Signer;
 use anchor_client::{Client, Program};
+use anchor_spl::associated_token::get_associated_token_address;
 use clap::*;
+use solana_program::account_info::{AccountInfo, IntoAccountInfo};
+use solana_program::instruction::{AccountMeta, Instruction};
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
@@ -21,6 +26,97 @@ pub fn derive_event_authority_pda() -> (Pubkey, u8) {
     Pubkey::find_program_address(&[b"__event_authority"], &govern::ID)
 }
 
+/// The [govern::ProposalMeta] and [govern::OptionProposalMeta] of a proposal, if they exist.
+fn find_proposal_metas<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> (Option<Pubkey>, Option<Pubkey>) {
+    let [proposal_meta, option_proposal_meta] =
+        [b"ProposalMeta".as_ref(), b"OptionProposalMeta".as_ref()].map(|seed| {
+            let (meta, _bump) =
+                Pubkey::find_program_address(&[seed, proposal.as_ref()], &govern::id());
+            program.rpc().get_account(&meta).ok().map(|_| meta)
+        });
+    (proposal_meta, option_proposal_meta)
+}
+
+/// The payers recorded in the metadata of a proposal, who receive their rent when closed.
+/// [None] if the metadata does not exist, or its rent returns to the proposer.
+fn find_proposal_meta_payers<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal_meta: Option<Pubkey>,
+    option_proposal_meta: Option<Pubkey>,
+) -> Result<(Option<Pubkey>, Option<Pubkey>)> {
+    let proposal_meta_payer = match proposal_meta {
+        Some(proposal_meta) => {
+            let meta_state: govern::ProposalMeta = program.account(proposal_meta)?;
+            let data = program.rpc().get_account_data(&proposal_meta)?;
+            meta_state.recorded_payer(&data)
+        }
+        None => None,
+    };
+    let option_proposal_meta_payer = match option_proposal_meta {
+        Some(option_proposal_meta) => {
+            let meta_state: govern::OptionProposalMeta = program.account(option_proposal_meta)?;
+            let data = program.rpc().get_account_data(&option_proposal_meta)?;
+            meta_state.recorded_payer(&data)
+        }
+        None => None,
+    };
+    Ok((proposal_meta_payer, option_proposal_meta_payer))
+}
+
+/// Resolves the [govern::ProposalParams::lookup_instructions] of a proposal,
+/// returning them along with the lookup tables they reference.
+fn resolve_lookup_instructions<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal_params: &govern::ProposalParams,
+) -> Result<(Vec<smart_wallet::TXInstruction>, Vec<Pubkey>)> {
+    let mut lookup_tables: Vec<Pubkey> = vec![];
+    for lookup in proposal_params
+        .lookup_instructions
+        .iter()
+        .flat_map(|ix| ix.lookups.iter())
+    {
+        if !lookup_tables.contains(&lookup.table) {
+            lookup_tables.push(lookup.table);
+        }
+    }
+    let mut table_accounts = lookup_tables
+        .iter()
+        .map(|table| Ok((*table, program.rpc().get_account(table)?)))
+        .collect::<Result<Vec<_>>>()?;
+    let table_infos: Vec<AccountInfo> = table_accounts
+        .iter_mut()
+        .map(|(key, account)| (&*key, account).into_account_info())
+        .collect();
+    let instructions = proposal_params
+        .resolve_lookup_instructions(&table_infos)
+        .map_err(|err| anyhow::anyhow!("Cannot resolve lookup instructions: {}", err))?;
+    Ok((instructions, lookup_tables))
+}
+
+fn derive_proposal_params(proposal: Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"ProposalParams".as_ref(), proposal.as_ref()],
+        &govern::id(),
+    )
+    .0
+}
+
+/// The params of the proposal, unless it was created before proposals had them.
+fn find_proposal_params<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Option<Pubkey> {
+    let proposal_params = derive_proposal_params(proposal);
+    program
+        .rpc()
+        .get_account(&proposal_params)
+        .ok()
+        .map(|_| proposal_params)
+}
+
 fn main() -> Result<()> {
     let opts = Opts::parse();
     let payer =
@@ -51,6 +147,8 @@ fn main() -> Result<()> {
             voting_period,
             quorum_votes,
             timelock_delay_seconds,
+            approval_threshold_bps,
+            quorum_bps,
             voter_program,
         } => {
             create_governor(
@@ -60,21 +158,60 @@ fn main() -> Result<()> {
                 voting_period,
                 quorum_votes,
                 timelock_delay_seconds,
+                approval_threshold_bps,
+                quorum_bps,
                 voter_program,
             )?;
         }
-        CliCommand::CreateDummyProposal { base } => {
-            create_dummy_proposal(&program, base)?;
+        CliCommand::CreateDummyProposal {
+            base,
+            proposal_class,
+        } => {
+            create_dummy_proposal(&program, base, proposal_class)?;
         }
         CliCommand::CancelProposal { proposal } => {
             cancel_proposal(&program, proposal)?;
         }
+        CliCommand::CreateProposalParams { proposal } => {
+            create_proposal_params(&program, proposal)?;
+        }
+        CliCommand::CloseProposal { proposal } => {
+            close_proposal(&program, proposal)?;
+        }
+        CliCommand::CloseVotes { proposal } => {
+            close_votes(&program, proposal)?;
+        }
         CliCommand::QueueProposal { proposal } => {
             queue_proposal(&program, proposal)?;
         }
         CliCommand::NewVote { proposal } => {
             new_vote(&program, proposal)?;
         }
+        CliCommand::VetoProposal { proposal } => {
+            veto_proposal(&program, proposal)?;
+        }
+        CliCommand::SyncProposal { proposal } => {
+            sync_proposal(&program, proposal)?;
+        }
+        CliCommand::SettleProposalDeposit { proposal } => {
+            settle_proposal_deposit(&program, proposal)?;
+        }
+        CliCommand::TallyProposal { proposal } => {
+            tally_proposal(&program, proposal)?;
+        }
+        CliCommand::FundProposalReward {
+            proposal,
+            reward_mint,
+            amount,
+        } => {
+            fund_proposal_reward(&program, proposal, reward_mint, amount)?;
+        }
+        CliCommand::RefundProposalRewards { proposal } => {
+            refund_proposal_rewards(&program, proposal)?;
+        }
+        CliCommand::BatchClaimReward { proposal, voters } => {
+            batch_claim_reward(&program, proposal, voters)?;
+        }
         CliCommand::CreateProposalMeta {
             proposal,
             title,
@@ -108,6 +245,8 @@ fn main() -> Result<()> {
             voting_period,
             quorum_votes,
             timelock_delay_seconds,
+            approval_threshold_bps,
+            quorum_bps,
             voter_program,
         } => {
             verify(
@@ -117,6 +256,8 @@ fn main() -> Result<()> {
                 voting_period,
                 quorum_votes,
                 timelock_delay_seconds,
+                approval_threshold_bps,
+                quorum_bps,
                 voter_program,
             )
             .unwrap();
@@ -133,13 +274,15 @@ fn verify<C: Deref<Target = impl Signer> + Clone>(
     voting_period: u64,
     quorum_votes: u64,
     timelock_delay_seconds: i64,
+    approval_threshold_bps: u16,
+    quorum_bps: u16,
     voter_program: Pubkey,
 ) -> Result<()> {
     let (governor, _bump) =
         Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
     let governor_state: govern::Governor = program.account(governor)?;
 
-    let params = governor_state.params;
+    let params = governor_state.governance_params();
     println!("verify voting delay");
     assert_eq!(params.voting_delay, voting_delay);
     println!("verify voting period");
@@ -148,6 +291,10 @@ fn verify<C: Deref<Target = impl Signer> + Clone>(
     assert_eq!(params.quorum_votes, quorum_votes);
     println!("verify timelock_delay_seconds");
     assert_eq!(params.timelock_delay_seconds, timelock_delay_seconds);
+    println!("verify approval_threshold_bps");
+    assert_eq!(params.approval_threshold_bps, approval_threshold_bps);
+    println!("verify quorum_bps");
+    assert_eq!(params.quorum_bps, quorum_bps);
 
     let (smart_wallet, _bump) = Pubkey::find_program_address(
         &[b"SmartWallet".as_ref(), base.as_ref()],
@@ -169,6 +316,8 @@ fn create_governor<C: Deref<Target = impl Signer> + Clone>(
     voting_period: u64,
     quorum_votes: u64,
     timelock_delay_seconds: i64,
+    approval_threshold_bps: u16,
+    quorum_bps: u16,
     voter_program: Pubkey,
 ) -> Result<()> {
     let base = base_keypair.pubkey();
@@ -202,6 +351,8 @@ fn create_governor<C: Deref<Target = impl Signer> + Clone>(
                 voting_period,
                 quorum_votes,
                 timelock_delay_seconds,
+                approval_threshold_bps,
+                quorum_bps,
             },
         })
         .signer(&base_keypair);
@@ -219,12 +370,25 @@ fn create_governor<C: Deref<Target = impl Signer> + Clone>(
 fn create_dummy_proposal<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     base: Pubkey,
+    proposal_class: Option<String>,
 ) -> Result<()> {
     let (governor, _bump) =
         Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
 
     let governor_state: govern::Governor = program.account(governor)?;
 
+    let proposal_class = proposal_class.map(|name| {
+        Pubkey::find_program_address(
+            &[
+                b"ProposalClass".as_ref(),
+                governor.as_ref(),
+                name.as_bytes(),
+            ],
+            &govern::id(),
+        )
+        .0
+    });
+
     let (proposal, _bump) = Pubkey::find_program_address(
         &[
             b"Proposal".as_ref(),
@@ -237,17 +401,36 @@ fn create_dummy_proposal<C: Deref<Target = impl Signer> + Clone>(
 
     let (event_authority, _bump) = derive_event_authority_pda();
 
+    // escrow the proposal deposit from the payer's ATA, if the governor requires one
+    let proposal_deposit = governor_state.proposal_deposit;
+    let has_deposit = proposal_deposit.deposit_amount > 0;
+    let (deposit_vault, _bump) = Pubkey::find_program_address(
+        &[b"ProposalDeposit".as_ref(), proposal.as_ref()],
+        &govern::id(),
+    );
+
     let builder = program
         .request()
         .accounts(govern::accounts::CreateProposal {
             governor,
             proposal,
+            proposal_params: derive_proposal_params(proposal),
             smart_wallet: governor_state.smart_wallet,
             proposer: program.payer(),
             payer: program.payer(),
             event_authority,
             program: govern::ID,
             system_program: solana_program::system_program::ID,
+            locker: None,
+            deposit_mint: has_deposit.then_some(proposal_deposit.deposit_mint),
+            deposit_vault: has_deposit.then_some(deposit_vault),
+            proposer_token_account: has_deposit.then(|| {
+                get_associated_token_address(&program.payer(), &proposal_deposit.deposit_mint)
+            }),
+            token_program: has_deposit.then_some(anchor_spl::token::ID),
+            proposal_policy: (governor_state.proposal_policy != Pubkey::default())
+                .then_some(governor_state.proposal_policy),
+            proposal_class,
         })
         .args(govern::instruction::CreateProposal {
             proposal_type: 0,
@@ -266,6 +449,31 @@ fn cancel_proposal<C: Deref<Target = impl Signer> + Clone>(
     let proposal_state: govern::Proposal = program.account(proposal)?;
     let (event_authority, _bump) = derive_event_authority_pda();
 
+    let (proposal_meta, option_proposal_meta) = find_proposal_metas(program, proposal);
+    let (proposal_meta_payer, option_proposal_meta_payer) =
+        find_proposal_meta_payers(program, proposal_meta, option_proposal_meta)?;
+
+    // an unsettled deposit is slashed to the treasury
+    let has_deposit = proposal_state.has_unsettled_deposit();
+    let (deposit_vault, treasury) = if has_deposit {
+        let governor_state: govern::Governor = program.account(proposal_state.governor)?;
+        let (deposit_vault, _bump) = Pubkey::find_program_address(
+            &[b"ProposalDeposit".as_ref(), proposal.as_ref()],
+            &govern::id(),
+        );
+        let deposit_vault_state: anchor_spl::token::TokenAccount =
+            program.account(deposit_vault)?;
+        (
+            Some(deposit_vault),
+            Some(get_associated_token_address(
+                &governor_state.smart_wallet,
+                &deposit_vault_state.mint,
+            )),
+        )
+    } else {
+        (None, None)
+    };
+
     let builder = program
         .request()
         .accounts(govern::accounts::CancelProposal {
@@ -274,6 +482,15 @@ fn cancel_proposal<C: Deref<Target = impl Signer> + Clone>(
             event_authority,
             program: govern::ID,
             proposer: program.payer(),
+            payer: proposal_state.rent_payer(),
+            deposit_vault,
+            treasury,
+            token_program: has_deposit.then_some(anchor_spl::token::ID),
+            proposal_meta,
+            proposal_meta_payer,
+            option_proposal_meta,
+            option_proposal_meta_payer,
+            proposal_params: find_proposal_params(program, proposal),
         })
         .args(govern::instruction::CancelProposal {});
     let signature = builder.send()?;
@@ -281,12 +498,189 @@ fn cancel_proposal<C: Deref<Target = impl Signer> + Clone>(
     Ok(())
 }
 
+fn create_proposal_params<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let builder = program
+        .request()
+        .accounts(govern::accounts::CreateProposalParams {
+            governor: proposal_state.governor,
+            proposal,
+            proposal_params: derive_proposal_params(proposal),
+            payer: program.payer(),
+            system_program: solana_program::system_program::ID,
+            event_authority,
+            program: govern::ID,
+        })
+        .args(govern::instruction::CreateProposalParams {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn close_proposal<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let (proposal_meta, option_proposal_meta) = find_proposal_metas(program, proposal);
+    let (proposal_meta_payer, option_proposal_meta_payer) =
+        find_proposal_meta_payers(program, proposal_meta, option_proposal_meta)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    // the voting reward which was not claimed is swept to the treasury
+    let has_unswept_reward = proposal_state.unswept_reward().unwrap_or_default() > 0;
+    let (governor, reward_vault, treasury) = if has_unswept_reward {
+        let governor_state: govern::Governor = program.account(proposal_state.governor)?;
+        (
+            Some(proposal_state.governor),
+            Some(proposal_state.voting_reward.reward_vault),
+            Some(get_associated_token_address(
+                &governor_state.smart_wallet,
+                &proposal_state.voting_reward.reward_mint,
+            )),
+        )
+    } else {
+        (None, None, None)
+    };
+
+    let builder = program
+        .request()
+        .accounts(govern::accounts::CloseProposal {
+            proposal,
+            payer: proposal_state.rent_payer(),
+            proposer: proposal_state.proposer,
+            proposal_meta,
+            proposal_meta_payer,
+            option_proposal_meta,
+            option_proposal_meta_payer,
+            proposal_params: find_proposal_params(program, proposal),
+            governor,
+            reward_vault,
+            treasury,
+            token_program: has_unswept_reward.then_some(anchor_spl::token::ID),
+            event_authority,
+            program: govern::ID,
+        })
+        .args(govern::instruction::CloseProposal {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn close_votes<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let votes = accounts_with_rpc_timeout::<C, govern::Vote>(
+        program,
+        Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            &proposal.to_bytes(),
+        ))]),
+    )?;
+    for (vote, vote_state) in votes {
+        let builder = program
+            .request()
+            .accounts(govern::accounts::CloseVote {
+                proposal,
+                vote,
+                voter: vote_state.voter,
+            })
+            .args(govern::instruction::CloseVote {});
+        match builder.send() {
+            Ok(signature) => println!("Closed vote {} signature {:?}", vote, signature),
+            Err(err) => println!("Cannot close vote {}: {}", vote, err),
+        }
+    }
+    Ok(())
+}
+
 fn queue_proposal<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     proposal: Pubkey,
 ) -> Result<()> {
     let proposal_state: govern::Proposal = program.account(proposal)?;
     let governor_state: govern::Governor = program.account(proposal_state.governor)?;
+    let option_instructions = if proposal_state.proposal_type != 0
+        && proposal_state.has_option_instructions(proposal_state.winning_option)
+    {
+        let (option_instructions, _bump) = Pubkey::find_program_address(
+            &[
+                b"OptionInstructions".as_ref(),
+                proposal.as_ref(),
+                &[proposal_state.winning_option],
+            ],
+            &govern::id(),
+        );
+        Some(option_instructions)
+    } else {
+        None
+    };
+
+    // instructions too large to be queued at once are first buffered in chunks
+    let option_bundle: Option<govern::OptionInstructions> = option_instructions
+        .map(|option_instructions| program.account(option_instructions))
+        .transpose()?;
+    let proposal_params: govern::ProposalParams =
+        program.account(derive_proposal_params(proposal))?;
+    let (lookup_instructions, lookup_tables) =
+        resolve_lookup_instructions(program, &proposal_params)?;
+    let mut instructions = proposal_state.to_smart_wallet_instructions(option_bundle.as_ref());
+    instructions.extend(lookup_instructions);
+    let space: usize = instructions.iter().map(|ix| ix.space()).sum();
+    let transaction_buffer = if space > govern::MAX_INSTRUCTIONS_CHUNK_SPACE {
+        let (transaction_buffer, _bump) = Pubkey::find_program_address(
+            &[
+                b"TransactionBuffer".as_ref(),
+                governor_state.smart_wallet.as_ref(),
+                proposal_state.governor.as_ref(),
+                proposal.as_ref(),
+            ],
+            &smart_wallet::id(),
+        );
+        loop {
+            let buffered_count = program
+                .account::<smart_wallet::TransactionBuffer>(transaction_buffer)
+                .map(|buffer| buffer.instructions.len())
+                .unwrap_or(0);
+            if buffered_count == instructions.len() {
+                break;
+            }
+            println!(
+                "append queued instructions {}/{}",
+                buffered_count,
+                instructions.len()
+            );
+            let mut builder = program
+                .request()
+                .accounts(govern::accounts::AppendQueuedInstructions {
+                    governor: proposal_state.governor,
+                    proposal,
+                    proposal_params: derive_proposal_params(proposal),
+                    transaction_buffer,
+                    smart_wallet: governor_state.smart_wallet,
+                    payer: program.payer(),
+                    option_instructions,
+                    smart_wallet_program: smart_wallet::id(),
+                    system_program: solana_program::system_program::ID,
+                })
+                .args(govern::instruction::AppendQueuedInstructions {});
+            for lookup_table in lookup_tables.iter() {
+                builder = builder.accounts(AccountMeta::new_readonly(*lookup_table, false));
+            }
+            let signature = builder.send()?;
+            println!("Signature {:?}", signature);
+        }
+        Some(transaction_buffer)
+    } else {
+        None
+    };
+
     let smart_wallet_state: smart_wallet::SmartWallet =
         program.account(governor_state.smart_wallet)?;
     let (transaction, _bump) = Pubkey::find_program_address(
@@ -298,13 +692,16 @@ fn queue_proposal<C: Deref<Target = impl Signer> + Clone>(
         &smart_wallet::id(),
     );
     let (event_authority, _bump) = derive_event_authority_pda();
-    let builder = program
+    let mut builder = program
         .request()
         .accounts(govern::accounts::QueueProposal {
             governor: proposal_state.governor,
             proposal,
+            proposal_params: derive_proposal_params(proposal),
             transaction,
             smart_wallet: governor_state.smart_wallet,
+            option_instructions,
+            transaction_buffer,
             smart_wallet_program: smart_wallet::id(),
             payer: program.payer(),
             event_authority,
@@ -312,8 +709,12 @@ fn queue_proposal<C: Deref<Target = impl Signer> + Clone>(
             system_program: solana_program::system_program::ID,
         })
         .args(govern::instruction::QueueProposal {});
+    for lookup_table in lookup_tables.iter() {
+        builder = builder.accounts(AccountMeta::new_readonly(*lookup_table, false));
+    }
     let signature = builder.send()?;
     println!("Signature {:?}", signature);
+
     Ok(())
 }
 
@@ -345,6 +746,314 @@ fn new_vote<C: Deref<Target = impl Signer> + Clone>(
     Ok(())
 }
 
+fn veto_proposal<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let governor_state: govern::Governor = program.account(proposal_state.governor)?;
+    let transaction = if proposal_state.queued_at > 0 {
+        Some(proposal_state.queued_transaction)
+    } else {
+        None
+    };
+    // the rent of the queued transaction returns to its payer
+    let transaction_payer = match transaction {
+        Some(transaction) => {
+            let transaction_state: smart_wallet::Transaction = program.account(transaction)?;
+            let smart_wallet_state: smart_wallet::SmartWallet =
+                program.account(governor_state.smart_wallet)?;
+            let data = program.rpc().get_account_data(&transaction)?;
+            Some(
+                transaction_state
+                    .recorded_payer(&data, smart_wallet_state.max_owners as usize)
+                    .unwrap_or(transaction_state.proposer),
+            )
+        }
+        None => None,
+    };
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let builder = program
+        .request()
+        .accounts(govern::accounts::VetoProposal {
+            governor: proposal_state.governor,
+            proposal,
+            veto_authority: program.payer(),
+            transaction,
+            transaction_payer,
+            smart_wallet: governor_state.smart_wallet,
+            smart_wallet_program: smart_wallet::id(),
+            event_authority,
+            program: govern::ID,
+        })
+        .args(govern::instruction::VetoProposal {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn sync_proposal<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let governor_state: govern::Governor = program.account(proposal_state.governor)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let builder = program
+        .request()
+        .accounts(govern::accounts::SyncProposal {
+            governor: proposal_state.governor,
+            proposal,
+            transaction: proposal_state.queued_transaction,
+            smart_wallet: governor_state.smart_wallet,
+            event_authority,
+            program: govern::ID,
+        })
+        .args(govern::instruction::SyncProposal {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn tally_proposal<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let (event_authority, _bump) = derive_event_authority_pda();
+    loop {
+        let proposal_state: govern::Proposal = program.account(proposal)?;
+        if proposal_state.tallied_at > 0 {
+            println!("winning option {}", proposal_state.winning_option);
+            let (option_proposal_meta, _bump) = Pubkey::find_program_address(
+                &[b"OptionProposalMeta".as_ref(), proposal.as_ref()],
+                &govern::id(),
+            );
+            if let Ok(meta) = program.account::<govern::OptionProposalMeta>(option_proposal_meta) {
+                if let Some(description) = meta.winning_option_description(&proposal_state) {
+                    println!("winning option description {}", description);
+                }
+            }
+            return Ok(());
+        }
+
+        // votes of eliminated ranked-choice options are transferred in chunks
+        let votes: Vec<Pubkey> = accounts_with_rpc_timeout::<C, govern::Vote>(
+            program,
+            Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+                8,
+                &proposal.to_bytes(),
+            ))]),
+        )?
+        .into_iter()
+        .filter(|(_, vote)| vote.side != 0 && proposal_state.is_eliminated(vote.side))
+        .map(|(key, _)| key)
+        .collect();
+        let chunks: Vec<&[Pubkey]> = if votes.is_empty() {
+            vec![&[]]
+        } else {
+            votes.chunks(20).collect()
+        };
+
+        for chunk in chunks {
+            let mut accounts = govern::accounts::TallyProposal {
+                proposal,
+                event_authority,
+                program: govern::ID,
+            }
+            .to_account_metas(None);
+            accounts.extend(chunk.iter().map(|vote| AccountMeta::new(*vote, false)));
+
+            let builder = program
+                .request()
+                .accounts(accounts)
+                .args(govern::instruction::TallyProposal {});
+            let signature = builder.send()?;
+            println!("Signature {:?}", signature);
+        }
+    }
+}
+
+fn settle_proposal_deposit<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let governor_state: govern::Governor = program.account(proposal_state.governor)?;
+    let (deposit_vault, _bump) = Pubkey::find_program_address(
+        &[b"ProposalDeposit".as_ref(), proposal.as_ref()],
+        &govern::id(),
+    );
+    let deposit_vault_state: anchor_spl::token::TokenAccount = program.account(deposit_vault)?;
+    let deposit_mint = deposit_vault_state.mint;
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let builder = program
+        .request()
+        .accounts(govern::accounts::SettleProposalDeposit {
+            governor: proposal_state.governor,
+            proposal,
+            deposit_vault,
+            proposer: proposal_state.proposer,
+            proposer_token_account: get_associated_token_address(
+                &proposal_state.proposer,
+                &deposit_mint,
+            ),
+            treasury: get_associated_token_address(&governor_state.smart_wallet, &deposit_mint),
+            token_program: anchor_spl::token::ID,
+            event_authority,
+            program: govern::ID,
+        })
+        .args(govern::instruction::SettleProposalDeposit {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn fund_proposal_reward<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+    reward_mint: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let (proposal_reward, _bump) = Pubkey::find_program_address(
+        &[
+            b"ProposalReward".as_ref(),
+            proposal.as_ref(),
+            reward_mint.as_ref(),
+        ],
+        &govern::id(),
+    );
+    let (reward_vault, _bump) = Pubkey::find_program_address(
+        &[b"ProposalRewardVault".as_ref(), proposal_reward.as_ref()],
+        &govern::id(),
+    );
+
+    let (event_authority, _bump) = derive_event_authority_pda();
+
+    let mut builder = program.request();
+    if program.rpc().get_account(&proposal_reward).is_err() {
+        println!("create proposal reward {}", proposal_reward);
+        builder = builder.instruction(Instruction {
+            program_id: govern::ID,
+            accounts: govern::accounts::CreateProposalReward {
+                governor: proposal_state.governor,
+                proposal,
+                proposal_reward,
+                reward_mint,
+                reward_vault,
+                payer: program.payer(),
+                system_program: solana_program::system_program::ID,
+                token_program: anchor_spl::token::ID,
+                event_authority,
+                program: govern::ID,
+            }
+            .to_account_metas(None),
+            data: govern::instruction::CreateProposalReward {}.data(),
+        });
+    }
+    let builder = builder
+        .accounts(govern::accounts::FundProposalReward {
+            proposal,
+            proposal_reward,
+            reward_vault,
+            funder: program.payer(),
+            funder_token_account: get_associated_token_address(&program.payer(), &reward_mint),
+            token_program: anchor_spl::token::ID,
+            event_authority,
+            program: govern::ID,
+        })
+        .args(govern::instruction::FundProposalReward { amount });
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn refund_proposal_rewards<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let proposal_rewards = accounts_with_rpc_timeout::<C, govern::ProposalReward>(
+        program,
+        Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            &proposal.to_bytes(),
+        ))]),
+    )?;
+    for (proposal_reward, reward_state) in proposal_rewards {
+        let builder = program
+            .request()
+            .accounts(govern::accounts::RefundProposalReward {
+                governor: proposal_state.governor,
+                proposal,
+                proposal_reward,
+                reward_vault: reward_state.reward_vault,
+                sponsor: reward_state.sponsor,
+                sponsor_token_account: get_associated_token_address(
+                    &reward_state.sponsor,
+                    &reward_state.reward_mint,
+                ),
+                token_program: anchor_spl::token::ID,
+                event_authority,
+                program: govern::ID,
+            })
+            .args(govern::instruction::RefundProposalReward {});
+        match builder.send() {
+            Ok(signature) => println!(
+                "Refunded reward {} signature {:?}",
+                proposal_reward, signature
+            ),
+            Err(err) => println!("Cannot refund reward {}: {}", proposal_reward, err),
+        }
+    }
+    Ok(())
+}
+
+fn batch_claim_reward<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    proposal: Pubkey,
+    voters: Vec<Pubkey>,
+) -> Result<()> {
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let reward_mint = proposal_state.voting_reward.reward_mint;
+
+    let mut remaining_accounts = vec![];
+    for voter in voters.iter() {
+        let (vote, _bump) = Pubkey::find_program_address(
+            &[b"Vote".as_ref(), proposal.as_ref(), voter.as_ref()],
+            &govern::id(),
+        );
+        remaining_accounts.push(AccountMeta::new(vote, false));
+        remaining_accounts.push(AccountMeta::new(
+            get_associated_token_address(voter, &reward_mint),
+            false,
+        ));
+    }
+
+    let (event_authority, _bump) = derive_event_authority_pda();
+    let mut accounts = govern::accounts::BatchClaimReward {
+        governor: proposal_state.governor,
+        reward_vault: proposal_state.voting_reward.reward_vault,
+        proposal,
+        claimer: program.payer(),
+        token_program: anchor_spl::token::ID,
+        event_authority,
+        program: govern::ID,
+    }
+    .to_account_metas(None);
+    accounts.append(&mut remaining_accounts);
+
+    let builder = program
+        .request()
+        .accounts(accounts)
+        .args(govern::instruction::BatchClaimReward {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
 fn create_proposal_meta<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     proposal: Pubkey,
@@ -395,6 +1104,8 @@ fn view_proposal<C: Deref<Target = impl Signer> + Clone>(
 ) -> Result<()> {
     let state: govern::Proposal = program.account(proposal)?;
     println!("{:?}", state);
+    let now = program.rpc().get_block_time(program.rpc().get_slot()?)?;
+    println!("proposal state {:?}", state.state(now));
     Ok(())
 }
 fn view_proposal_meta<C: Deref<Target = impl Signer> + Clone>(
@@ -76,6 +76,24 @@ pub enum CliCommand {
         #[clap(long)]
         base: Pubkey,
     },
+    /// Creates the slope changes tracking the total voting power of a locker created before they existed.
+    NewLockerSlopeChanges {
+        #[clap(long)]
+        base: Pubkey,
+    },
+    /// Counts the escrows created before the total voting power was tracked in it.
+    SyncEscrowsVotingPower {
+        #[clap(long)]
+        base: Pubkey,
+    },
+    /// Creates the voting power checkpoints of an escrow created before they existed.
+    NewEscrowCheckpoints {
+        #[clap(long)]
+        base: Pubkey,
+        /// Owner of the escrow, defaults to the wallet.
+        #[clap(long)]
+        owner: Option<Pubkey>,
+    },
     IncreaseLockedAmount {
         #[clap(long)]
         base: Pubkey,
@@ -105,6 +123,34 @@ pub enum CliCommand {
         proposal: Pubkey,
         #[clap(long)]
         side: u8,
+        /// Owner of the escrow voted for, if the wallet is one of its vote delegates.
+        #[clap(long)]
+        escrow_owner: Option<Pubkey>,
+        /// Index of the partial vote delegate slot of the wallet, to vote with its share.
+        #[clap(long)]
+        partial_vote_delegate: Option<u8>,
+    },
+    /// Signs a vote off-chain with the wallet, to be cast by a relayer with CastSignedVotes.
+    SignVote {
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        side: u8,
+        /// Must be greater than the nonce of the last signed vote cast for the escrow.
+        #[clap(long)]
+        nonce: u64,
+        /// Owner of the escrow voted for, if the wallet is its vote delegate.
+        #[clap(long)]
+        escrow_owner: Option<Pubkey>,
+    },
+    /// Casts votes signed off-chain, given as ESCROW_OWNER:SIDE:NONCE:SIGNATURE.
+    CastSignedVotes {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        votes: Vec<String>,
     },
     SetVoteDelegate {
         #[clap(long)]
@@ -112,6 +158,70 @@ pub enum CliCommand {
         #[clap(long)]
         new_delegate: Pubkey,
     },
+    /// Delegates a share of the escrow voting power in bps; the vote delegate votes with the rest.
+    SetPartialVoteDelegate {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        index: u8,
+        #[clap(long)]
+        delegate: Pubkey,
+        #[clap(long)]
+        weight_bps: u16,
+    },
+    /// Withdraws the rewards and rent held by the voter of a partial vote delegate slot
+    /// of the escrow of the wallet, after claiming them with claim_reward_for_voter.
+    WithdrawPartialVoteRewards {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        index: u8,
+        /// Mint of the rewards to withdraw. Only the rent is withdrawn if not given.
+        #[clap(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Creates a delegate with the wallet as its authority; escrows delegate to it with
+    /// SetVoteDelegate, giving the delegate address.
+    NewDelegate {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        name: String,
+        #[clap(long)]
+        url: String,
+    },
+    SetDelegateProfile {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        name: String,
+        #[clap(long)]
+        url: String,
+    },
+    /// Withdraws the rewards and rent held by the delegate of the wallet, after claiming
+    /// them with claim_reward_for_voter.
+    WithdrawDelegateRewards {
+        #[clap(long)]
+        base: Pubkey,
+        /// Mint of the rewards to withdraw. Only the rent is withdrawn if not given.
+        #[clap(long)]
+        mint: Option<Pubkey>,
+    },
+    /// Lists the delegates of a locker with their profiles.
+    ListDelegates {
+        #[clap(long)]
+        base: Pubkey,
+    },
+    /// Casts the vote of the delegate of the wallet, then adds the voting power of the
+    /// escrows delegating to it.
+    CastDelegateVote {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        side: u8,
+    },
     ViewLocker {
         #[clap(long)]
         base: Pubkey,
@@ -10,13 +10,13 @@ use anchor_lang::ToAccountMetas;
 use anchor_spl::associated_token::get_associated_token_address;
 use anyhow::Result;
 use clap::*;
-use solana_program::instruction::Instruction;
+use solana_program::instruction::{AccountMeta, Instruction};
 use solana_rpc_client_api::filter::Memcmp;
 use solana_rpc_client_api::filter::RpcFilterType;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
-use utils_cli::accounts_with_rpc_timeout;
+use utils_cli::{accounts_with_rpc_timeout, new_ed25519_instruction, parse_signed_vote};
 
 fn main() -> Result<()> {
     let opts = Opts::parse();
@@ -67,6 +67,40 @@ fn main() -> Result<()> {
             );
             new_escrow(&program, locker)?;
         }
+        CliCommand::NewLockerSlopeChanges { base } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            let builder = program
+                .request()
+                .instruction(new_locker_slope_changes_ix(&program, locker));
+            let signature = builder.send()?;
+            println!("Signature {:?}", signature);
+        }
+        CliCommand::SyncEscrowsVotingPower { base } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            sync_escrows_voting_power(&program, locker)?;
+        }
+        CliCommand::NewEscrowCheckpoints { base, owner } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            let owner = owner.unwrap_or(wallet);
+            let (escrow, _bump) = Pubkey::find_program_address(
+                &[b"Escrow".as_ref(), locker.as_ref(), owner.as_ref()],
+                &locked_voter::id(),
+            );
+            let builder = program
+                .request()
+                .instruction(new_escrow_checkpoints_ix(&program, escrow));
+            let signature = builder.send()?;
+            println!("Signature {:?}", signature);
+        }
         CliCommand::IncreaseLockedAmount { base, amount } => {
             let (locker, _bump) = Pubkey::find_program_address(
                 &[b"Locker".as_ref(), base.as_ref()],
@@ -99,12 +133,48 @@ fn main() -> Result<()> {
             base,
             proposal,
             side,
+            escrow_owner,
+            partial_vote_delegate,
         } => {
             let (locker, _bump) = Pubkey::find_program_address(
                 &[b"Locker".as_ref(), base.as_ref()],
                 &locked_voter::id(),
             );
-            cast_vote(&program, locker, proposal, side)?;
+            cast_vote(
+                &program,
+                locker,
+                proposal,
+                side,
+                escrow_owner.unwrap_or(wallet),
+                partial_vote_delegate,
+            )?;
+        }
+        CliCommand::SignVote {
+            proposal,
+            side,
+            nonce,
+            escrow_owner,
+        } => {
+            let message = govern::signed_vote_message(&proposal, side, nonce);
+            let signature = payer.sign_message(&message);
+            println!(
+                "{}:{}:{}:{}",
+                escrow_owner.unwrap_or(wallet),
+                side,
+                nonce,
+                signature
+            );
+        }
+        CliCommand::CastSignedVotes {
+            base,
+            proposal,
+            votes,
+        } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            cast_signed_votes(&program, locker, proposal, votes)?;
         }
         CliCommand::SetVoteDelegate { base, new_delegate } => {
             let (locker, _bump) = Pubkey::find_program_address(
@@ -113,13 +183,84 @@ fn main() -> Result<()> {
             );
             set_vote_delegate(&program, locker, new_delegate)?;
         }
+        CliCommand::SetPartialVoteDelegate {
+            base,
+            index,
+            delegate,
+            weight_bps,
+        } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            set_partial_vote_delegate(&program, locker, index, delegate, weight_bps)?;
+        }
+        CliCommand::WithdrawPartialVoteRewards { base, index, mint } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            withdraw_partial_vote_rewards(&program, locker, index, mint)?;
+        }
+        CliCommand::NewDelegate { base, name, url } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            new_delegate(&program, locker, name, url)?;
+        }
+        CliCommand::SetDelegateProfile { base, name, url } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            set_delegate_profile(&program, locker, name, url)?;
+        }
+        CliCommand::WithdrawDelegateRewards { base, mint } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            withdraw_delegate_rewards(&program, locker, mint)?;
+        }
+        CliCommand::ListDelegates { base } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            list_delegates(&program, locker)?;
+        }
+        CliCommand::CastDelegateVote {
+            base,
+            proposal,
+            side,
+        } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &locked_voter::id(),
+            );
+            cast_delegate_vote(&program, locker, proposal, side)?;
+        }
         CliCommand::ViewLocker { base } => {
             let (locker, _bump) = Pubkey::find_program_address(
                 &[b"Locker".as_ref(), base.as_ref()],
                 &locked_voter::id(),
             );
+            let (slope_changes, _bump) = Pubkey::find_program_address(
+                &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+                &locked_voter::id(),
+            );
             let locker: locked_voter::Locker = program.account(locker)?;
             println!("{:?}", locker);
+            if let Ok(slope_changes) =
+                program.account::<locked_voter::LockerSlopeChanges>(slope_changes)
+            {
+                let now = program.rpc().get_block_time(program.rpc().get_slot()?)?;
+                println!(
+                    "total voting power {:?}",
+                    locker.total_voting_power_at_time(&slope_changes, now)
+                );
+            }
         }
         CliCommand::ViewEscrow { base, owner } => {
             let (locker, _bump) = Pubkey::find_program_address(
@@ -230,6 +371,7 @@ fn new_locker<C: Deref<Target = impl Signer> + Clone>(
                 proposal_activation_min_votes,
             },
         })
+        .instruction(new_locker_slope_changes_ix(program, locker))
         .signer(&base_keypair);
     let signature = builder.send()?;
     println!("Signature {:?}", signature);
@@ -249,7 +391,12 @@ fn new_escrow<C: Deref<Target = impl Signer> + Clone>(
         &locked_voter::id(),
     );
 
-    let builder = program
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+
+    let mut builder = program
         .request()
         .accounts(locked_voter::accounts::NewEscrow {
             locker,
@@ -259,11 +406,57 @@ fn new_escrow<C: Deref<Target = impl Signer> + Clone>(
             system_program: solana_program::system_program::ID,
         })
         .args(locked_voter::instruction::NewEscrow {});
+    // checkpoints outlive the escrow, so a reopened escrow keeps its history
+    if program.rpc().get_account_data(&escrow_checkpoints).is_err() {
+        builder = builder.instruction(new_escrow_checkpoints_ix(program, escrow));
+    }
     let signature = builder.send()?;
     println!("Signature {:?}", signature);
     Ok(())
 }
 
+fn new_locker_slope_changes_ix<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+) -> Instruction {
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &locked_voter::id(),
+    );
+    Instruction {
+        program_id: locked_voter::id(),
+        accounts: locked_voter::accounts::NewLockerSlopeChanges {
+            locker,
+            slope_changes,
+            payer: program.payer(),
+            system_program: solana_program::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: locked_voter::instruction::NewLockerSlopeChanges {}.data(),
+    }
+}
+
+fn new_escrow_checkpoints_ix<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    escrow: Pubkey,
+) -> Instruction {
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+    Instruction {
+        program_id: locked_voter::id(),
+        accounts: locked_voter::accounts::NewEscrowCheckpoints {
+            escrow,
+            escrow_checkpoints,
+            payer: program.payer(),
+            system_program: solana_program::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: locked_voter::instruction::NewEscrowCheckpoints {}.data(),
+    }
+}
+
 fn increase_locked_amount<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     locker: Pubkey,
@@ -278,6 +471,14 @@ fn increase_locked_amount<C: Deref<Target = impl Signer> + Clone>(
         ],
         &locked_voter::id(),
     );
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &locked_voter::id(),
+    );
     let escrow_tokens = get_associated_token_address(&escrow, &locker_state.token_mint);
 
     let source_tokens = get_associated_token_address(&program.payer(), &locker_state.token_mint);
@@ -287,6 +488,8 @@ fn increase_locked_amount<C: Deref<Target = impl Signer> + Clone>(
         .accounts(locked_voter::accounts::IncreaseLockedAmount {
             locker,
             escrow,
+            escrow_checkpoints,
+            slope_changes,
             escrow_tokens,
             source_tokens,
             payer: program.payer(),
@@ -311,12 +514,22 @@ fn extend_locked_duration<C: Deref<Target = impl Signer> + Clone>(
         ],
         &locked_voter::id(),
     );
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &locked_voter::id(),
+    );
 
     let builder = program
         .request()
         .accounts(locked_voter::accounts::ExtendLockDuration {
             locker,
             escrow,
+            escrow_checkpoints,
+            slope_changes,
             escrow_owner: program.payer(),
         })
         .args(locked_voter::instruction::ExtendLockDuration { duration });
@@ -338,6 +551,14 @@ fn toggle_max_lock<C: Deref<Target = impl Signer> + Clone>(
         ],
         &locked_voter::id(),
     );
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &locked_voter::id(),
+    );
 
     let is_max_lock = if is_max_lock == 0 { false } else { true };
     let builder = program
@@ -345,6 +566,8 @@ fn toggle_max_lock<C: Deref<Target = impl Signer> + Clone>(
         .accounts(locked_voter::accounts::ToggleMaxLock {
             locker,
             escrow,
+            escrow_checkpoints,
+            slope_changes,
             escrow_owner: program.payer(),
         })
         .args(locked_voter::instruction::ToggleMaxLock { is_max_lock });
@@ -366,6 +589,14 @@ fn withdraw<C: Deref<Target = impl Signer> + Clone>(
         ],
         &locked_voter::id(),
     );
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &locked_voter::id(),
+    );
     let escrow_tokens = get_associated_token_address(&escrow, &locker_state.token_mint);
 
     let destination_tokens =
@@ -376,6 +607,8 @@ fn withdraw<C: Deref<Target = impl Signer> + Clone>(
         .accounts(locked_voter::accounts::Withdraw {
             locker,
             escrow,
+            escrow_checkpoints,
+            slope_changes,
             escrow_tokens,
             destination_tokens,
             escrow_owner: program.payer(),
@@ -393,24 +626,35 @@ fn cast_vote<C: Deref<Target = impl Signer> + Clone>(
     locker: Pubkey,
     proposal: Pubkey,
     side: u8,
+    escrow_owner: Pubkey,
+    partial_vote_delegate: Option<u8>,
 ) -> Result<()> {
     let locker_state: locked_voter::Locker = program.account(locker)?;
     let (escrow, _bump) = Pubkey::find_program_address(
-        &[
-            b"Escrow".as_ref(),
-            locker.as_ref(),
-            program.payer().as_ref(),
-        ],
+        &[b"Escrow".as_ref(), locker.as_ref(), escrow_owner.as_ref()],
+        &locked_voter::id(),
+    );
+    // partial vote delegates vote as their own voter
+    let voter = match partial_vote_delegate {
+        Some(index) => locked_voter::Escrow::partial_vote_voter(&escrow, index.into()),
+        None => escrow_owner,
+    };
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
         &locked_voter::id(),
     );
 
     let (vote, _bump) = Pubkey::find_program_address(
+        &[b"Vote".as_ref(), proposal.as_ref(), voter.as_ref()],
+        &govern::id(),
+    );
+    let (delegated_vote, _bump) = Pubkey::find_program_address(
         &[
-            b"Vote".as_ref(),
+            b"DelegatedVote".as_ref(),
             proposal.as_ref(),
-            program.payer().as_ref(),
+            escrow.as_ref(),
         ],
-        &govern::id(),
+        &locked_voter::id(),
     );
     let mut instructions = vec![];
     if program.rpc().get_account_data(&vote).is_err() {
@@ -423,24 +667,39 @@ fn cast_vote<C: Deref<Target = impl Signer> + Clone>(
                 system_program: solana_program::system_program::ID,
             }
             .to_account_metas(None),
-            data: govern::instruction::NewVote {
-                voter: program.payer(),
-            }
-            .data(),
+            data: govern::instruction::NewVote { voter }.data(),
         });
     }
+    let mut accounts = locked_voter::accounts::CastVote {
+        locker,
+        escrow,
+        escrow_checkpoints,
+        vote,
+        delegated_vote,
+        proposal,
+        vote_delegate: program.payer(),
+        governor: locker_state.governor,
+        govern_program: govern::ID,
+        system_program: solana_program::system_program::ID,
+    }
+    .to_account_metas(None);
+    // an owner voting over their delegate removes what was added to the delegate vote
+    if let Ok(delegated_vote) = program.account::<locked_voter::DelegatedVote>(delegated_vote) {
+        if program.payer() == escrow_owner && delegated_vote.voting_power > 0 {
+            let (delegate_vote, _bump) = Pubkey::find_program_address(
+                &[
+                    b"Vote".as_ref(),
+                    proposal.as_ref(),
+                    delegated_vote.delegate.as_ref(),
+                ],
+                &govern::id(),
+            );
+            accounts.push(AccountMeta::new(delegate_vote, false));
+        }
+    }
     instructions.push(Instruction {
         program_id: locked_voter::id(),
-        accounts: locked_voter::accounts::CastVote {
-            locker,
-            escrow,
-            vote,
-            proposal,
-            vote_delegate: program.payer(),
-            governor: locker_state.governor,
-            govern_program: govern::ID,
-        }
-        .to_account_metas(None),
+        accounts,
         data: locked_voter::instruction::CastVote { side }.data(),
     });
 
@@ -484,6 +743,77 @@ fn set_vote_delegate<C: Deref<Target = impl Signer> + Clone>(
     Ok(())
 }
 
+fn set_partial_vote_delegate<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    index: u8,
+    delegate: Pubkey,
+    weight_bps: u16,
+) -> Result<()> {
+    let (escrow, _bump) = Pubkey::find_program_address(
+        &[
+            b"Escrow".as_ref(),
+            locker.as_ref(),
+            program.payer().as_ref(),
+        ],
+        &locked_voter::id(),
+    );
+    let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+        &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+        &locked_voter::id(),
+    );
+
+    let builder = program
+        .request()
+        .accounts(locked_voter::accounts::SetPartialVoteDelegate {
+            escrow,
+            escrow_checkpoints,
+            escrow_owner: program.payer(),
+        })
+        .args(locked_voter::instruction::SetPartialVoteDelegate {
+            index,
+            delegate,
+            weight_bps,
+        });
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn withdraw_partial_vote_rewards<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    index: u8,
+    mint: Option<Pubkey>,
+) -> Result<()> {
+    let (escrow, _bump) = Pubkey::find_program_address(
+        &[
+            b"Escrow".as_ref(),
+            locker.as_ref(),
+            program.payer().as_ref(),
+        ],
+        &locked_voter::id(),
+    );
+    let partial_vote_voter = locked_voter::Escrow::partial_vote_voter(&escrow, index.into());
+
+    let builder = program
+        .request()
+        .accounts(locked_voter::accounts::WithdrawPartialVoteRewards {
+            escrow,
+            escrow_owner: program.payer(),
+            partial_vote_voter,
+            voter_tokens: mint.map(|mint| get_associated_token_address(&partial_vote_voter, &mint)),
+            destination_tokens: mint
+                .map(|mint| get_associated_token_address(&program.payer(), &mint)),
+            token_program: anchor_spl::token::ID,
+            system_program: solana_program::system_program::ID,
+        })
+        .args(locked_voter::instruction::WithdrawPartialVoteRewards { index });
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
 fn get_stakers<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     locker: Pubkey,
@@ -504,3 +834,342 @@ fn get_stakers<C: Deref<Target = impl Signer> + Clone>(
 
     Ok(())
 }
+
+fn sync_escrows_voting_power<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+) -> Result<()> {
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &locked_voter::id(),
+    );
+    let program_accounts = accounts_with_rpc_timeout::<C, locked_voter::Escrow>(
+        program,
+        Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            &locker.to_bytes(),
+        ))]),
+    )?;
+    let escrows: Vec<Pubkey> = program_accounts
+        .into_iter()
+        .filter(|(_, escrow)| !escrow.is_tracked)
+        .map(|(key, _)| key)
+        .collect();
+    println!("Found {} escrows to sync", escrows.len());
+
+    for chunk in escrows.chunks(20) {
+        let mut accounts = locked_voter::accounts::SyncEscrowsVotingPower {
+            locker,
+            slope_changes,
+        }
+        .to_account_metas(None);
+        accounts.extend(chunk.iter().map(|escrow| AccountMeta::new(*escrow, false)));
+        let builder = program.request().instruction(Instruction {
+            program_id: locked_voter::id(),
+            accounts,
+            data: locked_voter::instruction::SyncEscrowsVotingPower {}.data(),
+        });
+        let signature = builder.send()?;
+        println!("Signature {:?}", signature);
+    }
+    Ok(())
+}
+
+fn cast_signed_votes<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    proposal: Pubkey,
+    votes: Vec<String>,
+) -> Result<()> {
+    let locker_state: locked_voter::Locker = program.account(locker)?;
+    let mut instructions = vec![];
+    let mut signed_votes = vec![];
+    let mut remaining_accounts = vec![];
+    for vote in votes.iter() {
+        let (escrow_owner, side, nonce, signature) = parse_signed_vote(vote)?;
+        let (escrow, _bump) = Pubkey::find_program_address(
+            &[b"Escrow".as_ref(), locker.as_ref(), escrow_owner.as_ref()],
+            &locked_voter::id(),
+        );
+        let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+            &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+            &locked_voter::id(),
+        );
+        let (vote, _bump) = Pubkey::find_program_address(
+            &[b"Vote".as_ref(), proposal.as_ref(), escrow_owner.as_ref()],
+            &govern::id(),
+        );
+        let (delegated_vote, _bump) = Pubkey::find_program_address(
+            &[
+                b"DelegatedVote".as_ref(),
+                proposal.as_ref(),
+                escrow.as_ref(),
+            ],
+            &locked_voter::id(),
+        );
+        let escrow_state: locked_voter::Escrow = program.account(escrow)?;
+
+        let message = govern::signed_vote_message(&proposal, side, nonce);
+        instructions.push(new_ed25519_instruction(
+            &escrow_state.vote_delegate,
+            &signature,
+            &message,
+        ));
+        signed_votes.push(locked_voter::SignedVote { side, nonce });
+        remaining_accounts.push(AccountMeta::new(escrow, false));
+        remaining_accounts.push(AccountMeta::new_readonly(escrow_checkpoints, false));
+        remaining_accounts.push(AccountMeta::new(vote, false));
+        remaining_accounts.push(AccountMeta::new_readonly(delegated_vote, false));
+    }
+
+    let mut accounts = locked_voter::accounts::CastSignedVotes {
+        locker,
+        proposal,
+        governor: locker_state.governor,
+        payer: program.payer(),
+        instructions_sysvar: solana_program::sysvar::instructions::ID,
+        govern_program: govern::ID,
+        system_program: solana_program::system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.append(&mut remaining_accounts);
+    instructions.push(Instruction {
+        program_id: locked_voter::id(),
+        accounts,
+        data: locked_voter::instruction::CastSignedVotes {
+            votes: signed_votes,
+        }
+        .data(),
+    });
+
+    let builder = program.request();
+    let builder = instructions
+        .into_iter()
+        .fold(builder, |bld, ix| bld.instruction(ix));
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn new_delegate<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    name: String,
+    url: String,
+) -> Result<()> {
+    let (delegate, _bump) = Pubkey::find_program_address(
+        &[
+            b"Delegate".as_ref(),
+            locker.as_ref(),
+            program.payer().as_ref(),
+        ],
+        &locked_voter::id(),
+    );
+
+    let builder = program
+        .request()
+        .accounts(locked_voter::accounts::NewDelegate {
+            locker,
+            delegate,
+            authority: program.payer(),
+            system_program: solana_program::system_program::ID,
+        })
+        .args(locked_voter::instruction::NewDelegate { name, url });
+    let signature = builder.send()?;
+    println!("Delegate {}", delegate);
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn set_delegate_profile<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    name: String,
+    url: String,
+) -> Result<()> {
+    let (delegate, _bump) = Pubkey::find_program_address(
+        &[
+            b"Delegate".as_ref(),
+            locker.as_ref(),
+            program.payer().as_ref(),
+        ],
+        &locked_voter::id(),
+    );
+
+    let builder = program
+        .request()
+        .accounts(locked_voter::accounts::SetDelegateProfile {
+            delegate,
+            authority: program.payer(),
+        })
+        .args(locked_voter::instruction::SetDelegateProfile { name, url });
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn withdraw_delegate_rewards<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    mint: Option<Pubkey>,
+) -> Result<()> {
+    let (delegate, _bump) = Pubkey::find_program_address(
+        &[
+            b"Delegate".as_ref(),
+            locker.as_ref(),
+            program.payer().as_ref(),
+        ],
+        &locked_voter::id(),
+    );
+
+    let builder = program
+        .request()
+        .accounts(locked_voter::accounts::WithdrawDelegateRewards {
+            delegate,
+            authority: program.payer(),
+            delegate_tokens: mint.map(|mint| get_associated_token_address(&delegate, &mint)),
+            destination_tokens: mint
+                .map(|mint| get_associated_token_address(&program.payer(), &mint)),
+            token_program: anchor_spl::token::ID,
+        })
+        .args(locked_voter::instruction::WithdrawDelegateRewards {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
+fn list_delegates<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+) -> Result<()> {
+    let program_accounts = accounts_with_rpc_timeout::<C, locked_voter::Delegate>(
+        program,
+        Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            &locker.to_bytes(),
+        ))]),
+    )?;
+    println!("Found {} delegates", program_accounts.len());
+
+    println!("delegate,authority,name,url");
+    for (key, delegate) in program_accounts {
+        println!(
+            "{key},{},{},{}",
+            delegate.authority, delegate.name, delegate.url
+        );
+    }
+
+    Ok(())
+}
+
+fn cast_delegate_vote<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    proposal: Pubkey,
+    side: u8,
+) -> Result<()> {
+    let locker_state: locked_voter::Locker = program.account(locker)?;
+    let (delegate, _bump) = Pubkey::find_program_address(
+        &[
+            b"Delegate".as_ref(),
+            locker.as_ref(),
+            program.payer().as_ref(),
+        ],
+        &locked_voter::id(),
+    );
+    let (vote, _bump) = Pubkey::find_program_address(
+        &[b"Vote".as_ref(), proposal.as_ref(), delegate.as_ref()],
+        &govern::id(),
+    );
+
+    let mut builder = program.request();
+    if program.rpc().get_account_data(&vote).is_err() {
+        builder = builder.instruction(Instruction {
+            program_id: govern::id(),
+            accounts: govern::accounts::NewVote {
+                proposal,
+                vote,
+                payer: program.payer(),
+                system_program: solana_program::system_program::ID,
+            }
+            .to_account_metas(None),
+            data: govern::instruction::NewVote { voter: delegate }.data(),
+        });
+    }
+    let builder = builder
+        .accounts(locked_voter::accounts::CastDelegateVote {
+            locker,
+            delegate,
+            authority: program.payer(),
+            proposal,
+            vote,
+            governor: locker_state.governor,
+            govern_program: govern::ID,
+        })
+        .args(locked_voter::instruction::CastDelegateVote { side });
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+
+    // add the voting power of the escrows delegating to the delegate, once per proposal
+    let program_accounts = accounts_with_rpc_timeout::<C, locked_voter::Escrow>(
+        program,
+        Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            8,
+            &locker.to_bytes(),
+        ))]),
+    )?;
+    let mut escrows = vec![];
+    for (escrow, escrow_state) in program_accounts {
+        if escrow_state.vote_delegate != delegate {
+            continue;
+        }
+        let (delegated_vote, _bump) = Pubkey::find_program_address(
+            &[
+                b"DelegatedVote".as_ref(),
+                proposal.as_ref(),
+                escrow.as_ref(),
+            ],
+            &locked_voter::id(),
+        );
+        if program.rpc().get_account_data(&delegated_vote).is_err() {
+            escrows.push((escrow, escrow_state.owner, delegated_vote));
+        }
+    }
+    println!("Found {} escrows to add", escrows.len());
+
+    for chunk in escrows.chunks(5) {
+        let mut builder = program.request();
+        for (escrow, owner, delegated_vote) in chunk {
+            let (escrow_checkpoints, _bump) = Pubkey::find_program_address(
+                &[b"EscrowCheckpoints".as_ref(), escrow.as_ref()],
+                &locked_voter::id(),
+            );
+            let (escrow_vote, _bump) = Pubkey::find_program_address(
+                &[b"Vote".as_ref(), proposal.as_ref(), owner.as_ref()],
+                &govern::id(),
+            );
+            builder = builder.instruction(Instruction {
+                program_id: locked_voter::id(),
+                accounts: locked_voter::accounts::AddDelegatedVote {
+                    locker,
+                    delegate,
+                    escrow: *escrow,
+                    escrow_checkpoints,
+                    escrow_vote,
+                    delegated_vote: *delegated_vote,
+                    proposal,
+                    vote,
+                    payer: program.payer(),
+                    governor: locker_state.governor,
+                    govern_program: govern::ID,
+                    system_program: solana_program::system_program::ID,
+                }
+                .to_account_metas(None),
+                data: locked_voter::instruction::AddDelegatedVote {}.data(),
+            });
+        }
+        let signature = builder.send()?;
+        println!("Signature {:?}", signature);
+    }
+    Ok(())
+}
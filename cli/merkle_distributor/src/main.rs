@@ -373,6 +373,13 @@ fn claim<C: Deref<Target = impl Signer> + Clone>(
         }];
     }
     let escrow_tokens = get_associated_token_address(&escrow, &distributor_state.mint);
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[
+            b"LockerSlopeChanges".as_ref(),
+            distributor_state.locker.as_ref(),
+        ],
+        &met_voter::id(),
+    );
     if program.rpc().get_account_data(&escrow_tokens).is_err() {
         instructions.push(
             spl_associated_token_account::instruction::create_associated_token_account(
@@ -395,6 +402,7 @@ fn claim<C: Deref<Target = impl Signer> + Clone>(
             locker: distributor_state.locker,
             escrow,
             escrow_tokens,
+            slope_changes,
         }
         .to_account_metas(None),
         data: merkle_distributor::instruction::Claim {
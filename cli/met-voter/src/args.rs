@@ -80,6 +80,16 @@ pub enum CliCommand {
         #[clap(long)]
         base: Pubkey,
     },
+    /// Creates the slope changes tracking the total voting power of a locker created before they existed.
+    NewLockerSlopeChanges {
+        #[clap(long)]
+        base: Pubkey,
+    },
+    /// Counts the escrows created before the total voting power was tracked in it.
+    SyncEscrowsVotingPower {
+        #[clap(long)]
+        base: Pubkey,
+    },
     IncreaseLockedAmount {
         #[clap(long)]
         base: Pubkey,
@@ -116,6 +126,28 @@ pub enum CliCommand {
         #[clap(long)]
         side: u8,
     },
+    /// Signs a vote off-chain with the wallet, to be cast by a relayer with CastSignedVotes.
+    SignVote {
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        side: u8,
+        /// Must be greater than the nonce of the last signed vote cast for the escrow.
+        #[clap(long)]
+        nonce: u64,
+        /// Owner of the escrow voted for, if the wallet is its vote delegate.
+        #[clap(long)]
+        escrow_owner: Option<Pubkey>,
+    },
+    /// Casts votes signed off-chain, given as ESCROW_OWNER:SIDE:NONCE:SIGNATURE.
+    CastSignedVotes {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        proposal: Pubkey,
+        #[clap(long)]
+        votes: Vec<String>,
+    },
     SetVoteDelegate {
         #[clap(long)]
         base: Pubkey,
@@ -1,5 +1,7 @@
 mod args;
 use crate::args::*;
+use anchor_client::solana_client::rpc_filter::Memcmp;
+use anchor_client::solana_client::rpc_filter::RpcFilterType;
 use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signer::keypair::*;
@@ -10,10 +12,11 @@ use anchor_lang::ToAccountMetas;
 use anchor_spl::associated_token::get_associated_token_address;
 use anyhow::Result;
 use clap::*;
-use solana_program::instruction::Instruction;
+use solana_program::instruction::{AccountMeta, Instruction};
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
+use utils_cli::{new_ed25519_instruction, parse_signed_vote};
 // use utils_cli::*;
 
 fn main() -> Result<()> {
@@ -67,6 +70,24 @@ fn main() -> Result<()> {
             );
             new_escrow(&program, locker)?;
         }
+        CliCommand::NewLockerSlopeChanges { base } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &met_voter::id(),
+            );
+            let builder = program
+                .request()
+                .instruction(new_locker_slope_changes_ix(&program, locker));
+            let signature = builder.send()?;
+            println!("Signature {:?}", signature);
+        }
+        CliCommand::SyncEscrowsVotingPower { base } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &met_voter::id(),
+            );
+            sync_escrows_voting_power(&program, locker)?;
+        }
         CliCommand::IncreaseLockedAmount { base, amount } => {
             let (locker, _bump) = Pubkey::find_program_address(
                 &[b"Locker".as_ref(), base.as_ref()],
@@ -113,6 +134,33 @@ fn main() -> Result<()> {
             );
             cast_vote(&program, locker, proposal, side)?;
         }
+        CliCommand::SignVote {
+            proposal,
+            side,
+            nonce,
+            escrow_owner,
+        } => {
+            let message = govern::signed_vote_message(&proposal, side, nonce);
+            let signature = payer.sign_message(&message);
+            println!(
+                "{}:{}:{}:{}",
+                escrow_owner.unwrap_or(wallet),
+                side,
+                nonce,
+                signature
+            );
+        }
+        CliCommand::CastSignedVotes {
+            base,
+            proposal,
+            votes,
+        } => {
+            let (locker, _bump) = Pubkey::find_program_address(
+                &[b"Locker".as_ref(), base.as_ref()],
+                &met_voter::id(),
+            );
+            cast_signed_votes(&program, locker, proposal, votes)?;
+        }
         CliCommand::SetVoteDelegate { base, new_delegate } => {
             let (locker, _bump) = Pubkey::find_program_address(
                 &[b"Locker".as_ref(), base.as_ref()],
@@ -244,12 +292,71 @@ fn new_locker<C: Deref<Target = impl Signer> + Clone>(
                 proposal_activation_min_votes,
             },
         })
+        .instruction(new_locker_slope_changes_ix(program, locker))
         .signer(&base_keypair);
     let signature = builder.send()?;
     println!("Signature {:?}", signature);
     Ok(())
 }
 
+fn new_locker_slope_changes_ix<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: met_voter::id(),
+        accounts: met_voter::accounts::NewLockerSlopeChanges {
+            locker,
+            slope_changes: locker_slope_changes(locker),
+            payer: program.payer(),
+            system_program: solana_program::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: met_voter::instruction::NewLockerSlopeChanges {}.data(),
+    }
+}
+
+fn locker_slope_changes(locker: Pubkey) -> Pubkey {
+    let (slope_changes, _bump) = Pubkey::find_program_address(
+        &[b"LockerSlopeChanges".as_ref(), locker.as_ref()],
+        &met_voter::id(),
+    );
+    slope_changes
+}
+
+fn sync_escrows_voting_power<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+) -> Result<()> {
+    let slope_changes = locker_slope_changes(locker);
+    let program_accounts = program.accounts::<met_voter::Escrow>(vec![RpcFilterType::Memcmp(
+        Memcmp::new_base58_encoded(8, &locker.to_bytes()),
+    )])?;
+    let escrows: Vec<Pubkey> = program_accounts
+        .into_iter()
+        .filter(|(_, escrow)| !escrow.is_tracked)
+        .map(|(key, _)| key)
+        .collect();
+    println!("Found {} escrows to sync", escrows.len());
+
+    for chunk in escrows.chunks(20) {
+        let mut accounts = met_voter::accounts::SyncEscrowsVotingPower {
+            locker,
+            slope_changes,
+        }
+        .to_account_metas(None);
+        accounts.extend(chunk.iter().map(|escrow| AccountMeta::new(*escrow, false)));
+        let builder = program.request().instruction(Instruction {
+            program_id: met_voter::id(),
+            accounts,
+            data: met_voter::instruction::SyncEscrowsVotingPower {}.data(),
+        });
+        let signature = builder.send()?;
+        println!("Signature {:?}", signature);
+    }
+    Ok(())
+}
+
 fn new_escrow<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     locker: Pubkey,
@@ -301,6 +408,7 @@ fn increase_locked_amount<C: Deref<Target = impl Signer> + Clone>(
         .accounts(met_voter::accounts::IncreaseLockedAmount {
             locker,
             escrow,
+            slope_changes: locker_slope_changes(locker),
             escrow_tokens,
             source_tokens,
             payer: program.payer(),
@@ -331,6 +439,7 @@ fn extend_locked_duration<C: Deref<Target = impl Signer> + Clone>(
         .accounts(met_voter::accounts::ExtendLockDuration {
             locker,
             escrow,
+            slope_changes: locker_slope_changes(locker),
             escrow_owner: program.payer(),
         })
         .args(met_voter::instruction::ExtendLockDuration { duration });
@@ -359,6 +468,7 @@ fn toggle_max_lock<C: Deref<Target = impl Signer> + Clone>(
         .accounts(met_voter::accounts::ToggleMaxLock {
             locker,
             escrow,
+            slope_changes: locker_slope_changes(locker),
             escrow_owner: program.payer(),
         })
         .args(met_voter::instruction::ToggleMaxLock { is_max_lock });
@@ -390,6 +500,7 @@ fn withdraw<C: Deref<Target = impl Signer> + Clone>(
         .accounts(met_voter::accounts::Withdraw {
             locker,
             escrow,
+            slope_changes: locker_slope_changes(locker),
             escrow_tokens,
             destination_tokens,
             escrow_owner: program.payer(),
@@ -422,7 +533,13 @@ fn active_proposal<C: Deref<Target = impl Signer> + Clone>(
         .accounts(met_voter::accounts::ActivateProposal {
             locker,
             escrow,
+            slope_changes: locker_slope_changes(locker),
             proposal,
+            proposal_params: Pubkey::find_program_address(
+                &[b"ProposalParams".as_ref(), proposal.as_ref()],
+                &govern::id(),
+            )
+            .0,
             escrow_owner: program.payer(),
             governor: locker_state.governor,
             govern_program: govern::ID,
@@ -528,3 +645,65 @@ fn set_vote_delegate<C: Deref<Target = impl Signer> + Clone>(
     println!("Signature {:?}", signature);
     Ok(())
 }
+
+fn cast_signed_votes<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    locker: Pubkey,
+    proposal: Pubkey,
+    votes: Vec<String>,
+) -> Result<()> {
+    let locker_state: met_voter::Locker = program.account(locker)?;
+    let mut instructions = vec![];
+    let mut signed_votes = vec![];
+    let mut remaining_accounts = vec![];
+    for vote in votes.iter() {
+        let (escrow_owner, side, nonce, signature) = parse_signed_vote(vote)?;
+        let (escrow, _bump) = Pubkey::find_program_address(
+            &[b"Escrow".as_ref(), locker.as_ref(), escrow_owner.as_ref()],
+            &met_voter::id(),
+        );
+        let (vote, _bump) = Pubkey::find_program_address(
+            &[b"Vote".as_ref(), proposal.as_ref(), escrow_owner.as_ref()],
+            &govern::id(),
+        );
+        let escrow_state: met_voter::Escrow = program.account(escrow)?;
+
+        let message = govern::signed_vote_message(&proposal, side, nonce);
+        instructions.push(new_ed25519_instruction(
+            &escrow_state.vote_delegate,
+            &signature,
+            &message,
+        ));
+        signed_votes.push(met_voter::SignedVote { side, nonce });
+        remaining_accounts.push(AccountMeta::new(escrow, false));
+        remaining_accounts.push(AccountMeta::new(vote, false));
+    }
+
+    let mut accounts = met_voter::accounts::CastSignedVotes {
+        locker,
+        proposal,
+        governor: locker_state.governor,
+        payer: program.payer(),
+        instructions_sysvar: solana_program::sysvar::instructions::ID,
+        govern_program: govern::ID,
+        system_program: solana_program::system_program::ID,
+    }
+    .to_account_metas(None);
+    accounts.append(&mut remaining_accounts);
+    instructions.push(Instruction {
+        program_id: met_voter::id(),
+        accounts,
+        data: met_voter::instruction::CastSignedVotes {
+            votes: signed_votes,
+        }
+        .data(),
+    });
+
+    let builder = program.request();
+    let builder = instructions
+        .into_iter()
+        .fold(builder, |bld, ix| bld.instruction(ix));
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
@@ -94,6 +94,13 @@ pub enum CliCommand {
         #[clap(long)]
         proposal: Pubkey,
     },
+    /// Creates a transaction which cancels a proposal that has not been queued
+    CreateForceCancelProposalTx {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        proposal: Pubkey,
+    },
     CreateSetGovernanceParamsTx {
         #[clap(long)]
         base: Pubkey,
@@ -109,6 +116,111 @@ pub enum CliCommand {
         /// The timelock delay of the DAO's created proposals.
         #[clap(long)]
         timelock_delay_seconds: i64,
+        /// The share of votes, in basis points, the winning side of a proposal must reach. 0 is a simple majority.
+        #[clap(long, default_value_t = 0)]
+        approval_threshold_bps: u16,
+        /// If set, the quorum is this share, in basis points, of the total voting power of the locker.
+        #[clap(long, default_value_t = 0)]
+        quorum_bps: u16,
+    },
+    /// Creates a transaction which creates a proposal class, or sets its parameters if it exists
+    CreateSetProposalClassTx {
+        #[clap(long)]
+        base: Pubkey,
+        /// Name of the proposal class, e.g. "emergency".
+        #[clap(long)]
+        name: String,
+        /// The delay before voting on a proposal may take place, once proposed, in seconds
+        #[clap(long)]
+        voting_delay: u64,
+        /// The duration of voting on a proposal, in seconds
+        #[clap(long)]
+        voting_period: u64,
+        /// The number of votes in support of a proposal required in order for a quorum to be reached and for a vote to succeed
+        #[clap(long)]
+        quorum_votes: u64,
+        /// The timelock delay of the proposals of the class.
+        #[clap(long)]
+        timelock_delay_seconds: i64,
+        /// The share of votes, in basis points, the winning side of a proposal must reach. 0 is a simple majority.
+        #[clap(long, default_value_t = 0)]
+        approval_threshold_bps: u16,
+        /// If set, the quorum is this share, in basis points, of the total voting power of the locker.
+        #[clap(long, default_value_t = 0)]
+        quorum_bps: u16,
+        /// Instructions proposals of the class may call, as `program_id[:hex_discriminator]`.
+        #[clap(long)]
+        filter: Vec<String>,
+    },
+    CreateSetVetoAuthorityTx {
+        #[clap(long)]
+        base: Pubkey,
+        /// The authority which may veto proposals. The default pubkey removes the veto authority.
+        #[clap(long)]
+        veto_authority: Pubkey,
+    },
+    CreateSetProposalDepositTx {
+        #[clap(long)]
+        base: Pubkey,
+        /// The mint of the deposit escrowed when a proposal is created.
+        #[clap(long)]
+        deposit_mint: Pubkey,
+        /// The amount escrowed per proposal. 0 disables the deposit.
+        #[clap(long)]
+        deposit_amount: u64,
+        /// The share of the quorum, in basis points, a proposal's votes must reach for the deposit to be refunded.
+        #[clap(long)]
+        refund_threshold_bps: u16,
+    },
+    /// Creates a transaction which sets when votes may no longer be changed.
+    CreateSetVoteLockTx {
+        #[clap(long)]
+        base: Pubkey,
+        /// Seconds before voting ends during which votes already cast cannot be changed.
+        #[clap(long, default_value_t = 0)]
+        vote_lock_period: i64,
+        /// Votes cannot be changed once cast.
+        #[clap(long)]
+        votes_final: bool,
+    },
+    /// Creates a transaction which sets how long late votes extend voting by.
+    CreateSetLateQuorumExtensionTx {
+        #[clap(long)]
+        base: Pubkey,
+        /// Seconds voting is extended to when a late vote reaches quorum or changes the leading side.
+        #[clap(long)]
+        late_quorum_extension: i64,
+        /// Maximum number of seconds voting may be extended by in total.
+        #[clap(long)]
+        max_voting_extension: i64,
+    },
+    /// Creates a transaction which creates or replaces the proposal policy of the governor.
+    /// Instructions are given as PROGRAM_ID or PROGRAM_ID:HEX_DISCRIMINATOR.
+    CreateSetProposalPolicyTx {
+        #[clap(long)]
+        base: Pubkey,
+        /// Allow proposals to call instructions which are not listed.
+        #[clap(long)]
+        allow_unlisted: bool,
+        /// Instructions proposals must not call.
+        #[clap(long)]
+        deny: Vec<String>,
+        /// Instructions proposals may call.
+        #[clap(long)]
+        allow: Vec<String>,
+        /// Instructions proposals may call, with a longer timelock delay.
+        #[clap(long)]
+        elevate: Vec<String>,
+        /// The timelock delay of proposals calling elevated instructions.
+        #[clap(long, default_value_t = 0)]
+        elevated_timelock_delay_seconds: i64,
+    },
+    /// Creates a transaction which sweeps the unclaimed voting reward of a proposal to the treasury
+    CreateSweepVotingRewardTx {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        proposal: Pubkey,
     },
     ApproveTransaction {
         #[clap(long)]
@@ -127,6 +239,9 @@ pub enum CliCommand {
         base: Pubkey,
         #[clap(long)]
         transaction: Pubkey,
+        /// Address lookup tables to compress the accounts of the transaction with
+        #[clap(long)]
+        lookup_table: Vec<Pubkey>,
     },
     RemoveTransaction {
         #[clap(long)]
@@ -134,6 +249,13 @@ pub enum CliCommand {
         #[clap(long)]
         transaction: Pubkey,
     },
+    /// Cancels a transaction proposed by the wallet, even if owners approved it.
+    CancelTransaction {
+        #[clap(long)]
+        base: Pubkey,
+        #[clap(long)]
+        transaction: Pubkey,
+    },
     ViewSmartwallet {
         #[clap(long)]
         base: Pubkey,
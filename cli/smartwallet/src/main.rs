@@ -3,6 +3,7 @@ mod args;
 use crate::args::*;
 use anchor_client::anchor_lang::InstructionData;
 use anchor_client::anchor_lang::ToAccountMetas;
+use anchor_client::solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
 use anchor_client::solana_sdk::commitment_config::CommitmentConfig;
 use anchor_client::solana_sdk::pubkey::Pubkey;
 use anchor_client::solana_sdk::signer::keypair::*;
@@ -14,6 +15,7 @@ use solana_program::instruction::AccountMeta;
 use std::ops::Deref;
 use std::rc::Rc;
 use std::str::FromStr;
+use utils_cli::{fetch_lookup_table, send_versioned_transaction};
 
 use clap::*;
 
@@ -58,6 +60,12 @@ fn main() -> Result<()> {
         CliCommand::CreateActivateProposalTx { base, proposal } => {
             create_activate_proposal_tx(&program, base, proposal)?;
         }
+        CliCommand::CreateForceCancelProposalTx { base, proposal } => {
+            create_force_cancel_proposal_tx(&program, base, proposal)?;
+        }
+        CliCommand::CreateSweepVotingRewardTx { base, proposal } => {
+            create_sweep_voting_reward_tx(&program, base, proposal)?;
+        }
         CliCommand::ApproveTransaction { base, transaction } => {
             approve_transaction(&program, base, transaction)?;
         }
@@ -67,8 +75,15 @@ fn main() -> Result<()> {
         CliCommand::RemoveTransaction { base, transaction } => {
             remove_transaction(&program, base, transaction)?;
         }
-        CliCommand::ExecuteTransaction { base, transaction } => {
-            execute_transaction(&program, base, transaction)?;
+        CliCommand::CancelTransaction { base, transaction } => {
+            cancel_transaction(&program, base, transaction)?;
+        }
+        CliCommand::ExecuteTransaction {
+            base,
+            transaction,
+            lookup_table,
+        } => {
+            execute_transaction(&program, &payer, base, transaction, lookup_table)?;
         }
         CliCommand::ViewSmartwallet { base } => {
             view_smartwallet(&program, base)?;
@@ -91,6 +106,8 @@ fn main() -> Result<()> {
             voting_period,
             quorum_votes,
             timelock_delay_seconds,
+            approval_threshold_bps,
+            quorum_bps,
         } => {
             create_set_governance_params_tx(
                 &program,
@@ -99,6 +116,91 @@ fn main() -> Result<()> {
                 voting_period,
                 quorum_votes,
                 timelock_delay_seconds,
+                approval_threshold_bps,
+                quorum_bps,
+            )?;
+        }
+        CliCommand::CreateSetProposalClassTx {
+            base,
+            name,
+            voting_delay,
+            voting_period,
+            quorum_votes,
+            timelock_delay_seconds,
+            approval_threshold_bps,
+            quorum_bps,
+            filter,
+        } => {
+            create_set_proposal_class_tx(
+                &program,
+                base,
+                name,
+                govern::GovernanceParameters {
+                    voting_delay,
+                    voting_period,
+                    quorum_votes,
+                    timelock_delay_seconds,
+                    approval_threshold_bps,
+                    quorum_bps,
+                },
+                filter,
+            )?;
+        }
+        CliCommand::CreateSetVetoAuthorityTx {
+            base,
+            veto_authority,
+        } => {
+            create_set_veto_authority_tx(&program, base, veto_authority)?;
+        }
+        CliCommand::CreateSetProposalDepositTx {
+            base,
+            deposit_mint,
+            deposit_amount,
+            refund_threshold_bps,
+        } => {
+            create_set_proposal_deposit_tx(
+                &program,
+                base,
+                deposit_mint,
+                deposit_amount,
+                refund_threshold_bps,
+            )?;
+        }
+        CliCommand::CreateSetVoteLockTx {
+            base,
+            vote_lock_period,
+            votes_final,
+        } => {
+            create_set_vote_lock_tx(&program, base, vote_lock_period, votes_final)?;
+        }
+        CliCommand::CreateSetLateQuorumExtensionTx {
+            base,
+            late_quorum_extension,
+            max_voting_extension,
+        } => {
+            create_set_late_quorum_extension_tx(
+                &program,
+                base,
+                late_quorum_extension,
+                max_voting_extension,
+            )?;
+        }
+        CliCommand::CreateSetProposalPolicyTx {
+            base,
+            allow_unlisted,
+            deny,
+            allow,
+            elevate,
+            elevated_timelock_delay_seconds,
+        } => {
+            create_set_proposal_policy_tx(
+                &program,
+                base,
+                allow_unlisted,
+                deny,
+                allow,
+                elevate,
+                elevated_timelock_delay_seconds,
             )?;
         }
         CliCommand::Verify {
@@ -314,6 +416,8 @@ fn create_set_governance_params_tx<C: Deref<Target = impl Signer> + Clone>(
     voting_period: u64,
     quorum_votes: u64,
     timelock_delay_seconds: i64,
+    approval_threshold_bps: u16,
+    quorum_bps: u16,
 ) -> Result<()> {
     let (smart_wallet, _bump) = Pubkey::find_program_address(
         &[b"SmartWallet".as_ref(), base.as_ref()],
@@ -329,6 +433,8 @@ fn create_set_governance_params_tx<C: Deref<Target = impl Signer> + Clone>(
             voting_period,
             quorum_votes,
             timelock_delay_seconds,
+            approval_threshold_bps,
+            quorum_bps,
         },
     }
     .data();
@@ -352,6 +458,368 @@ fn create_set_governance_params_tx<C: Deref<Target = impl Signer> + Clone>(
     create_transaction(program, base, vec![instruction])
 }
 
+fn create_set_proposal_class_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    name: String,
+    params: govern::GovernanceParameters,
+    filters: Vec<String>,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let (governor, _bump) =
+        Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
+    let (proposal_class, _bump) = Pubkey::find_program_address(
+        &[
+            b"ProposalClass".as_ref(),
+            governor.as_ref(),
+            name.as_bytes(),
+        ],
+        &govern::id(),
+    );
+
+    let filters = filters
+        .iter()
+        .map(|filter| parse_instruction_filter(filter))
+        .collect::<Result<Vec<_>>>()?;
+
+    let has_class = program
+        .account::<govern::ProposalClass>(proposal_class)
+        .is_ok();
+    println!("set proposal class {} {}", name, proposal_class);
+    let (keys, data) = if has_class {
+        (
+            govern::accounts::SetProposalClassParams {
+                governor,
+                proposal_class,
+                smart_wallet,
+                payer: program.payer(),
+                system_program: solana_program::system_program::ID,
+            }
+            .to_account_metas(None),
+            govern::instruction::SetProposalClassParams { params, filters }.data(),
+        )
+    } else {
+        (
+            govern::accounts::CreateProposalClass {
+                governor,
+                proposal_class,
+                smart_wallet,
+                payer: program.payer(),
+                system_program: solana_program::system_program::ID,
+            }
+            .to_account_metas(None),
+            govern::instruction::CreateProposalClass {
+                name,
+                params,
+                filters,
+            }
+            .data(),
+        )
+    };
+    // the owner executing the transaction pays for a new or larger class
+    let keys = keys
+        .into_iter()
+        .map(|meta| smart_wallet::TXAccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys,
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
+fn create_set_veto_authority_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    veto_authority: Pubkey,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let (governor, _bump) =
+        Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
+
+    println!("set veto authority");
+    let data = govern::instruction::SetVetoAuthority {
+        new_veto_authority: veto_authority,
+    }
+    .data();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys: vec![
+            smart_wallet::TXAccountMeta {
+                pubkey: governor,
+                is_signer: false,
+                is_writable: true,
+            },
+            smart_wallet::TXAccountMeta {
+                pubkey: smart_wallet,
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
+fn create_set_proposal_deposit_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    deposit_mint: Pubkey,
+    deposit_amount: u64,
+    refund_threshold_bps: u16,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let (governor, _bump) =
+        Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
+
+    println!("set proposal deposit");
+    let data = govern::instruction::SetProposalDeposit {
+        deposit_amount,
+        refund_threshold_bps,
+    }
+    .data();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys: vec![
+            smart_wallet::TXAccountMeta {
+                pubkey: governor,
+                is_signer: false,
+                is_writable: true,
+            },
+            smart_wallet::TXAccountMeta {
+                pubkey: deposit_mint,
+                is_signer: false,
+                is_writable: false,
+            },
+            smart_wallet::TXAccountMeta {
+                pubkey: smart_wallet,
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
+fn create_set_vote_lock_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    vote_lock_period: i64,
+    votes_final: bool,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let (governor, _bump) =
+        Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
+
+    println!("set vote lock");
+    let data = govern::instruction::SetVoteLock {
+        vote_lock_period,
+        votes_final,
+    }
+    .data();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys: vec![
+            smart_wallet::TXAccountMeta {
+                pubkey: governor,
+                is_signer: false,
+                is_writable: true,
+            },
+            smart_wallet::TXAccountMeta {
+                pubkey: smart_wallet,
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
+fn create_set_late_quorum_extension_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    late_quorum_extension: i64,
+    max_voting_extension: i64,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let (governor, _bump) =
+        Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
+
+    println!("set late quorum extension");
+    let data = govern::instruction::SetLateQuorumExtension {
+        late_quorum_extension,
+        max_voting_extension,
+    }
+    .data();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys: vec![
+            smart_wallet::TXAccountMeta {
+                pubkey: governor,
+                is_signer: false,
+                is_writable: true,
+            },
+            smart_wallet::TXAccountMeta {
+                pubkey: smart_wallet,
+                is_signer: true,
+                is_writable: false,
+            },
+        ],
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
+fn parse_instruction_filter(filter: &str) -> Result<govern::InstructionFilter> {
+    let (program_id, discriminator) = filter.split_once(':').unwrap_or((filter, ""));
+    anyhow::ensure!(
+        discriminator.len() % 2 == 0,
+        "invalid discriminator {}",
+        filter
+    );
+    let discriminator = (0..discriminator.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&discriminator[i..i + 2], 16))
+        .collect::<std::result::Result<Vec<u8>, _>>()?;
+    Ok(govern::InstructionFilter {
+        program_id: Pubkey::from_str(program_id)?,
+        discriminator,
+    })
+}
+
+fn create_set_proposal_policy_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    allow_unlisted: bool,
+    deny: Vec<String>,
+    allow: Vec<String>,
+    elevate: Vec<String>,
+    elevated_timelock_delay_seconds: i64,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let (governor, _bump) =
+        Pubkey::find_program_address(&[b"Governor".as_ref(), base.as_ref()], &govern::id());
+    let (proposal_policy, _bump) = Pubkey::find_program_address(
+        &[b"ProposalPolicy".as_ref(), governor.as_ref()],
+        &govern::id(),
+    );
+    let governor_state: govern::Governor = program.account(governor)?;
+
+    let to_filters = |filters: Vec<String>| -> Result<Vec<govern::InstructionFilter>> {
+        filters
+            .iter()
+            .map(|filter| parse_instruction_filter(filter))
+            .collect()
+    };
+    let mut lists = vec![];
+    if !deny.is_empty() {
+        lists.push(govern::InstructionList {
+            allowed: false,
+            params: None,
+            filters: to_filters(deny)?,
+        });
+    }
+    if !allow.is_empty() {
+        lists.push(govern::InstructionList {
+            allowed: true,
+            params: None,
+            filters: to_filters(allow)?,
+        });
+    }
+    if !elevate.is_empty() {
+        lists.push(govern::InstructionList {
+            allowed: true,
+            params: Some(govern::GovernanceParameters {
+                timelock_delay_seconds: elevated_timelock_delay_seconds,
+                ..governor_state.governance_params()
+            }),
+            filters: to_filters(elevate)?,
+        });
+    }
+
+    let has_policy = governor_state.proposal_policy != Pubkey::default();
+    println!("set proposal policy {}", proposal_policy);
+    let (keys, data) = if has_policy {
+        (
+            govern::accounts::SetProposalPolicy {
+                governor,
+                proposal_policy,
+                smart_wallet,
+                payer: program.payer(),
+                system_program: solana_program::system_program::ID,
+            }
+            .to_account_metas(None),
+            govern::instruction::SetProposalPolicy {
+                allow_unlisted,
+                lists,
+            }
+            .data(),
+        )
+    } else {
+        (
+            govern::accounts::CreateProposalPolicy {
+                governor,
+                proposal_policy,
+                smart_wallet,
+                payer: program.payer(),
+                system_program: solana_program::system_program::ID,
+            }
+            .to_account_metas(None),
+            govern::instruction::CreateProposalPolicy {
+                allow_unlisted,
+                lists,
+            }
+            .data(),
+        )
+    };
+    // the owner executing the transaction pays for the policy
+    let keys = keys
+        .into_iter()
+        .map(|meta| smart_wallet::TXAccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        })
+        .collect();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys,
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
 fn create_activate_proposal_tx<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     base: Pubkey,
@@ -400,6 +868,95 @@ fn create_activate_proposal_tx<C: Deref<Target = impl Signer> + Clone>(
     create_transaction(program, base, vec![instruction])
 }
 
+fn create_force_cancel_proposal_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    proposal: Pubkey,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let (event_authority, _bump) =
+        Pubkey::find_program_address(&[b"__event_authority"], &govern::ID);
+
+    println!("force cancel proposal {}", proposal);
+    let data = govern::instruction::ForceCancelProposal {}.data();
+    let keys = govern::accounts::ForceCancelProposal {
+        governor: proposal_state.governor,
+        proposal,
+        smart_wallet,
+        event_authority,
+        program: govern::ID,
+    }
+    .to_account_metas(None)
+    .into_iter()
+    .map(|meta| smart_wallet::TXAccountMeta {
+        pubkey: meta.pubkey,
+        is_signer: meta.is_signer,
+        is_writable: meta.is_writable,
+    })
+    .collect();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys,
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
+fn create_sweep_voting_reward_tx<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    proposal: Pubkey,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    let proposal_state: govern::Proposal = program.account(proposal)?;
+    let voting_reward = proposal_state.voting_reward;
+    let (event_authority, _bump) =
+        Pubkey::find_program_address(&[b"__event_authority"], &govern::ID);
+
+    println!(
+        "sweep {} unclaimed reward of proposal {}",
+        proposal_state.unswept_reward().unwrap_or_default(),
+        proposal
+    );
+    let data = govern::instruction::SweepVotingReward {}.data();
+    let keys = govern::accounts::SweepVotingReward {
+        governor: proposal_state.governor,
+        proposal,
+        reward_vault: voting_reward.reward_vault,
+        treasury: anchor_spl::associated_token::get_associated_token_address(
+            &smart_wallet,
+            &voting_reward.reward_mint,
+        ),
+        smart_wallet,
+        token_program: anchor_spl::token::ID,
+        event_authority,
+        program: govern::ID,
+    }
+    .to_account_metas(None)
+    .into_iter()
+    .map(|meta| smart_wallet::TXAccountMeta {
+        pubkey: meta.pubkey,
+        is_signer: meta.is_signer,
+        is_writable: meta.is_writable,
+    })
+    .collect();
+    let instruction = smart_wallet::TXInstruction {
+        program_id: govern::ID,
+        keys,
+        data,
+    };
+
+    create_transaction(program, base, vec![instruction])
+}
+
 fn approve_transaction<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
     base: Pubkey,
@@ -469,10 +1026,43 @@ fn remove_transaction<C: Deref<Target = impl Signer> + Clone>(
     Ok(())
 }
 
+fn cancel_transaction<C: Deref<Target = impl Signer> + Clone>(
+    program: &Program<C>,
+    base: Pubkey,
+    transaction: Pubkey,
+) -> Result<()> {
+    let (smart_wallet, _bump) = Pubkey::find_program_address(
+        &[b"SmartWallet".as_ref(), base.as_ref()],
+        &smart_wallet::id(),
+    );
+    println!("Cancel transaction {}", transaction);
+    // the rent of the transaction returns to its payer
+    let transaction_state: smart_wallet::Transaction = program.account(transaction)?;
+    let smart_wallet_state: smart_wallet::SmartWallet = program.account(smart_wallet)?;
+    let data = program.rpc().get_account_data(&transaction)?;
+    let payer = transaction_state
+        .recorded_payer(&data, smart_wallet_state.max_owners as usize)
+        .unwrap_or(transaction_state.proposer);
+    let builder = program
+        .request()
+        .accounts(smart_wallet::accounts::CancelTransaction {
+            smart_wallet,
+            transaction,
+            proposer: program.payer(),
+            payer,
+        })
+        .args(smart_wallet::instruction::CancelTransaction {});
+    let signature = builder.send()?;
+    println!("Signature {:?}", signature);
+    Ok(())
+}
+
 fn execute_transaction<C: Deref<Target = impl Signer> + Clone>(
     program: &Program<C>,
+    payer: &Keypair,
     base: Pubkey,
     transaction: Pubkey,
+    lookup_tables: Vec<Pubkey>,
 ) -> Result<()> {
     let (smart_wallet, _bump) = Pubkey::find_program_address(
         &[b"SmartWallet".as_ref(), base.as_ref()],
@@ -481,6 +1071,7 @@ fn execute_transaction<C: Deref<Target = impl Signer> + Clone>(
 
     println!("Execute transaction {}", transaction);
     let tx_account: smart_wallet::Transaction = program.account(transaction)?;
+    let rpc_client = program.rpc();
     let mut remaining_accounts = vec![];
     for ix in tx_account.instructions.iter() {
         remaining_accounts.push(AccountMeta::new_readonly(ix.program_id, false));
@@ -492,6 +1083,11 @@ fn execute_transaction<C: Deref<Target = impl Signer> + Clone>(
             });
         }
     }
+    // the accounts of the instructions may be compressed with lookup tables
+    let lookup_tables = lookup_tables
+        .into_iter()
+        .map(|table| fetch_lookup_table(&rpc_client, table))
+        .collect::<Result<Vec<AddressLookupTableAccount>>>()?;
 
     let mut accounts = smart_wallet::accounts::ExecuteTransaction {
         smart_wallet,
@@ -511,7 +1107,11 @@ fn execute_transaction<C: Deref<Target = impl Signer> + Clone>(
     // println!("{:?}", result);
     // return Ok(());
 
-    let signature = builder.send()?;
+    let signature = if lookup_tables.is_empty() {
+        builder.send()?
+    } else {
+        send_versioned_transaction(&rpc_client, &builder.instructions()?, &lookup_tables, payer)?
+    };
     println!("Signature {:?}", signature);
     Ok(())
 }
@@ -2,7 +2,12 @@ use anchor_client::solana_client::rpc_client::RpcClient;
 use anchor_client::solana_client::rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig};
 use anchor_client::solana_client::rpc_filter::{Memcmp, RpcFilterType};
 use anchor_client::solana_client::rpc_response::RpcSimulateTransactionResult;
-use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair};
+use anchor_client::solana_sdk::address_lookup_table_account::AddressLookupTableAccount;
+use anchor_client::solana_sdk::ed25519_program;
+use anchor_client::solana_sdk::instruction::Instruction;
+use anchor_client::solana_sdk::message::{v0, VersionedMessage};
+use anchor_client::solana_sdk::signature::{read_keypair_file, Keypair, Signature};
+use anchor_client::solana_sdk::transaction::VersionedTransaction;
 use anchor_client::RequestBuilder;
 use anchor_client::{
     solana_client::rpc_response::Response,
@@ -15,6 +20,7 @@ use anyhow::Result;
 use regex::Regex;
 use solana_account_decoder::UiAccountEncoding;
 use std::ops::Deref;
+use std::str::FromStr;
 use std::time::Duration;
 
 pub fn parse_event_log<
@@ -71,6 +77,87 @@ pub fn simulate_transaction<C: Deref<Target = impl Signer> + Clone>(
     Ok(simulation)
 }
 
+/// Size of the metadata preceding the addresses of an address lookup table.
+const LOOKUP_TABLE_META_SIZE: usize = 56;
+
+pub fn fetch_lookup_table(
+    rpc_client: &RpcClient,
+    key: Pubkey,
+) -> Result<AddressLookupTableAccount> {
+    let data = rpc_client.get_account_data(&key)?;
+    let addresses = data
+        .get(LOOKUP_TABLE_META_SIZE..)
+        .unwrap_or_default()
+        .chunks_exact(32)
+        .map(|address| Pubkey::try_from(address).unwrap())
+        .collect();
+    Ok(AddressLookupTableAccount { key, addresses })
+}
+
+pub fn send_versioned_transaction(
+    rpc_client: &RpcClient,
+    instructions: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    payer: &dyn Signer,
+) -> Result<Signature> {
+    let recent_blockhash = rpc_client.get_latest_blockhash()?;
+    let message = v0::Message::try_compile(
+        &payer.pubkey(),
+        instructions,
+        lookup_tables,
+        recent_blockhash,
+    )?;
+    let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer])?;
+    let signature = rpc_client.send_and_confirm_transaction(&tx)?;
+    Ok(signature)
+}
+
+/// Builds an ed25519 program instruction checking a single signature,
+/// with the public key, signature and message in the instruction itself.
+pub fn new_ed25519_instruction(
+    pubkey: &Pubkey,
+    signature: &Signature,
+    message: &[u8],
+) -> Instruction {
+    let public_key_offset: u16 = 2 + 14;
+    let signature_offset = public_key_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = vec![1, 0];
+    for value in [
+        signature_offset,
+        u16::MAX,
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(pubkey.as_ref());
+    data.extend_from_slice(signature.as_ref());
+    data.extend_from_slice(message);
+
+    Instruction {
+        program_id: ed25519_program::ID,
+        accounts: vec![],
+        data,
+    }
+}
+
+/// Parses a signed vote given as ESCROW_OWNER:SIDE:NONCE:SIGNATURE.
+pub fn parse_signed_vote(signed_vote: &str) -> Result<(Pubkey, u8, u64, Signature)> {
+    let parts: Vec<&str> = signed_vote.split(':').collect();
+    anyhow::ensure!(parts.len() == 4, "invalid signed vote {}", signed_vote);
+    Ok((
+        Pubkey::from_str(parts[0])?,
+        parts[1].parse()?,
+        parts[2].parse()?,
+        Signature::from_str(parts[3])?,
+    ))
+}
+
 pub fn default_keypair() -> Keypair {
     read_keypair_file(&*shellexpand::tilde("~/.config/solana/id.json"))
         .expect("Requires a keypair file")
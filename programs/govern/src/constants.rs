@@ -11,3 +11,18 @@ pub const AGAINST_VOTE_INDEX: usize = 1;
 
 #[constant]
 pub const FOR_VOTE_INDEX: usize = 2;
+
+#[constant]
+pub const MAX_BPS: u16 = 10_000;
+
+#[constant]
+pub const MAX_PROPOSAL_REWARDS: u8 = 16;
+
+#[constant]
+pub const MAX_INSTRUCTIONS_CHUNK_SPACE: usize = 8_192;
+
+#[constant]
+pub const MAX_PROPOSAL_CLASS_NAME_LEN: usize = 32;
+
+#[constant]
+pub const SIGNED_VOTE_DOMAIN: &[u8] = b"govern:vote";
@@ -8,18 +8,24 @@ pub struct ActivateProposal<'info> {
     /// The [Proposal] to activate.
     #[account(mut)]
     pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal].
+    #[account(has_one = proposal)]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
     /// The locker of the [Governor] that may activate the proposal.
     pub locker: Signer<'info>,
 }
 
 impl<'info> ActivateProposal<'info> {
-    pub fn activate_proposal(&mut self) -> Result<()> {
+    pub fn activate_proposal(&mut self, total_voting_power: u64) -> Result<()> {
         let proposal = &mut self.proposal;
         let now = Clock::get()?.unix_timestamp;
         proposal.activated_at = now;
+        if proposal.quorum_bps > 0 {
+            proposal.quorum_votes =
+                unwrap_int!(proposal.quorum_from_voting_power(total_voting_power));
+        }
         proposal.voting_ends_at = unwrap_int!(self
-            .governor
-            .params
+            .proposal_params
             .voting_period
             .to_i64()
             .and_then(|v: i64| now.checked_add(v)));
@@ -28,6 +34,7 @@ impl<'info> ActivateProposal<'info> {
             governor: proposal.governor,
             proposal: proposal.key(),
             voting_ends_at: proposal.voting_ends_at,
+            quorum_votes: proposal.quorum_votes,
         });
 
         Ok(())
@@ -42,12 +49,14 @@ impl<'info> Validate<'info> for ActivateProposal<'info> {
             self.proposal.get_state()? == ProposalState::Draft,
             ProposalNotDraft
         );
+        // voters must know every instruction of the proposal
+        invariant!(self.proposal_params.is_sealed(), ProposalNotSealed);
 
+        // the delay lets voters review the instructions, so it runs from the sealing
         let earliest_activation_time = unwrap_int!(self
-            .governor
-            .params
+            .proposal_params
             .voting_delay
-            .checked_add(self.proposal.created_at as u64));
+            .checked_add(self.proposal_params.sealed_at as u64));
         let now = Clock::get()?.unix_timestamp as u64;
         if earliest_activation_time > now {
             msg!(
@@ -73,4 +82,6 @@ pub struct ProposalActivateEvent {
     pub proposal: Pubkey,
     /// When voting ends for the [Proposal].
     pub voting_ends_at: i64,
+    /// The quorum of the [Proposal].
+    pub quorum_votes: u64,
 }
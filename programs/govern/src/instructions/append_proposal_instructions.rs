@@ -0,0 +1,191 @@
+use crate::*;
+use smart_wallet::TXLookupInstruction;
+
+/// Accounts for [govern::append_proposal_instructions].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(instructions: Vec<TXLookupInstruction>)]
+pub struct AppendProposalInstructions<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal], holding the instructions unless they are kept in the [ProposalParams].
+    #[account(
+        mut,
+        has_one = governor,
+        has_one = proposer,
+        realloc = proposal.to_account_info().data_len()
+            + if proposal_params.keeps_lookup_instructions(&instructions) {
+                0
+            } else {
+                instructions.iter().map(|ix| ix.space()).sum::<usize>()
+            },
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal], holding the instructions which reference
+    /// lookup tables.
+    #[account(
+        mut,
+        has_one = proposal,
+        realloc = proposal_params.to_account_info().data_len()
+            + if proposal_params.keeps_lookup_instructions(&instructions) {
+                ProposalParams::lookup_instructions_space(&instructions)
+            } else {
+                0
+            },
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
+    /// Proposer of the proposal.
+    pub proposer: Signer<'info>,
+    /// Payer of the larger [Proposal] or [ProposalParams].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+    /// The [Governor::proposal_policy], if the governor has one.
+    pub proposal_policy: Option<Box<Account<'info, ProposalPolicy>>>,
+    /// The [ProposalParams::proposal_class], if the proposal has one.
+    pub proposal_class: Option<Box<Account<'info, ProposalClass>>>,
+}
+
+impl<'info> AppendProposalInstructions<'info> {
+    pub fn append_proposal_instructions(
+        &mut self,
+        instructions: Vec<TXLookupInstruction>,
+        lookup_tables: &[AccountInfo],
+    ) -> Result<ProposalInstructionsAppendEvent> {
+        // the policies check the actual accounts
+        let resolved_instructions = instructions
+            .iter()
+            .map(|ix| Ok(ix.resolve(lookup_tables)?.into()))
+            .collect::<Result<Vec<ProposalInstruction>>>()?;
+        if let Some(proposal_class) = &self.proposal_class {
+            proposal_class.check_instructions(&resolved_instructions)?;
+        }
+        if let Some(proposal_policy) = &self.proposal_policy {
+            if let Some(policy_params) =
+                proposal_policy.check_instructions(&resolved_instructions)?
+            {
+                let params = self
+                    .proposal
+                    .params(&self.proposal_params)
+                    .strictest(&policy_params);
+                self.proposal.set_params(&mut self.proposal_params, &params);
+            }
+        }
+
+        let proposal = &mut self.proposal;
+        let proposal_params = &mut self.proposal_params;
+        if proposal_params.keeps_lookup_instructions(&instructions) {
+            proposal_params.lookup_instructions.extend(instructions);
+        } else {
+            proposal
+                .instructions
+                .extend(resolved_instructions.iter().cloned());
+        }
+        // the proposal must be sealed again before it is activated
+        proposal_params.sealed_at = 0;
+        proposal_params.instructions_hash = [0; 32];
+
+        Ok(ProposalInstructionsAppendEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            instructions: resolved_instructions,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for AppendProposalInstructions<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.proposal.proposal_type == u8::from(ProposalType::YesNo),
+            InvalidProposalType
+        );
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Draft,
+            ProposalNotDraft
+        );
+        if self.governor.proposal_policy != Pubkey::default() {
+            let proposal_policy =
+                unwrap_opt!(self.proposal_policy.as_ref(), "proposal policy is required");
+            assert_keys_eq!(proposal_policy.key(), self.governor.proposal_policy);
+        }
+        if self.proposal_params.proposal_class != Pubkey::default() {
+            let proposal_class =
+                unwrap_opt!(self.proposal_class.as_ref(), "proposal class is required");
+            assert_keys_eq!(proposal_class.key(), self.proposal_params.proposal_class);
+        }
+        Ok(())
+    }
+}
+
+/// Accounts for [govern::seal_proposal_instructions].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SealProposalInstructions<'info> {
+    /// The [Proposal].
+    #[account(has_one = proposer)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal].
+    #[account(mut, has_one = proposal)]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
+    /// Proposer of the proposal.
+    pub proposer: Signer<'info>,
+}
+
+impl<'info> SealProposalInstructions<'info> {
+    pub fn seal_proposal_instructions(&mut self) -> Result<ProposalInstructionsSealEvent> {
+        let proposal = &self.proposal;
+        let proposal_params = &mut self.proposal_params;
+        proposal_params.seal_instructions(proposal, Clock::get()?.unix_timestamp)?;
+
+        Ok(ProposalInstructionsSealEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            instruction_count: proposal.instructions.len() as u32,
+            instructions_hash: proposal_params.instructions_hash,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for SealProposalInstructions<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(!self.proposal_params.is_sealed(), ProposalSealed);
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Draft,
+            ProposalNotDraft
+        );
+        Ok(())
+    }
+}
+
+/// Event called in [govern::append_proposal_instructions].
+#[event]
+pub struct ProposalInstructionsAppendEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// The instructions appended, with their accounts resolved.
+    pub instructions: Vec<ProposalInstruction>,
+}
+
+/// Event called in [govern::seal_proposal_instructions].
+#[event]
+pub struct ProposalInstructionsSealEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// Number of instructions of the proposal.
+    pub instruction_count: u32,
+    /// The [ProposalParams::instructions_hash].
+    pub instructions_hash: [u8; 32],
+}
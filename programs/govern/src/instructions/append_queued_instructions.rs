@@ -0,0 +1,125 @@
+use crate::*;
+use smart_wallet::TransactionBuffer;
+
+/// Accounts for [govern::append_queued_instructions].
+#[derive(Accounts)]
+pub struct AppendQueuedInstructions<'info> {
+    /// The Governor.
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The Proposal to queue.
+    #[account(has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal].
+    #[account(has_one = proposal)]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
+    /// The [TransactionBuffer] of the [Proposal], created by the first call.
+    /// This account is passed to and validated by the Smart Wallet program.
+    #[account(
+        mut,
+        seeds = [
+            b"TransactionBuffer".as_ref(),
+            smart_wallet.key().as_ref(),
+            governor.key().as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+        seeds::program = smart_wallet_program.key()
+    )]
+    /// CHECK: checked by the [smart_wallet] program.
+    pub transaction_buffer: UncheckedAccount<'info>,
+    /// The Smart Wallet.
+    pub smart_wallet: Account<'info, SmartWallet>,
+    /// Payer of the [TransactionBuffer].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The [OptionInstructions] of the winning option of a multi-option proposal,
+    /// if the option has a bundle.
+    pub option_instructions: Option<Box<Account<'info, OptionInstructions>>>,
+    /// The Smart Wallet program.
+    pub smart_wallet_program: Program<'info, smart_wallet::program::SmartWallet>,
+    /// The System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AppendQueuedInstructions<'info> {
+    /// Appends the next chunk of instructions to the [TransactionBuffer] of the Proposal.
+    pub fn append_queued_instructions(&mut self, lookup_tables: &[AccountInfo]) -> Result<()> {
+        let instructions = queued_instructions(
+            &self.proposal,
+            &self.proposal_params,
+            self.option_instructions.as_deref().map(|bundle| &**bundle),
+            lookup_tables,
+        )?;
+        let buffered_count = if self.transaction_buffer.data_is_empty() {
+            0
+        } else {
+            let transaction_buffer: Account<TransactionBuffer> =
+                Account::try_from(&self.transaction_buffer)?;
+            transaction_buffer.instructions.len()
+        };
+        let remaining_instructions = unwrap_opt!(
+            instructions.get(buffered_count..),
+            "transaction buffer holds more instructions than the proposal"
+        );
+        invariant!(
+            !remaining_instructions.is_empty(),
+            "all instructions have been buffered"
+        );
+        let chunk: Vec<smart_wallet::TXLookupInstruction> = remaining_instructions
+            [..instructions_chunk_len(remaining_instructions)]
+            .iter()
+            .cloned()
+            .map(Into::into)
+            .collect();
+
+        let seeds = governor_seeds!(self.governor);
+        let signer_seeds = &[&seeds[..]];
+        if buffered_count == 0 {
+            smart_wallet::cpi::create_transaction_buffer(
+                CpiContext::new_with_signer(
+                    self.smart_wallet_program.to_account_info(),
+                    smart_wallet::cpi::accounts::CreateTransactionBuffer {
+                        smart_wallet: self.smart_wallet.to_account_info(),
+                        transaction_buffer: self.transaction_buffer.to_account_info(),
+                        proposer: self.governor.to_account_info(),
+                        payer: self.payer.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                self.proposal.key(),
+                chunk,
+            )
+        } else {
+            smart_wallet::cpi::append_transaction_buffer(
+                CpiContext::new_with_signer(
+                    self.smart_wallet_program.to_account_info(),
+                    smart_wallet::cpi::accounts::AppendTransactionBuffer {
+                        smart_wallet: self.smart_wallet.to_account_info(),
+                        transaction_buffer: self.transaction_buffer.to_account_info(),
+                        proposer: self.governor.to_account_info(),
+                        payer: self.payer.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                chunk,
+            )
+        }
+    }
+}
+
+impl<'info> Validate<'info> for AppendQueuedInstructions<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Succeeded,
+            "proposal must be succeeded to be queued"
+        );
+        validate_winning_option_instructions(
+            &self.proposal,
+            self.option_instructions.as_deref().map(|bundle| &**bundle),
+        )?;
+        Ok(())
+    }
+}
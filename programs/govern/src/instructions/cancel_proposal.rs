@@ -1,4 +1,14 @@
 use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Why a [Proposal] was canceled.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProposalCancelReason {
+    /// Withdrawn by the [Proposal::proposer].
+    Withdrawn,
+    /// Force-canceled by the [Governor::smart_wallet].
+    ForceCanceled,
+}
 
 /// Accounts for [govern::cancel_proposal].
 #[event_cpi]
@@ -6,22 +16,94 @@ use crate::*;
 pub struct CancelProposal<'info> {
     /// The [Governor].
     pub governor: Box<Account<'info, Governor>>,
-    /// The [Proposal] to activate.
+    /// The [Proposal] to cancel. It is closed along with its metadata and params, unless
+    /// its [ProposalReward]s must first be refunded with [govern::refund_proposal_reward].
     #[account(mut)]
     pub proposal: Box<Account<'info, Proposal>>,
-    /// The [Proposal::proposer].
+    /// The [Proposal::proposer], who receives the rent of the deposit escrow and of the metadata it paid for.
+    #[account(mut)]
     pub proposer: Signer<'info>,
+    /// The [Proposal::payer], who receives the rent of the [Proposal].
+    /// CHECK: checked against [Proposal::rent_payer].
+    #[account(mut, address = proposal.rent_payer())]
+    pub payer: UncheckedAccount<'info>,
+    /// Escrow of the [Proposal::deposit_amount], if the deposit has not been settled.
+    #[account(mut)]
+    pub deposit_vault: Option<Box<Account<'info, TokenAccount>>>,
+    /// Token account of the [Governor::smart_wallet], which receives the slashed deposit.
+    #[account(mut)]
+    pub treasury: Option<Box<Account<'info, TokenAccount>>>,
+    /// Token program, if the deposit has not been settled.
+    pub token_program: Option<Program<'info, Token>>,
+    /// The [ProposalMeta] of the proposal, if any, closed along with it.
+    #[account(
+        mut,
+        seeds = [
+            b"ProposalMeta".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+    )]
+    pub proposal_meta: Option<Box<Account<'info, ProposalMeta>>>,
+    /// The payer of the [ProposalMeta], if it is not the proposer.
+    /// CHECK: checked in [Validate] against the payer recorded in the [ProposalMeta].
+    #[account(mut)]
+    pub proposal_meta_payer: Option<UncheckedAccount<'info>>,
+    /// The [OptionProposalMeta] of the proposal, if any, closed along with it.
+    #[account(
+        mut,
+        seeds = [
+            b"OptionProposalMeta".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+    )]
+    pub option_proposal_meta: Option<Box<Account<'info, OptionProposalMeta>>>,
+    /// The payer of the [OptionProposalMeta], if it is not the proposer.
+    /// CHECK: checked in [Validate] against the payer recorded in the [OptionProposalMeta].
+    #[account(mut)]
+    pub option_proposal_meta_payer: Option<UncheckedAccount<'info>>,
+    /// The [ProposalParams] of the proposal, closed along with it to the [Proposal::payer].
+    /// Proposals created before [ProposalParams] existed may not have any.
+    #[account(
+        mut,
+        seeds = [
+            b"ProposalParams".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+    )]
+    pub proposal_params: Option<Box<Account<'info, ProposalParams>>>,
 }
 
 impl<'info> CancelProposal<'info> {
     pub fn cancel_proposal(&mut self) -> Result<ProposalCancelEvent> {
-        let proposal = &mut self.proposal;
-        proposal.canceled_at = Clock::get()?.unix_timestamp;
+        let event = cancel_and_slash_deposit(
+            &self.governor,
+            &mut self.proposal,
+            self.proposer.to_account_info(),
+            &self.deposit_vault,
+            &self.treasury,
+            &self.token_program,
+            ProposalCancelReason::Withdrawn,
+        )?;
 
-        Ok(ProposalCancelEvent {
-            governor: proposal.governor,
-            proposal: proposal.key(),
-        })
+        // the proposal is needed to refund its rewards, so it is kept until
+        // closed with [govern::close_proposal]
+        if !self.proposal.has_unrefunded_rewards() {
+            close_proposal_metas(
+                self.proposer.to_account_info(),
+                &self.proposal_meta,
+                &self.proposal_meta_payer,
+                &self.option_proposal_meta,
+                &self.option_proposal_meta_payer,
+            )?;
+            if let Some(proposal_params) = &self.proposal_params {
+                proposal_params.close(self.payer.to_account_info())?;
+            }
+            self.proposal.close(self.payer.to_account_info())?;
+        }
+        Ok(event)
     }
 }
 
@@ -37,14 +119,138 @@ impl<'info> Validate<'info> for CancelProposal<'info> {
             self.proposal.governor,
             "proposal should be under the governor"
         );
+        match self.proposal.get_state()? {
+            ProposalState::Draft => {}
+            ProposalState::Active => {
+                // the proposer may only withdraw an active proposal before anyone has voted
+                invariant!(
+                    unwrap_opt!(self.proposal.total_votes(), "Math is overflow") == 0,
+                    ProposalHasVotes
+                );
+            }
+            _ => return Err(error!(ErrorCode::ProposalNotCancelable)),
+        }
+        validate_meta_payers(
+            self.proposer.key(),
+            &self.proposal_meta,
+            &self.proposal_meta_payer,
+            &self.option_proposal_meta,
+            &self.option_proposal_meta_payer,
+        )?;
+        validate_deposit_accounts(
+            &self.governor,
+            &self.proposal,
+            &self.deposit_vault,
+            &self.treasury,
+            &self.token_program,
+        )
+    }
+}
+
+/// Accounts for [govern::force_cancel_proposal].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ForceCancelProposal<'info> {
+    /// The [Governor].
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal] to cancel.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Governor::smart_wallet].
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> ForceCancelProposal<'info> {
+    /// Marks the proposal as canceled. Its deposit is slashed with
+    /// [govern::settle_proposal_deposit], and its rent is returned by [govern::close_proposal].
+    pub fn force_cancel_proposal(&mut self) -> Result<ProposalCancelEvent> {
+        let proposal = &mut self.proposal;
+        proposal.canceled_at = Clock::get()?.unix_timestamp;
+
+        Ok(ProposalCancelEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            reason: ProposalCancelReason::ForceCanceled,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for ForceCancelProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(
+            self.governor,
+            self.proposal.governor,
+            "proposal should be under the governor"
+        );
+        // proposals which have been decided keep their outcome, deposit and rewards
         invariant!(
-            self.proposal.get_state()? == ProposalState::Draft,
-            ProposalNotDraft
+            matches!(
+                self.proposal.get_state()?,
+                ProposalState::Draft | ProposalState::Active
+            ),
+            ProposalNotCancelable
         );
         Ok(())
     }
 }
-/// Event called in [govern::cancel_proposal].
+
+/// Marks the proposal as canceled, slashing its unsettled deposit to the treasury.
+fn cancel_and_slash_deposit<'info>(
+    governor: &Account<'info, Governor>,
+    proposal: &mut Account<'info, Proposal>,
+    proposer: AccountInfo<'info>,
+    deposit_vault: &Option<Box<Account<'info, TokenAccount>>>,
+    treasury: &Option<Box<Account<'info, TokenAccount>>>,
+    token_program: &Option<Program<'info, Token>>,
+    reason: ProposalCancelReason,
+) -> Result<ProposalCancelEvent> {
+    let now = Clock::get()?.unix_timestamp;
+    if proposal.has_unsettled_deposit() {
+        release_proposal_deposit(
+            governor,
+            unwrap_opt!(deposit_vault.as_ref()).to_account_info(),
+            unwrap_opt!(treasury.as_ref()).to_account_info(),
+            proposer,
+            unwrap_opt!(token_program.as_ref()).to_account_info(),
+            proposal.deposit_amount,
+        )?;
+        proposal.deposit_settled_at = now;
+    }
+    proposal.canceled_at = now;
+
+    Ok(ProposalCancelEvent {
+        governor: proposal.governor,
+        proposal: proposal.key(),
+        reason,
+    })
+}
+
+/// Validates the accounts needed to slash an unsettled deposit.
+fn validate_deposit_accounts<'info>(
+    governor: &Account<'info, Governor>,
+    proposal: &Account<'info, Proposal>,
+    deposit_vault: &Option<Box<Account<'info, TokenAccount>>>,
+    treasury: &Option<Box<Account<'info, TokenAccount>>>,
+    token_program: &Option<Program<'info, Token>>,
+) -> Result<()> {
+    if !proposal.has_unsettled_deposit() {
+        return Ok(());
+    }
+    let deposit_vault = unwrap_opt!(deposit_vault.as_ref(), "deposit vault is required");
+    let (expected_vault, _) = Pubkey::find_program_address(
+        &[b"ProposalDeposit".as_ref(), proposal.key().as_ref()],
+        &crate::ID,
+    );
+    assert_keys_eq!(deposit_vault.key(), expected_vault);
+    let treasury = unwrap_opt!(treasury.as_ref(), "treasury is required");
+    assert_keys_eq!(treasury.owner, governor.smart_wallet);
+    assert_keys_eq!(treasury.mint, deposit_vault.mint);
+    invariant!(token_program.is_some(), "token program is required");
+    Ok(())
+}
+
+/// Event called in [govern::cancel_proposal] and [govern::force_cancel_proposal].
 #[event]
 pub struct ProposalCancelEvent {
     /// The governor.
@@ -53,4 +259,6 @@ pub struct ProposalCancelEvent {
     /// The proposal being canceled.
     #[index]
     pub proposal: Pubkey,
+    /// Why the proposal was canceled.
+    pub reason: ProposalCancelReason,
 }
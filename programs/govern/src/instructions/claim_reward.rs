@@ -1,6 +1,9 @@
 use crate::*;
 use anchor_spl::token::{self, Token, TokenAccount};
 /// Accounts for [govern::claim_reward].
+///
+/// The [ProposalReward]s to claim are passed in the remaining accounts,
+/// as `[proposal_reward, reward_vault, voter_token_account]` triples.
 #[event_cpi]
 #[derive(Accounts)]
 pub struct ClaimReward<'info> {
@@ -16,8 +19,8 @@ pub struct ClaimReward<'info> {
     /// The [Vote].
     #[account(mut, has_one = proposal, has_one = voter)]
     pub vote: Box<Account<'info, Vote>>,
-    /// Owner of the vault
-    /// TODO: check whether vote delegrate can claim on behalf of owner?
+    /// Owner of the vault.
+    /// Vote delegates and crankers should use [govern::claim_reward_for_voter] instead.
     pub voter: Signer<'info>,
     /// Voter token account
     #[account(mut)]
@@ -27,66 +30,57 @@ pub struct ClaimReward<'info> {
 }
 
 impl<'info> ClaimReward<'info> {
-    pub fn claim_reward(&mut self) -> Result<ClaimRewardEvent> {
-        self.vote.claimed = true;
-
-        let voting_reward = unwrap_opt!(
-            self.proposal.get_voting_reward(&self.vote),
-            "Math is overflow"
+    pub fn claim_reward(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(Option<ClaimRewardEvent>, Vec<ProposalRewardClaimEvent>)> {
+        invariant!(
+            !self.vote.claimed || !remaining_accounts.is_empty(),
+            "Voter has claimed reward"
         );
 
-        if voting_reward != 0 {
-            // transfer tokens to the escrow
-            let seeds = governor_seeds!(self.governor);
-            let signer_seeds = &[&seeds[..]];
-            token::transfer(
-                CpiContext::new_with_signer(
-                    self.token_program.to_account_info(),
-                    token::Transfer {
-                        from: self.reward_vault.to_account_info(),
-                        to: self.voter_token_account.to_account_info(),
-                        authority: self.governor.to_account_info(),
-                    },
-                    signer_seeds,
-                ),
-                voting_reward,
+        // the reward of the governor is claimed once, alongside any proposal reward
+        let event = if self.vote.claimed {
+            None
+        } else {
+            let voting_reward = transfer_voting_reward(
+                &self.governor,
+                &mut self.proposal,
+                &mut self.vote,
+                self.reward_vault.to_account_info(),
+                self.voter_token_account.to_account_info(),
+                self.token_program.to_account_info(),
             )?;
+            Some(ClaimRewardEvent {
+                governor: self.governor.key(),
+                voter: self.voter.key(),
+                proposal: self.proposal.key(),
+                voting_reward,
+            })
+        };
 
-            self.proposal.total_claimed_reward = unwrap_opt!(
-                self.proposal
-                    .total_claimed_reward
-                    .checked_add(voting_reward),
-                "Math is overflow"
-            );
-        }
+        let reward_events = transfer_proposal_rewards(
+            &self.governor,
+            &self.proposal,
+            &mut self.vote,
+            self.voter.key(),
+            remaining_accounts,
+            self.token_program.to_account_info(),
+        )?;
 
-        Ok(ClaimRewardEvent {
-            governor: self.governor.key(),
-            voter: self.voter.key(),
-            proposal: self.proposal.key(),
-            voting_reward,
-        })
+        Ok((event, reward_events))
     }
 }
 
 impl<'info> Validate<'info> for ClaimReward<'info> {
     fn validate(&self) -> Result<()> {
         let voting_reward = self.proposal.voting_reward;
-        assert_keys_eq!(voting_reward.reward_vault, self.reward_vault);
+        // proposals rewarded only by [ProposalReward]s have no reward vault on the governor
+        if voting_reward.reward_per_proposal > 0 {
+            assert_keys_eq!(voting_reward.reward_vault, self.reward_vault);
+        }
 
-        invariant!(
-            unwrap_opt!(self.proposal.meets_quorum(), "Math is overflow"),
-            "Proposal doesn't meet quorum"
-        );
-        let now = Clock::get()?.unix_timestamp;
-        let proposal_state = unwrap_opt!(self.proposal.state(now), "invalid state");
-        invariant!(
-            proposal_state == ProposalState::Defeated
-                || proposal_state == ProposalState::Queued
-                || proposal_state == ProposalState::Succeeded,
-            "Proposal must be defeated, queued or succeeded"
-        );
-        invariant!(!self.vote.claimed, "Voter has claimed reward");
+        assert_rewards_claimable(&self.proposal)?;
 
         invariant!(
             self.voter_token_account.owner == self.voter.key(),
@@ -96,6 +90,191 @@ impl<'info> Validate<'info> for ClaimReward<'info> {
     }
 }
 
+/// Checks that the reward of a [Vote] on a [Proposal] can be claimed.
+pub(crate) fn assert_reward_claimable(proposal: &Proposal, vote: &Vote) -> Result<()> {
+    assert_rewards_claimable(proposal)?;
+    invariant!(!vote.claimed, "Voter has claimed reward");
+    Ok(())
+}
+
+/// Checks that the rewards of a [Proposal] can be claimed.
+pub(crate) fn assert_rewards_claimable(proposal: &Proposal) -> Result<()> {
+    invariant!(
+        unwrap_opt!(proposal.meets_quorum(), "Math is overflow"),
+        "Proposal doesn't meet quorum"
+    );
+    let now = Clock::get()?.unix_timestamp;
+    let proposal_state = unwrap_opt!(proposal.state(now), "invalid state");
+    invariant!(
+        proposal_state == ProposalState::Defeated
+            || proposal_state == ProposalState::Queued
+            || proposal_state == ProposalState::Succeeded
+            || proposal_state == ProposalState::Executed
+            || proposal_state == ProposalState::Expired,
+        "Proposal must be over"
+    );
+    if let Some(claim_ends_at) = proposal.reward_claim_ends_at() {
+        invariant!(now <= claim_ends_at, RewardClaimWindowOver);
+    }
+    Ok(())
+}
+
+/// Marks the [Vote] as claimed and transfers its share of the reward
+/// from the reward vault to the voter's token account.
+pub(crate) fn transfer_voting_reward<'info>(
+    governor: &Account<'info, Governor>,
+    proposal: &mut Proposal,
+    vote: &mut Vote,
+    reward_vault: AccountInfo<'info>,
+    voter_token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    vote.claimed = true;
+
+    let voting_reward = unwrap_opt!(proposal.get_voting_reward(vote), "Math is overflow");
+
+    if voting_reward != 0 {
+        // transfer tokens to the escrow
+        let seeds = governor_seeds!(governor);
+        let signer_seeds = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                token::Transfer {
+                    from: reward_vault,
+                    to: voter_token_account,
+                    authority: governor.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            voting_reward,
+        )?;
+
+        proposal.total_claimed_reward = unwrap_opt!(
+            proposal.total_claimed_reward.checked_add(voting_reward),
+            "Math is overflow"
+        );
+    }
+
+    Ok(voting_reward)
+}
+
+/// Transfers the share of the [Vote] of the [ProposalReward]s passed in the remaining accounts,
+/// as `[proposal_reward, reward_vault, voter_token_account]` triples.
+pub(crate) fn transfer_proposal_rewards<'info>(
+    governor: &Account<'info, Governor>,
+    proposal: &Account<'info, Proposal>,
+    vote: &mut Vote,
+    voter: Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    token_program: AccountInfo<'info>,
+) -> Result<Vec<ProposalRewardClaimEvent>> {
+    let triples = remaining_accounts.chunks_exact(3);
+    invariant!(
+        triples.remainder().is_empty(),
+        "Remaining accounts must be [proposal_reward, reward_vault, voter_token_account] triples"
+    );
+
+    let mut reward_events = Vec::with_capacity(triples.len());
+    for accounts in triples {
+        let mut proposal_reward = Account::<ProposalReward>::try_from(&accounts[0])?;
+        let voter_token_account = Account::<TokenAccount>::try_from(&accounts[2])?;
+
+        assert_keys_eq!(
+            proposal_reward.proposal,
+            proposal.key(),
+            "proposal reward should match"
+        );
+        assert_keys_eq!(proposal_reward.reward_vault, accounts[1].key());
+        assert_keys_eq!(voter_token_account.owner, voter);
+        assert_keys_eq!(voter_token_account.mint, proposal_reward.reward_mint);
+        invariant!(
+            !vote.has_claimed_reward(proposal_reward.index),
+            "Voter has claimed reward"
+        );
+
+        let reward = transfer_proposal_reward(
+            governor,
+            proposal,
+            vote,
+            &mut proposal_reward,
+            accounts[1].to_account_info(),
+            voter_token_account.to_account_info(),
+            token_program.clone(),
+        )?;
+        proposal_reward.exit(&crate::ID)?;
+
+        reward_events.push(ProposalRewardClaimEvent {
+            governor: governor.key(),
+            voter,
+            proposal: proposal.key(),
+            reward_mint: proposal_reward.reward_mint,
+            reward,
+        });
+    }
+
+    Ok(reward_events)
+}
+
+/// Marks the [ProposalReward] as claimed by the [Vote] and transfers its share
+/// from the reward vault to the voter's token account.
+pub(crate) fn transfer_proposal_reward<'info>(
+    governor: &Account<'info, Governor>,
+    proposal: &Proposal,
+    vote: &mut Vote,
+    proposal_reward: &mut ProposalReward,
+    reward_vault: AccountInfo<'info>,
+    voter_token_account: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<u64> {
+    vote.claimed_rewards |= 1 << proposal_reward.index;
+
+    let reward = unwrap_opt!(
+        proposal.get_reward_share(vote, proposal_reward.total_reward),
+        "Math is overflow"
+    );
+
+    if reward != 0 {
+        let seeds = governor_seeds!(governor);
+        let signer_seeds = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                token::Transfer {
+                    from: reward_vault,
+                    to: voter_token_account,
+                    authority: governor.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            reward,
+        )?;
+
+        proposal_reward.total_claimed_reward = unwrap_opt!(
+            proposal_reward.total_claimed_reward.checked_add(reward),
+            "Math is overflow"
+        );
+    }
+
+    Ok(reward)
+}
+
+/// Event called in [govern::claim_reward] for each [ProposalReward].
+#[event]
+pub struct ProposalRewardClaimEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// Voter
+    pub voter: Pubkey,
+    /// Proposal
+    pub proposal: Pubkey,
+    /// Reward mint
+    pub reward_mint: Pubkey,
+    /// Reward claimed
+    pub reward: u64,
+}
+
 /// Event called in [govern::claim_reward].
 #[event]
 pub struct ClaimRewardEvent {
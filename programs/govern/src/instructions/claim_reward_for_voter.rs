@@ -0,0 +1,167 @@
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Accounts for [govern::claim_reward_for_voter].
+///
+/// The [ProposalReward]s to claim are passed in the remaining accounts,
+/// as `[proposal_reward, reward_vault, voter_token_account]` triples.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ClaimRewardForVoter<'info> {
+    /// The [Governor]
+    #[account(mut)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// reward vault
+    #[account(mut)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    /// proposal
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Vote].
+    #[account(mut, has_one = proposal)]
+    pub vote: Box<Account<'info, Vote>>,
+    /// Token account of the [Vote::voter], which receives the reward.
+    #[account(mut)]
+    pub voter_token_account: Account<'info, TokenAccount>,
+    /// Anyone may claim on behalf of the voter, e.g. a vote delegate or a cranker.
+    pub claimer: Signer<'info>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> ClaimRewardForVoter<'info> {
+    pub fn claim_reward_for_voter(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<(Option<ClaimRewardEvent>, Vec<ProposalRewardClaimEvent>)> {
+        invariant!(
+            !self.vote.claimed || !remaining_accounts.is_empty(),
+            "Voter has claimed reward"
+        );
+
+        // the reward of the governor is claimed once, alongside any proposal reward
+        let event = if self.vote.claimed {
+            None
+        } else {
+            let voting_reward = transfer_voting_reward(
+                &self.governor,
+                &mut self.proposal,
+                &mut self.vote,
+                self.reward_vault.to_account_info(),
+                self.voter_token_account.to_account_info(),
+                self.token_program.to_account_info(),
+            )?;
+            Some(ClaimRewardEvent {
+                governor: self.governor.key(),
+                voter: self.vote.voter,
+                proposal: self.proposal.key(),
+                voting_reward,
+            })
+        };
+
+        let voter = self.vote.voter;
+        let reward_events = transfer_proposal_rewards(
+            &self.governor,
+            &self.proposal,
+            &mut self.vote,
+            voter,
+            remaining_accounts,
+            self.token_program.to_account_info(),
+        )?;
+
+        Ok((event, reward_events))
+    }
+}
+
+impl<'info> Validate<'info> for ClaimRewardForVoter<'info> {
+    fn validate(&self) -> Result<()> {
+        let voting_reward = self.proposal.voting_reward;
+        // proposals rewarded only by [ProposalReward]s have no reward vault on the governor
+        if voting_reward.reward_per_proposal > 0 {
+            assert_keys_eq!(voting_reward.reward_vault, self.reward_vault);
+        }
+
+        assert_rewards_claimable(&self.proposal)?;
+
+        invariant!(
+            self.voter_token_account.owner == self.vote.voter,
+            "Must be withdraw to owner wallet"
+        );
+        Ok(())
+    }
+}
+
+/// Accounts for [govern::batch_claim_reward].
+///
+/// The [Vote]s to claim for are passed in the remaining accounts,
+/// as `[vote, voter_token_account]` pairs.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct BatchClaimReward<'info> {
+    /// The [Governor]
+    #[account(mut)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// reward vault
+    #[account(mut)]
+    pub reward_vault: Account<'info, TokenAccount>,
+    /// proposal
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// Anyone may claim on behalf of the voters, e.g. a vote delegate or a cranker.
+    pub claimer: Signer<'info>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> BatchClaimReward<'info> {
+    pub fn batch_claim_reward(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Vec<ClaimRewardEvent>> {
+        let pairs = remaining_accounts.chunks_exact(2);
+        invariant!(
+            pairs.len() > 0 && pairs.remainder().is_empty(),
+            "Remaining accounts must be [vote, voter_token_account] pairs"
+        );
+
+        let mut events = Vec::with_capacity(pairs.len());
+        for accounts in pairs {
+            let mut vote = Account::<Vote>::try_from(&accounts[0])?;
+            let voter_token_account = Account::<TokenAccount>::try_from(&accounts[1])?;
+
+            assert_keys_eq!(vote.proposal, self.proposal, "vote proposal should match");
+            assert_reward_claimable(&self.proposal, &vote)?;
+            invariant!(
+                voter_token_account.owner == vote.voter,
+                "Must be withdraw to owner wallet"
+            );
+
+            let voting_reward = transfer_voting_reward(
+                &self.governor,
+                &mut self.proposal,
+                &mut vote,
+                self.reward_vault.to_account_info(),
+                voter_token_account.to_account_info(),
+                self.token_program.to_account_info(),
+            )?;
+            vote.exit(&crate::ID)?;
+
+            events.push(ClaimRewardEvent {
+                governor: self.governor.key(),
+                voter: vote.voter,
+                proposal: self.proposal.key(),
+                voting_reward,
+            });
+        }
+
+        Ok(events)
+    }
+}
+
+impl<'info> Validate<'info> for BatchClaimReward<'info> {
+    fn validate(&self) -> Result<()> {
+        let voting_reward = self.proposal.voting_reward;
+        assert_keys_eq!(voting_reward.reward_vault, self.reward_vault);
+        Ok(())
+    }
+}
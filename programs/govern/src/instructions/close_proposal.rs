@@ -0,0 +1,212 @@
+use crate::*;
+use anchor_spl::token::{Token, TokenAccount};
+
+/// Accounts for [govern::close_proposal].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    /// The [Proposal] to close.
+    #[account(mut, has_one = proposer, close = payer)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Proposal::payer], who receives the rent of the [Proposal].
+    /// CHECK: checked against [Proposal::rent_payer].
+    #[account(mut, address = proposal.rent_payer())]
+    pub payer: UncheckedAccount<'info>,
+    /// The [Proposal::proposer], who receives the rent of the metadata it paid for.
+    /// CHECK: checked by `has_one` on the proposal.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    /// The [ProposalMeta] of the proposal, if any.
+    #[account(
+        mut,
+        seeds = [
+            b"ProposalMeta".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+    )]
+    pub proposal_meta: Option<Box<Account<'info, ProposalMeta>>>,
+    /// The payer of the [ProposalMeta], if it is not the proposer.
+    /// CHECK: checked in [Validate] against the payer recorded in the [ProposalMeta].
+    #[account(mut)]
+    pub proposal_meta_payer: Option<UncheckedAccount<'info>>,
+    /// The [OptionProposalMeta] of the proposal, if any.
+    #[account(
+        mut,
+        seeds = [
+            b"OptionProposalMeta".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+    )]
+    pub option_proposal_meta: Option<Box<Account<'info, OptionProposalMeta>>>,
+    /// The payer of the [OptionProposalMeta], if it is not the proposer.
+    /// CHECK: checked in [Validate] against the payer recorded in the [OptionProposalMeta].
+    #[account(mut)]
+    pub option_proposal_meta_payer: Option<UncheckedAccount<'info>>,
+    /// The [ProposalParams] of the proposal, closed along with it to the [Proposal::payer].
+    /// Proposals created before [ProposalParams] existed may not have any.
+    #[account(
+        mut,
+        seeds = [
+            b"ProposalParams".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+        close = payer,
+    )]
+    pub proposal_params: Option<Box<Account<'info, ProposalParams>>>,
+    /// The [Governor], if the voting reward of the proposal has not been swept.
+    pub governor: Option<Box<Account<'info, Governor>>>,
+    /// The [VotingReward::reward_vault] of the proposal, if its voting reward has not been swept.
+    #[account(mut)]
+    pub reward_vault: Option<Box<Account<'info, TokenAccount>>>,
+    /// Token account of the [Governor::smart_wallet] receiving the unclaimed voting reward.
+    #[account(mut)]
+    pub treasury: Option<Box<Account<'info, TokenAccount>>>,
+    /// Token program, if the voting reward of the proposal has not been swept.
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+impl<'info> CloseProposal<'info> {
+    pub fn close_proposal(
+        &mut self,
+    ) -> Result<(ProposalCloseEvent, Option<ProposalRewardSweepEvent>)> {
+        // the voting reward which was not claimed goes back to the treasury
+        let sweep_event = if self.has_unswept_reward()? {
+            Some(sweep_unclaimed_reward(
+                unwrap_opt!(self.governor.as_ref()),
+                &mut self.proposal,
+                unwrap_opt!(self.reward_vault.as_ref()).to_account_info(),
+                unwrap_opt!(self.treasury.as_ref()).to_account_info(),
+                unwrap_opt!(self.token_program.as_ref()).to_account_info(),
+            )?)
+        } else {
+            None
+        };
+        close_proposal_metas(
+            self.proposer.to_account_info(),
+            &self.proposal_meta,
+            &self.proposal_meta_payer,
+            &self.option_proposal_meta,
+            &self.option_proposal_meta_payer,
+        )?;
+        Ok((
+            ProposalCloseEvent {
+                governor: self.proposal.governor,
+                proposal: self.proposal.key(),
+                payer: self.payer.key(),
+            },
+            sweep_event,
+        ))
+    }
+
+    fn has_unswept_reward(&self) -> Result<bool> {
+        Ok(unwrap_opt!(self.proposal.unswept_reward(), "Math is overflow") > 0)
+    }
+}
+
+impl<'info> Validate<'info> for CloseProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_opt!(self.proposal.is_closable(now), "Math is overflow"),
+            ProposalNotClosable
+        );
+        if self.has_unswept_reward()? {
+            let governor = unwrap_opt!(self.governor.as_ref(), "governor is required");
+            assert_keys_eq!(governor.key(), self.proposal.governor);
+            validate_sweep_accounts(
+                &self.proposal,
+                governor.smart_wallet,
+                unwrap_opt!(self.reward_vault.as_ref(), "reward vault is required"),
+                unwrap_opt!(self.treasury.as_ref(), "treasury is required"),
+            )?;
+            invariant!(self.token_program.is_some(), "token program is required");
+        }
+        validate_meta_payers(
+            self.proposer.key(),
+            &self.proposal_meta,
+            &self.proposal_meta_payer,
+            &self.option_proposal_meta,
+            &self.option_proposal_meta_payer,
+        )
+    }
+}
+
+/// Closes the metadata of a proposal, returning the rent of each account to its payer.
+pub(crate) fn close_proposal_metas<'info>(
+    proposer: AccountInfo<'info>,
+    proposal_meta: &Option<Box<Account<'info, ProposalMeta>>>,
+    proposal_meta_payer: &Option<UncheckedAccount<'info>>,
+    option_proposal_meta: &Option<Box<Account<'info, OptionProposalMeta>>>,
+    option_proposal_meta_payer: &Option<UncheckedAccount<'info>>,
+) -> Result<()> {
+    if let Some(proposal_meta) = proposal_meta {
+        proposal_meta.close(
+            proposal_meta_payer
+                .as_ref()
+                .map_or(proposer.clone(), |payer| payer.to_account_info()),
+        )?;
+    }
+    if let Some(option_proposal_meta) = option_proposal_meta {
+        option_proposal_meta.close(
+            option_proposal_meta_payer
+                .as_ref()
+                .map_or(proposer, |payer| payer.to_account_info()),
+        )?;
+    }
+    Ok(())
+}
+
+/// Validates the receivers of the rent of the metadata of a proposal: the payer recorded
+/// in each account, or the proposer if none is given or the account was created before
+/// payers were recorded.
+pub(crate) fn validate_meta_payers<'info>(
+    proposer: Pubkey,
+    proposal_meta: &Option<Box<Account<'info, ProposalMeta>>>,
+    proposal_meta_payer: &Option<UncheckedAccount<'info>>,
+    option_proposal_meta: &Option<Box<Account<'info, OptionProposalMeta>>>,
+    option_proposal_meta_payer: &Option<UncheckedAccount<'info>>,
+) -> Result<()> {
+    if let Some(proposal_meta) = proposal_meta {
+        let recorded_payer = proposal_meta
+            .recorded_payer(&proposal_meta.to_account_info().try_borrow_data()?)
+            .unwrap_or(proposer);
+        let receiver = proposal_meta_payer
+            .as_ref()
+            .map_or(proposer, |payer| payer.key());
+        assert_keys_eq!(
+            receiver,
+            recorded_payer,
+            "proposal meta payer should match recorded"
+        );
+    }
+    if let Some(option_proposal_meta) = option_proposal_meta {
+        let recorded_payer = option_proposal_meta
+            .recorded_payer(&option_proposal_meta.to_account_info().try_borrow_data()?)
+            .unwrap_or(proposer);
+        let receiver = option_proposal_meta_payer
+            .as_ref()
+            .map_or(proposer, |payer| payer.key());
+        assert_keys_eq!(
+            receiver,
+            recorded_payer,
+            "option proposal meta payer should match recorded"
+        );
+    }
+    Ok(())
+}
+
+/// Event called in [govern::close_proposal].
+#[event]
+pub struct ProposalCloseEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal being closed.
+    #[index]
+    pub proposal: Pubkey,
+    /// Receiver of the rent of the proposal.
+    pub payer: Pubkey,
+}
@@ -0,0 +1,56 @@
+use crate::*;
+
+/// Accounts for [govern::close_vote].
+#[derive(Accounts)]
+pub struct CloseVote<'info> {
+    /// The [Proposal] voted on. It may already have been closed.
+    /// CHECK: deserialized in [CloseVote::validate] if it still exists.
+    pub proposal: UncheckedAccount<'info>,
+    /// The [Vote] to close.
+    #[account(mut, has_one = proposal, has_one = voter, close = voter)]
+    pub vote: Box<Account<'info, Vote>>,
+    /// The [Vote::voter], who receives the rent of the [Vote]. A [Vote] has no room left
+    /// to record its payer, and is created for its voter, who usually pays for it.
+    /// CHECK: checked by `has_one` on the vote.
+    #[account(mut)]
+    pub voter: UncheckedAccount<'info>,
+}
+
+impl<'info> CloseVote<'info> {
+    pub fn close_vote(&self) -> Result<()> {
+        emit!(VoteCloseEvent {
+            proposal: self.proposal.key(),
+            voter: self.voter.key(),
+        });
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CloseVote<'info> {
+    fn validate(&self) -> Result<()> {
+        // votes on a closed proposal can no longer be counted or rewarded
+        if self.proposal.data_is_empty() {
+            return Ok(());
+        }
+        let proposal: Account<Proposal> = Account::try_from(&self.proposal)?;
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            unwrap_opt!(
+                proposal.is_vote_closable(&self.vote, now),
+                "Math is overflow"
+            ),
+            VoteNotClosable
+        );
+        Ok(())
+    }
+}
+
+/// Event called in [govern::close_vote].
+#[event]
+pub struct VoteCloseEvent {
+    /// The proposal voted on.
+    #[index]
+    pub proposal: Pubkey,
+    /// The voter, who receives the rent of the vote.
+    pub voter: Pubkey,
+}
@@ -37,6 +37,7 @@ impl<'info> CreateGovernor<'info> {
             params.timelock_delay_seconds >= 0,
             "timelock delay must be at least 0 seconds"
         );
+        params.validate()?;
 
         let governor = &mut self.governor;
         governor.base = self.base.key();
@@ -46,7 +47,7 @@ impl<'info> CreateGovernor<'info> {
         governor.locker = locker;
         governor.smart_wallet = self.smart_wallet.key();
 
-        governor.params = params;
+        governor.set_governance_params(&params);
 
         emit!(GovernorCreateEvent {
             governor: governor.key(),
@@ -0,0 +1,124 @@
+use crate::*;
+
+/// Accounts for [govern::create_option_instructions].
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(option: u8, instructions: Vec<ProposalInstruction>)]
+pub struct CreateOptionInstructions<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal].
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal], made stricter by the [ProposalPolicy].
+    #[account(mut, has_one = proposal)]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
+    /// Proposer of the proposal.
+    pub proposer: Signer<'info>,
+    /// The [OptionInstructions].
+    #[account(
+        init,
+        seeds = [
+            b"OptionInstructions".as_ref(),
+            proposal.key().as_ref(),
+            &[option]
+        ],
+        bump,
+        payer = payer,
+        space = 8 + OptionInstructions::space(&instructions)
+    )]
+    pub option_instructions: Box<Account<'info, OptionInstructions>>,
+    /// Payer of the [OptionInstructions].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+    /// The [Governor::proposal_policy], if the governor has one.
+    pub proposal_policy: Option<Box<Account<'info, ProposalPolicy>>>,
+    /// The [ProposalParams::proposal_class], if the proposal has one.
+    pub proposal_class: Option<Box<Account<'info, ProposalClass>>>,
+}
+
+impl<'info> CreateOptionInstructions<'info> {
+    pub fn create_option_instructions(
+        &mut self,
+        option: u8,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<OptionInstructionsCreateEvent> {
+        invariant!(
+            option != 0 && option <= self.proposal.max_option,
+            InvalidVoteSide
+        );
+        if let Some(proposal_class) = &self.proposal_class {
+            proposal_class.check_instructions(&instructions)?;
+        }
+        if let Some(proposal_policy) = &self.proposal_policy {
+            if let Some(policy_params) = proposal_policy.check_instructions(&instructions)? {
+                let params = self
+                    .proposal
+                    .params(&self.proposal_params)
+                    .strictest(&policy_params);
+                self.proposal.set_params(&mut self.proposal_params, &params);
+            }
+        }
+
+        let option_instructions = &mut self.option_instructions;
+        option_instructions.proposal = self.proposal.key();
+        option_instructions.option = option;
+        option_instructions.instructions = instructions.clone();
+
+        let proposal = &mut self.proposal;
+        proposal.option_instruction_sets |= 1 << option;
+        // the proposal must be sealed again before it is activated
+        let proposal_params = &mut self.proposal_params;
+        proposal_params.sealed_at = 0;
+        proposal_params.instructions_hash = [0; 32];
+
+        Ok(OptionInstructionsCreateEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            option,
+            instructions,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for CreateOptionInstructions<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.governor, self.proposal.governor);
+        assert_keys_eq!(self.proposer, self.proposal.proposer);
+        if self.governor.proposal_policy != Pubkey::default() {
+            let proposal_policy =
+                unwrap_opt!(self.proposal_policy.as_ref(), "proposal policy is required");
+            assert_keys_eq!(proposal_policy.key(), self.governor.proposal_policy);
+        }
+        if self.proposal_params.proposal_class != Pubkey::default() {
+            let proposal_class =
+                unwrap_opt!(self.proposal_class.as_ref(), "proposal class is required");
+            assert_keys_eq!(proposal_class.key(), self.proposal_params.proposal_class);
+        }
+        let proposal_type = ProposalType::try_from(self.proposal.proposal_type)?;
+        invariant!(proposal_type.is_multi_option(), NotOptionProposal);
+        // voters must know what each option executes
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Draft,
+            ProposalNotDraft
+        );
+        Ok(())
+    }
+}
+
+/// Event called in [govern::create_option_instructions].
+#[event]
+pub struct OptionInstructionsCreateEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal being voted on.
+    #[index]
+    pub proposal: Pubkey,
+    /// The option.
+    pub option: u8,
+    /// The instructions executed if the option wins.
+    pub instructions: Vec<ProposalInstruction>,
+}
@@ -18,10 +18,11 @@ pub struct CreateOptionProposalMeta<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + OptionProposalMeta::space(&option_descriptions)
+        space = 8 + OptionProposalMeta::space(&option_descriptions) + META_PAYER_SPACE
     )]
     pub option_proposal_meta: Box<Account<'info, OptionProposalMeta>>,
-    /// Payer of the [ProposalMeta].
+    /// Payer of the [OptionProposalMeta], recorded to get the rent back
+    /// when the metadata is closed with the proposal.
     #[account(mut)]
     pub payer: Signer<'info>,
     /// System program.
@@ -40,6 +41,12 @@ impl<'info> CreateOptionProposalMeta<'info> {
         let option_proposal_meta = &mut self.option_proposal_meta;
         option_proposal_meta.proposal = self.proposal.key();
         option_proposal_meta.option_descriptions = option_descriptions.clone();
+        let meta_info = option_proposal_meta.to_account_info();
+        unwrap_opt!(
+            option_proposal_meta
+                .record_payer(&mut meta_info.try_borrow_mut_data()?, self.payer.key()),
+            "cannot record payer"
+        );
 
         Ok(OptionProposalMetaCreateEvent {
             governor: self.proposal.governor,
@@ -52,10 +59,8 @@ impl<'info> CreateOptionProposalMeta<'info> {
 impl<'info> Validate<'info> for CreateOptionProposalMeta<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.proposer, self.proposal.proposer);
-        invariant!(
-            self.proposal.proposal_type == u8::from(ProposalType::Option),
-            NotOptionProposal
-        );
+        let proposal_type = ProposalType::try_from(self.proposal.proposal_type)?;
+        invariant!(proposal_type.is_multi_option(), NotOptionProposal);
         Ok(())
     }
 }
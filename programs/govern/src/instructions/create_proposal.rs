@@ -1,4 +1,5 @@
 use crate::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
 
 /// Accounts for [govern::create_proposal].
 #[event_cpi]
@@ -21,22 +22,62 @@ pub struct CreateProposal<'info> {
         space = 8 + Proposal::space(max_option + 1, instructions), // yes/no proposal only has 2 options, plus 1 for abstain vote
     )]
     pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal].
+    #[account(
+        init,
+        seeds = [
+            b"ProposalParams".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + ProposalParams::LEN
+    )]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
     /// smart wallet of governor
     pub smart_wallet: Account<'info, SmartWallet>,
     /// Proposer of the proposal.
-    /// One of the owners. Checked in the handler via [SmartWallet::owner_index].
+    /// One of the owners, checked in the handler via [SmartWallet::owner_index],
+    /// unless the [Governor::locker] has checked the voting power of the proposer.
     pub proposer: Signer<'info>,
     /// Payer of the proposal.
     #[account(mut)]
     pub payer: Signer<'info>,
     /// System program.
     pub system_program: Program<'info, System>,
+    /// The [Governor::locker], if the proposal is created through the locker.
+    pub locker: Option<Signer<'info>>,
+    /// The [ProposalDeposit::deposit_mint], if a deposit is required.
+    pub deposit_mint: Option<Box<Account<'info, Mint>>>,
+    /// Escrow of the [ProposalDeposit], if a deposit is required.
+    #[account(
+        init,
+        seeds = [
+            b"ProposalDeposit".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        token::mint = deposit_mint,
+        token::authority = governor,
+    )]
+    pub deposit_vault: Option<Box<Account<'info, TokenAccount>>>,
+    /// Token account of the proposer paying the deposit, if a deposit is required.
+    #[account(mut)]
+    pub proposer_token_account: Option<Box<Account<'info, TokenAccount>>>,
+    /// Token program, if a deposit is required.
+    pub token_program: Option<Program<'info, Token>>,
+    /// The [Governor::proposal_policy], if the governor has one.
+    pub proposal_policy: Option<Box<Account<'info, ProposalPolicy>>>,
+    /// The [ProposalClass] of the proposal, if it does not use [Governor::params].
+    pub proposal_class: Option<Box<Account<'info, ProposalClass>>>,
 }
 
 impl<'info> CreateProposal<'info> {
     pub fn create_proposal(
         &mut self,
         bump: u8,
+        params_bump: u8,
         proposal_type: u8,
         max_option: u8,
         instructions: Vec<ProposalInstruction>,
@@ -47,11 +88,26 @@ impl<'info> CreateProposal<'info> {
             ProposalType::YesNo => {
                 invariant!(max_option == 2, InvalidMaxOption);
             }
-            ProposalType::Option => {
+            ProposalType::Option | ProposalType::Approval | ProposalType::RankedChoice => {
                 invariant!(
                     max_option >= 2 && max_option <= MAX_OPTION,
                     InvalidMaxOption
                 );
+                // each option carries its own instructions
+                invariant!(instructions.is_empty(), InvalidOptionInstructions);
+            }
+        }
+
+        let mut params = match &self.proposal_class {
+            Some(proposal_class) => {
+                proposal_class.check_instructions(&instructions)?;
+                proposal_class.params
+            }
+            None => self.governor.governance_params(),
+        };
+        if let Some(proposal_policy) = &self.proposal_policy {
+            if let Some(policy_params) = proposal_policy.check_instructions(&instructions)? {
+                params = params.strictest(&policy_params);
             }
         }
 
@@ -64,7 +120,15 @@ impl<'info> CreateProposal<'info> {
 
         proposal.proposer = self.proposer.key();
 
-        proposal.quorum_votes = governor.params.quorum_votes;
+        let proposal_params = &mut self.proposal_params;
+        proposal_params.proposal = proposal.key();
+        proposal_params.bump = params_bump;
+        proposal.set_params(proposal_params, &params);
+        proposal_params.proposal_class = self
+            .proposal_class
+            .as_ref()
+            .map(|proposal_class| proposal_class.key())
+            .unwrap_or_default();
         proposal.created_at = Clock::get()?.unix_timestamp;
         proposal.canceled_at = 0;
         proposal.activated_at = 0;
@@ -74,13 +138,41 @@ impl<'info> CreateProposal<'info> {
         proposal.queued_transaction = Pubkey::default();
 
         proposal.voting_reward = governor.voting_reward;
+        proposal.reward_policy = governor.reward_policy;
+        proposal.reward_claim_period = governor.reward_claim_period;
+        proposal.vote_lock_period = unwrap_int!(u32::try_from(governor.vote_lock_period).ok());
+        proposal.votes_final = governor.votes_final;
+        proposal.late_quorum_extension =
+            unwrap_int!(u32::try_from(governor.late_quorum_extension).ok());
+        proposal.remaining_voting_extension =
+            unwrap_int!(u32::try_from(governor.max_voting_extension).ok());
+        proposal.payer = self.payer.key();
 
         proposal.instructions = instructions.clone();
+        // instructions too large for this transaction are appended before sealing again
+        proposal_params.seal_instructions(proposal, proposal.created_at)?;
 
         proposal.proposal_type = proposal_type;
         proposal.max_option = max_option;
         proposal.option_votes = vec![0; (max_option + 1) as usize]; // plus 1 for abstain vote
 
+        let proposal_deposit = governor.proposal_deposit;
+        if proposal_deposit.deposit_amount > 0 {
+            token::transfer(
+                CpiContext::new(
+                    unwrap_opt!(self.token_program.as_ref()).to_account_info(),
+                    token::Transfer {
+                        from: unwrap_opt!(self.proposer_token_account.as_ref()).to_account_info(),
+                        to: unwrap_opt!(self.deposit_vault.as_ref()).to_account_info(),
+                        authority: self.proposer.to_account_info(),
+                    },
+                ),
+                proposal_deposit.deposit_amount,
+            )?;
+            proposal.deposit_amount = proposal_deposit.deposit_amount;
+            proposal.deposit_refund_threshold_bps = proposal_deposit.refund_threshold_bps;
+        }
+
         governor.proposal_count += 1;
 
         Ok(ProposalCreateEvent {
@@ -90,6 +182,7 @@ impl<'info> CreateProposal<'info> {
             proposal_type,
             max_option,
             index: proposal.index,
+            proposal_class: proposal_params.proposal_class,
             instructions,
         })
     }
@@ -97,8 +190,36 @@ impl<'info> CreateProposal<'info> {
 
 impl<'info> Validate<'info> for CreateProposal<'info> {
     fn validate(&self) -> Result<()> {
-        // validate proposer is one of owners of smart-wallet
-        self.smart_wallet.owner_index(self.proposer.key())?;
+        if let Some(locker) = &self.locker {
+            // the locker has checked the voting power of the proposer
+            assert_keys_eq!(locker.key(), self.governor.locker);
+        } else {
+            // validate proposer is one of owners of smart-wallet
+            self.smart_wallet.owner_index(self.proposer.key())?;
+        }
+
+        let proposal_deposit = self.governor.proposal_deposit;
+        if proposal_deposit.deposit_amount > 0 {
+            let deposit_mint = unwrap_opt!(self.deposit_mint.as_ref(), "deposit mint is required");
+            assert_keys_eq!(deposit_mint.key(), proposal_deposit.deposit_mint);
+            invariant!(self.deposit_vault.is_some(), "deposit vault is required");
+            invariant!(self.token_program.is_some(), "token program is required");
+            let proposer_token_account = unwrap_opt!(
+                self.proposer_token_account.as_ref(),
+                "proposer token account is required"
+            );
+            assert_keys_eq!(proposer_token_account.owner, self.proposer);
+        }
+
+        if self.governor.proposal_policy != Pubkey::default() {
+            let proposal_policy =
+                unwrap_opt!(self.proposal_policy.as_ref(), "proposal policy is required");
+            assert_keys_eq!(proposal_policy.key(), self.governor.proposal_policy);
+        }
+
+        if let Some(proposal_class) = &self.proposal_class {
+            assert_keys_eq!(proposal_class.governor, self.governor);
+        }
         Ok(())
     }
 }
@@ -120,6 +241,8 @@ pub struct ProposalCreateEvent {
     pub max_option: u8,
     /// The index of the [Proposal].
     pub index: u64,
+    /// The [ProposalClass], or [Pubkey::default] if the proposal uses [Governor::params].
+    pub proposal_class: Pubkey,
     /// Instructions in the proposal.
     pub instructions: Vec<ProposalInstruction>,
 }
@@ -0,0 +1,148 @@
+use crate::*;
+
+/// Accounts for [govern::create_proposal_class].
+#[derive(Accounts)]
+#[instruction(name: String, params: GovernanceParameters, filters: Vec<InstructionFilter>)]
+pub struct CreateProposalClass<'info> {
+    /// The [Governor]
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [ProposalClass].
+    #[account(
+        init,
+        seeds = [
+            b"ProposalClass".as_ref(),
+            governor.key().as_ref(),
+            name.as_bytes()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + ProposalClass::space(&filters)
+    )]
+    pub proposal_class: Box<Account<'info, ProposalClass>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+    /// Payer of the [ProposalClass], e.g. the owner executing the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposalClass<'info> {
+    pub fn create_proposal_class(
+        &mut self,
+        bump: u8,
+        name: String,
+        params: GovernanceParameters,
+        filters: Vec<InstructionFilter>,
+    ) -> Result<()> {
+        invariant!(
+            !name.is_empty() && name.len() <= MAX_PROPOSAL_CLASS_NAME_LEN,
+            InvalidProposalClassName
+        );
+        invariant!(
+            params.timelock_delay_seconds >= 0,
+            "timelock delay must be at least 0 seconds"
+        );
+        params.validate()?;
+
+        let proposal_class = &mut self.proposal_class;
+        proposal_class.governor = self.governor.key();
+        proposal_class.bump = bump;
+        proposal_class.name = name.clone();
+        proposal_class.params = params;
+        proposal_class.filters = filters.clone();
+
+        emit!(ProposalClassSetEvent {
+            governor: self.governor.key(),
+            proposal_class: proposal_class.key(),
+            name,
+            params,
+            filters,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateProposalClass<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Accounts for [govern::set_proposal_class_params].
+#[derive(Accounts)]
+#[instruction(params: GovernanceParameters, filters: Vec<InstructionFilter>)]
+pub struct SetProposalClassParams<'info> {
+    /// The [Governor]
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [ProposalClass].
+    #[account(
+        mut,
+        has_one = governor,
+        realloc = 8 + ProposalClass::space(&filters),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub proposal_class: Box<Account<'info, ProposalClass>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+    /// Payer of a larger [ProposalClass], e.g. the owner executing the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetProposalClassParams<'info> {
+    pub fn set_proposal_class_params(
+        &mut self,
+        params: GovernanceParameters,
+        filters: Vec<InstructionFilter>,
+    ) -> Result<()> {
+        invariant!(
+            params.timelock_delay_seconds >= 0,
+            "timelock delay must be at least 0 seconds"
+        );
+        params.validate()?;
+
+        let proposal_class = &mut self.proposal_class;
+        proposal_class.params = params;
+        proposal_class.filters = filters.clone();
+
+        emit!(ProposalClassSetEvent {
+            governor: self.governor.key(),
+            proposal_class: proposal_class.key(),
+            name: proposal_class.name.clone(),
+            params,
+            filters,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetProposalClassParams<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Event called in [govern::create_proposal_class] and [govern::set_proposal_class_params].
+#[event]
+pub struct ProposalClassSetEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The [ProposalClass].
+    pub proposal_class: Pubkey,
+    /// Name of the class.
+    pub name: String,
+    /// Parameters of the proposals of the class.
+    pub params: GovernanceParameters,
+    /// The instructions proposals of the class may call.
+    pub filters: Vec<InstructionFilter>,
+}
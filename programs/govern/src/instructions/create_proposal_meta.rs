@@ -18,12 +18,11 @@ pub struct CreateProposalMeta<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + std::mem::size_of::<Pubkey>()
-            + 4 + title.as_bytes().len()
-            + 4 + description_link.as_bytes().len()
+        space = 8 + ProposalMeta::space(&title, &description_link) + META_PAYER_SPACE
     )]
     pub proposal_meta: Box<Account<'info, ProposalMeta>>,
-    /// Payer of the [ProposalMeta].
+    /// Payer of the [ProposalMeta], recorded to get the rent back
+    /// when the metadata is closed with the proposal.
     #[account(mut)]
     pub payer: Signer<'info>,
     /// System program.
@@ -40,6 +39,11 @@ impl<'info> CreateProposalMeta<'info> {
         proposal_meta.proposal = self.proposal.key();
         proposal_meta.title = title.clone();
         proposal_meta.description_link = description_link.clone();
+        let meta_info = proposal_meta.to_account_info();
+        unwrap_opt!(
+            proposal_meta.record_payer(&mut meta_info.try_borrow_mut_data()?, self.payer.key()),
+            "cannot record payer"
+        );
 
         Ok(ProposalMetaCreateEvent {
             governor: self.proposal.governor,
@@ -0,0 +1,79 @@
+use crate::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Accounts for [govern::create_proposal_params].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposalParams<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal], created before [ProposalParams] existed.
+    #[account(has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal].
+    #[account(
+        init,
+        seeds = [
+            b"ProposalParams".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + ProposalParams::LEN
+    )]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
+    /// Payer of the [ProposalParams]. Their rent goes to the [Proposal::payer]
+    /// when the proposal is closed.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposalParams<'info> {
+    pub fn create_proposal_params(&mut self, bump: u8) -> Result<ProposalParamsCreateEvent> {
+        let params = &self.governor.params;
+        let proposal_params = &mut self.proposal_params;
+        proposal_params.proposal = self.proposal.key();
+        proposal_params.bump = bump;
+        // these proposals were decided with the current parameters of the governor
+        proposal_params.voting_delay = params.voting_delay;
+        proposal_params.voting_period = params.voting_period;
+        proposal_params.timelock_delay_seconds = params.timelock_delay_seconds;
+        // and their instructions could not change once created
+        proposal_params.instructions_hash =
+            hash(&self.proposal.instructions.try_to_vec()?).to_bytes();
+        proposal_params.sealed_at = self.proposal.created_at;
+
+        Ok(ProposalParamsCreateEvent {
+            governor: self.governor.key(),
+            proposal: self.proposal.key(),
+            voting_delay: proposal_params.voting_delay,
+            voting_period: proposal_params.voting_period,
+            timelock_delay_seconds: proposal_params.timelock_delay_seconds,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for CreateProposalParams<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Event called in [govern::create_proposal_params].
+#[event]
+pub struct ProposalParamsCreateEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// The [ProposalParams::voting_delay].
+    pub voting_delay: u64,
+    /// The [ProposalParams::voting_period].
+    pub voting_period: u64,
+    /// The [ProposalParams::timelock_delay_seconds].
+    pub timelock_delay_seconds: i64,
+}
@@ -0,0 +1,182 @@
+use crate::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount};
+
+/// Accounts for [govern::create_proposal_reward].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct CreateProposalReward<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal] co-sponsored by the reward.
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalReward].
+    #[account(
+        init,
+        seeds = [
+            b"ProposalReward".as_ref(),
+            proposal.key().as_ref(),
+            reward_mint.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + ProposalReward::LEN
+    )]
+    pub proposal_reward: Box<Account<'info, ProposalReward>>,
+    /// Reward mint
+    pub reward_mint: Box<Account<'info, Mint>>,
+    /// Vault of the reward, owned by the [Governor].
+    #[account(
+        init,
+        seeds = [
+            b"ProposalRewardVault".as_ref(),
+            proposal_reward.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        token::mint = reward_mint,
+        token::authority = governor,
+    )]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    /// Payer of the [ProposalReward], its [ProposalReward::sponsor].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> CreateProposalReward<'info> {
+    pub fn create_proposal_reward(&mut self, bump: u8) -> Result<ProposalRewardCreateEvent> {
+        let proposal = &mut self.proposal;
+        let proposal_reward = &mut self.proposal_reward;
+        proposal_reward.proposal = proposal.key();
+        proposal_reward.index = proposal.reward_count;
+        proposal_reward.bump = bump;
+        proposal_reward.reward_mint = self.reward_mint.key();
+        proposal_reward.reward_vault = self.reward_vault.key();
+        proposal_reward.sponsor = self.payer.key();
+
+        proposal.reward_count += 1;
+
+        Ok(ProposalRewardCreateEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            reward_mint: proposal_reward.reward_mint,
+            index: proposal_reward.index,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for CreateProposalReward<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_reward_fundable(&self.proposal)?;
+        invariant!(
+            self.proposal.reward_count < MAX_PROPOSAL_REWARDS,
+            TooManyProposalRewards
+        );
+        Ok(())
+    }
+}
+
+/// Accounts for [govern::fund_proposal_reward].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct FundProposalReward<'info> {
+    /// The [Proposal] co-sponsored by the reward.
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalReward].
+    #[account(mut, has_one = proposal, has_one = reward_vault)]
+    pub proposal_reward: Box<Account<'info, ProposalReward>>,
+    /// Vault of the reward.
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    /// Anyone may top up the reward.
+    pub funder: Signer<'info>,
+    /// Token account of the funder.
+    #[account(mut)]
+    pub funder_token_account: Box<Account<'info, TokenAccount>>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> FundProposalReward<'info> {
+    pub fn fund_proposal_reward(&mut self, amount: u64) -> Result<ProposalRewardFundEvent> {
+        token::transfer(
+            CpiContext::new(
+                self.token_program.to_account_info(),
+                token::Transfer {
+                    from: self.funder_token_account.to_account_info(),
+                    to: self.reward_vault.to_account_info(),
+                    authority: self.funder.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        let proposal_reward = &mut self.proposal_reward;
+        proposal_reward.total_reward =
+            unwrap_int!(proposal_reward.total_reward.checked_add(amount));
+
+        Ok(ProposalRewardFundEvent {
+            governor: self.proposal.governor,
+            proposal: self.proposal.key(),
+            funder: self.funder.key(),
+            reward_mint: proposal_reward.reward_mint,
+            amount,
+            total_reward: proposal_reward.total_reward,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for FundProposalReward<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_reward_fundable(&self.proposal)?;
+        Ok(())
+    }
+}
+
+/// Checks that rewards may still be added to the [Proposal], i.e. voting has not ended.
+fn assert_reward_fundable(proposal: &Proposal) -> Result<()> {
+    let proposal_state = proposal.get_state()?;
+    invariant!(
+        proposal_state == ProposalState::Draft || proposal_state == ProposalState::Active,
+        "proposal must be a draft or active"
+    );
+    Ok(())
+}
+
+/// Event called in [govern::create_proposal_reward].
+#[event]
+pub struct ProposalRewardCreateEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// Reward mint
+    pub reward_mint: Pubkey,
+    /// Index of the reward on the proposal
+    pub index: u8,
+}
+
+/// Event called in [govern::fund_proposal_reward].
+#[event]
+pub struct ProposalRewardFundEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// The funder.
+    pub funder: Pubkey,
+    /// Reward mint
+    pub reward_mint: Pubkey,
+    /// Amount added
+    pub amount: u64,
+    /// Total reward after the top-up
+    pub total_reward: u64,
+}
@@ -1,27 +1,67 @@
 //! Instruction processors.
 
 pub mod activate_proposal;
+pub mod append_proposal_instructions;
+pub mod append_queued_instructions;
 pub mod cancel_proposal;
 pub mod claim_reward;
+pub mod claim_reward_for_voter;
+pub mod close_proposal;
+pub mod close_vote;
 pub mod create_governor;
+pub mod create_option_instructions;
 pub mod create_option_proposal_meta;
 pub mod create_proposal;
+pub mod create_proposal_class;
 pub mod create_proposal_meta;
+pub mod create_proposal_params;
+pub mod create_proposal_reward;
 pub mod new_vote;
 pub mod queue_proposal;
+pub mod refund_proposal_reward;
 pub mod set_governance_params;
+pub mod set_late_quorum_extension;
+pub mod set_proposal_deposit;
+pub mod set_proposal_policy;
 pub mod set_vote;
+pub mod set_vote_lock;
 pub mod set_voting_reward;
+pub mod set_voting_reward_params;
+pub mod settle_proposal_deposit;
+pub mod sweep_voting_reward;
+pub mod sync_proposal;
+pub mod tally_proposal;
+pub mod veto_proposal;
 
 pub use activate_proposal::*;
+pub use append_proposal_instructions::*;
+pub use append_queued_instructions::*;
 pub use cancel_proposal::*;
 pub use claim_reward::*;
+pub use claim_reward_for_voter::*;
+pub use close_proposal::*;
+pub use close_vote::*;
 pub use create_governor::*;
+pub use create_option_instructions::*;
 pub use create_option_proposal_meta::*;
 pub use create_proposal::*;
+pub use create_proposal_class::*;
 pub use create_proposal_meta::*;
+pub use create_proposal_params::*;
+pub use create_proposal_reward::*;
 pub use new_vote::*;
 pub use queue_proposal::*;
+pub use refund_proposal_reward::*;
 pub use set_governance_params::*;
+pub use set_late_quorum_extension::*;
+pub use set_proposal_deposit::*;
+pub use set_proposal_policy::*;
 pub use set_vote::*;
+pub use set_vote_lock::*;
 pub use set_voting_reward::*;
+pub use set_voting_reward_params::*;
+pub use settle_proposal_deposit::*;
+pub use sweep_voting_reward::*;
+pub use sync_proposal::*;
+pub use tally_proposal::*;
+pub use veto_proposal::*;
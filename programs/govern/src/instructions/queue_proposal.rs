@@ -10,6 +10,9 @@ pub struct QueueProposal<'info> {
     /// The Proposal to queue.
     #[account(mut)]
     pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalParams] of the [Proposal].
+    #[account(has_one = proposal)]
+    pub proposal_params: Box<Account<'info, ProposalParams>>,
     /// The transaction key of the proposal.
     /// This account is passed to and validated by the Smart Wallet program to be initialized.
     #[account(mut, constraint = transaction.to_account_info().data_is_empty())]
@@ -20,6 +23,13 @@ pub struct QueueProposal<'info> {
     /// Payer of the queued transaction.
     #[account(mut)]
     pub payer: Signer<'info>,
+    /// The [OptionInstructions] of the winning option of a multi-option proposal,
+    /// if the option has a bundle.
+    pub option_instructions: Option<Box<Account<'info, OptionInstructions>>>,
+    /// The [smart_wallet::TransactionBuffer] filled by [govern::append_queued_instructions],
+    /// if the instructions do not fit into a single transaction.
+    #[account(mut)]
+    pub transaction_buffer: Option<Box<Account<'info, smart_wallet::TransactionBuffer>>>,
     /// The Smart Wallet program.
     pub smart_wallet_program: Program<'info, smart_wallet::program::SmartWallet>,
     /// The System program.
@@ -28,38 +38,70 @@ pub struct QueueProposal<'info> {
 
 impl<'info> QueueProposal<'info> {
     /// Queues a Transaction into the Smart Wallet.
-    pub fn queue_transaction(&mut self) -> Result<ProposalQueueEvent> {
+    /// Instructions which do not fit into a single transaction are first buffered with
+    /// [govern::append_queued_instructions].
+    pub fn queue_transaction(
+        &mut self,
+        lookup_tables: &[AccountInfo],
+    ) -> Result<ProposalQueueEvent> {
+        let instructions = queued_instructions(
+            &self.proposal,
+            &self.proposal_params,
+            self.option_instructions.as_deref().map(|bundle| &**bundle),
+            lookup_tables,
+        )?;
+
         let seeds = governor_seeds!(self.governor);
         let signer_seeds = &[&seeds[..]];
-        let cpi_ctx = CpiContext::new_with_signer(
-            self.smart_wallet_program.to_account_info(),
-            smart_wallet::cpi::accounts::CreateTransaction {
-                smart_wallet: self.smart_wallet.to_account_info(),
-                transaction: self.transaction.to_account_info(),
-                proposer: self.governor.to_account_info(),
-                payer: self.payer.to_account_info(),
-                system_program: self.system_program.to_account_info(),
-            },
-            signer_seeds,
-        );
 
         // no delay
-        if self.governor.params.timelock_delay_seconds == 0 {
-            smart_wallet::cpi::create_transaction(
-                cpi_ctx,
-                0,
-                self.proposal.to_smart_wallet_instructions(),
-            )?;
+        let eta = if self.proposal_params.timelock_delay_seconds == 0 {
+            smart_wallet::NO_ETA
         } else {
             // delay; calculate ETA
-            smart_wallet::cpi::create_transaction_with_timelock(
-                cpi_ctx,
+            unwrap_int!(Clock::get()?
+                .unix_timestamp
+                .checked_add(self.proposal_params.timelock_delay_seconds))
+        };
+
+        if let Some(transaction_buffer) = &self.transaction_buffer {
+            invariant!(
+                transaction_buffer.instructions == instructions,
+                "transaction buffer must hold the instructions of the proposal"
+            );
+            smart_wallet::cpi::create_transaction_from_buffer(
+                CpiContext::new_with_signer(
+                    self.smart_wallet_program.to_account_info(),
+                    smart_wallet::cpi::accounts::CreateTransactionFromBuffer {
+                        smart_wallet: self.smart_wallet.to_account_info(),
+                        transaction_buffer: transaction_buffer.to_account_info(),
+                        transaction: self.transaction.to_account_info(),
+                        proposer: self.governor.to_account_info(),
+                        payer: self.payer.to_account_info(),
+                        system_program: self.system_program.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
                 0,
-                self.proposal.to_smart_wallet_instructions(),
-                unwrap_int!(Clock::get()?
-                    .unix_timestamp
-                    .checked_add(self.governor.params.timelock_delay_seconds)),
+                eta,
             )?;
+        } else {
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.smart_wallet_program.to_account_info(),
+                smart_wallet::cpi::accounts::CreateTransaction {
+                    smart_wallet: self.smart_wallet.to_account_info(),
+                    transaction: self.transaction.to_account_info(),
+                    proposer: self.governor.to_account_info(),
+                    payer: self.payer.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                },
+                signer_seeds,
+            );
+            if eta == smart_wallet::NO_ETA {
+                smart_wallet::cpi::create_transaction(cpi_ctx, 0, instructions)?;
+            } else {
+                smart_wallet::cpi::create_transaction_with_timelock(cpi_ctx, 0, instructions, eta)?;
+            }
         }
 
         let proposal = &mut self.proposal;
@@ -74,10 +116,73 @@ impl<'info> QueueProposal<'info> {
     }
 }
 
+/// The instructions queued for the proposal: [Proposal::to_smart_wallet_instructions],
+/// followed by its [ProposalParams::lookup_instructions] resolved from the `lookup_tables`.
+pub(crate) fn queued_instructions(
+    proposal: &Proposal,
+    proposal_params: &ProposalParams,
+    option_instructions: Option<&OptionInstructions>,
+    lookup_tables: &[AccountInfo],
+) -> Result<Vec<smart_wallet::TXInstruction>> {
+    let mut instructions = proposal.to_smart_wallet_instructions(option_instructions);
+    instructions.extend(proposal_params.resolve_lookup_instructions(lookup_tables)?);
+    Ok(instructions)
+}
+
+/// Number of leading instructions which fit into [MAX_INSTRUCTIONS_CHUNK_SPACE],
+/// at least one.
+pub(crate) fn instructions_chunk_len(instructions: &[smart_wallet::TXInstruction]) -> usize {
+    let mut space = 0;
+    let chunk_len = instructions
+        .iter()
+        .take_while(|ix| {
+            space += ix.space();
+            space <= MAX_INSTRUCTIONS_CHUNK_SPACE
+        })
+        .count();
+    chunk_len.max(1).min(instructions.len())
+}
+
+/// Checks that the [OptionInstructions] of the winning option of a multi-option
+/// [Proposal] are provided if the option has a bundle.
+pub(crate) fn validate_winning_option_instructions(
+    proposal: &Account<Proposal>,
+    option_instructions: Option<&OptionInstructions>,
+) -> Result<()> {
+    let proposal_type = ProposalType::try_from(proposal.proposal_type)?;
+    if proposal_type.is_multi_option() && proposal.has_option_instructions(proposal.winning_option)
+    {
+        let option_instructions = unwrap_opt!(
+            option_instructions,
+            "instructions of the winning option must be provided"
+        );
+        assert_keys_eq!(option_instructions.proposal, proposal.key());
+        invariant!(
+            option_instructions.option == proposal.winning_option,
+            InvalidOptionInstructions
+        );
+    }
+    Ok(())
+}
+
 impl<'info> Validate<'info> for QueueProposal<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.governor, self.proposal.governor);
         assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        invariant!(self.proposal_params.is_sealed(), ProposalNotSealed);
+        let proposal_type = ProposalType::try_from(self.proposal.proposal_type)?;
+        invariant!(
+            !proposal_type.is_multi_option() || self.proposal.tallied_at > 0,
+            ProposalNotTallied
+        );
+        validate_winning_option_instructions(
+            &self.proposal,
+            self.option_instructions.as_deref().map(|bundle| &**bundle),
+        )?;
+        if let Some(transaction_buffer) = &self.transaction_buffer {
+            assert_keys_eq!(transaction_buffer.proposer, self.governor);
+            assert_keys_eq!(transaction_buffer.base, self.proposal);
+        }
         let now = Clock::get()?.unix_timestamp;
         let proposal_state = unwrap_opt!(self.proposal.state(now), "invalid state");
         if proposal_state != ProposalState::Succeeded {
@@ -92,10 +197,20 @@ impl<'info> Validate<'info> for QueueProposal<'info> {
                     self.proposal.option_votes[FOR_VOTE_INDEX],
                     self.proposal.option_votes[AGAINST_VOTE_INDEX],
                 );
+            } else {
+                msg!(
+                    "tallied at: {}, winning option: {}",
+                    self.proposal.tallied_at,
+                    self.proposal.winning_option,
+                );
             }
+            msg!(
+                "approval threshold bps: {}",
+                self.proposal.approval_threshold_bps,
+            );
             msg!(
                 "quorum req: {}, abstain votes: {}",
-                self.governor.params.quorum_votes,
+                self.proposal.quorum_votes,
                 self.proposal.option_votes[ABSTAIN_VOTE_INDEX],
             );
             invariant!(
@@ -0,0 +1,113 @@
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+/// Accounts for [govern::refund_proposal_reward].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct RefundProposalReward<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal] co-sponsored by the reward.
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [ProposalReward] to refund.
+    #[account(mut, has_one = proposal, has_one = reward_vault, has_one = sponsor, close = sponsor)]
+    pub proposal_reward: Box<Account<'info, ProposalReward>>,
+    /// Vault of the reward.
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    /// The [ProposalReward::sponsor], who receives the rent of the reward and of its vault.
+    /// CHECK: checked by `has_one` on the proposal reward.
+    #[account(mut)]
+    pub sponsor: UncheckedAccount<'info>,
+    /// Token account of the sponsor, which receives the unclaimed reward.
+    #[account(mut)]
+    pub sponsor_token_account: Box<Account<'info, TokenAccount>>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> RefundProposalReward<'info> {
+    /// Refunds the reward left in the vault to the sponsor, then closes the vault
+    /// and the [ProposalReward].
+    pub fn refund_proposal_reward(&mut self) -> Result<ProposalRewardRefundEvent> {
+        let refunded_reward = self.reward_vault.amount;
+        let seeds = governor_seeds!(self.governor);
+        let signer_seeds = &[&seeds[..]];
+        if refunded_reward != 0 {
+            token::transfer(
+                CpiContext::new_with_signer(
+                    self.token_program.to_account_info(),
+                    token::Transfer {
+                        from: self.reward_vault.to_account_info(),
+                        to: self.sponsor_token_account.to_account_info(),
+                        authority: self.governor.to_account_info(),
+                    },
+                    signer_seeds,
+                ),
+                refunded_reward,
+            )?;
+        }
+        token::close_account(CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            token::CloseAccount {
+                account: self.reward_vault.to_account_info(),
+                destination: self.sponsor.to_account_info(),
+                authority: self.governor.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+
+        let proposal = &mut self.proposal;
+        proposal.refunded_reward_count = unwrap_int!(proposal.refunded_reward_count.checked_add(1));
+
+        Ok(ProposalRewardRefundEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            sponsor: self.sponsor.key(),
+            reward_mint: self.proposal_reward.reward_mint,
+            total_claimed_reward: self.proposal_reward.total_claimed_reward,
+            refunded_reward,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for RefundProposalReward<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.sponsor_token_account.owner, self.sponsor);
+        assert_keys_eq!(
+            self.sponsor_token_account.mint,
+            self.proposal_reward.reward_mint
+        );
+
+        let now = Clock::get()?.unix_timestamp;
+        let proposal_state = unwrap_opt!(self.proposal.state(now), "invalid state");
+        invariant!(
+            unwrap_opt!(
+                self.proposal.is_reward_window_over(&proposal_state, now),
+                "Math is overflow"
+            ),
+            "claim window must be over"
+        );
+        Ok(())
+    }
+}
+
+/// Event called in [govern::refund_proposal_reward].
+#[event]
+pub struct ProposalRewardRefundEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// The sponsor refunded.
+    pub sponsor: Pubkey,
+    /// Reward mint
+    pub reward_mint: Pubkey,
+    /// Reward claimed by voters
+    pub total_claimed_reward: u64,
+    /// Unclaimed reward refunded to the sponsor
+    pub refunded_reward: u64,
+}
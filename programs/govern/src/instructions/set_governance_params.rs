@@ -1,5 +1,5 @@
 use crate::*;
-/// Accounts for [govern::set_governance_params] and [govern::set_voter].
+/// Accounts for [govern::set_governance_params], [govern::set_locker] and [govern::set_veto_authority].
 #[derive(Accounts)]
 pub struct SetGovernanceParams<'info> {
     /// The [Governor]
@@ -11,8 +11,9 @@ pub struct SetGovernanceParams<'info> {
 
 impl<'info> SetGovernanceParams<'info> {
     pub fn set_governance_params(&mut self, params: GovernanceParameters) -> Result<()> {
-        let prev_params = self.governor.params;
-        self.governor.params = params;
+        params.validate()?;
+        let prev_params = self.governor.governance_params();
+        self.governor.set_governance_params(&params);
 
         emit!(GovernorSetParamsEvent {
             governor: self.governor.key(),
@@ -35,6 +36,19 @@ impl<'info> SetGovernanceParams<'info> {
 
         Ok(())
     }
+
+    pub fn set_veto_authority(&mut self, veto_authority: Pubkey) -> Result<()> {
+        let prev_veto_authority = self.governor.veto_authority;
+        self.governor.veto_authority = veto_authority;
+
+        emit!(GovernorSetVetoAuthorityEvent {
+            governor: self.governor.key(),
+            prev_veto_authority,
+            new_veto_authority: veto_authority,
+        });
+
+        Ok(())
+    }
 }
 
 impl<'info> Validate<'info> for SetGovernanceParams<'info> {
@@ -71,3 +85,15 @@ pub struct GovernorSetVoterEvent {
     /// New [Governor::locker].
     pub new_locker: Pubkey,
 }
+
+/// Event called in [govern::set_veto_authority].
+#[event]
+pub struct GovernorSetVetoAuthorityEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// Previous [Governor::veto_authority].
+    pub prev_veto_authority: Pubkey,
+    /// New [Governor::veto_authority].
+    pub new_veto_authority: Pubkey,
+}
@@ -0,0 +1,54 @@
+use crate::*;
+
+/// Accounts for [govern::set_late_quorum_extension].
+#[derive(Accounts)]
+pub struct SetLateQuorumExtension<'info> {
+    /// The [Governor]
+    #[account(mut, has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetLateQuorumExtension<'info> {
+    pub fn set_late_quorum_extension(
+        &mut self,
+        late_quorum_extension: i64,
+        max_voting_extension: i64,
+    ) -> Result<()> {
+        // proposals snapshot the extensions as u32s
+        let range = 0..=u32::MAX.into();
+        invariant!(
+            range.contains(&late_quorum_extension) && range.contains(&max_voting_extension),
+            "extensions must be between 0 and u32::MAX"
+        );
+        self.governor.late_quorum_extension = late_quorum_extension;
+        self.governor.max_voting_extension = max_voting_extension;
+
+        emit!(GovernorSetLateQuorumExtension {
+            governor: self.governor.key(),
+            late_quorum_extension,
+            max_voting_extension,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetLateQuorumExtension<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Event called in [govern::set_late_quorum_extension].
+#[event]
+pub struct GovernorSetLateQuorumExtension {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// Seconds voting is extended to when a late vote reaches quorum or changes the leading side.
+    pub late_quorum_extension: i64,
+    /// Maximum number of seconds voting may be extended by in total.
+    pub max_voting_extension: i64,
+}
@@ -0,0 +1,60 @@
+use anchor_spl::token::Mint;
+
+use crate::*;
+/// Accounts for [govern::set_proposal_deposit].
+#[derive(Accounts)]
+pub struct SetProposalDeposit<'info> {
+    /// The [Governor]
+    #[account(mut, has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// deposit mint
+    pub deposit_mint: Account<'info, Mint>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetProposalDeposit<'info> {
+    pub fn set_proposal_deposit(
+        &mut self,
+        deposit_amount: u64,
+        refund_threshold_bps: u16,
+    ) -> Result<()> {
+        invariant!(refund_threshold_bps <= MAX_BPS, InvalidRefundThreshold);
+        let deposit_mint = self.deposit_mint.key();
+
+        self.governor.proposal_deposit = ProposalDeposit {
+            deposit_mint,
+            deposit_amount,
+            refund_threshold_bps,
+        };
+
+        emit!(GovernorSetProposalDepositEvent {
+            governor: self.governor.key(),
+            deposit_mint,
+            deposit_amount,
+            refund_threshold_bps,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetProposalDeposit<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Event called in [govern::set_proposal_deposit].
+#[event]
+pub struct GovernorSetProposalDepositEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// Deposit mint
+    pub deposit_mint: Pubkey,
+    /// Amount escrowed per proposal
+    pub deposit_amount: u64,
+    /// Share of the quorum, in basis points, required for a refund
+    pub refund_threshold_bps: u16,
+}
@@ -0,0 +1,144 @@
+use crate::*;
+
+/// Accounts for [govern::create_proposal_policy].
+#[derive(Accounts)]
+#[instruction(allow_unlisted: bool, lists: Vec<InstructionList>)]
+pub struct CreateProposalPolicy<'info> {
+    /// The [Governor]
+    #[account(mut, has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [ProposalPolicy].
+    #[account(
+        init,
+        seeds = [
+            b"ProposalPolicy".as_ref(),
+            governor.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + ProposalPolicy::space(&lists)
+    )]
+    pub proposal_policy: Box<Account<'info, ProposalPolicy>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+    /// Payer of the [ProposalPolicy], e.g. the owner executing the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateProposalPolicy<'info> {
+    pub fn create_proposal_policy(
+        &mut self,
+        bump: u8,
+        allow_unlisted: bool,
+        lists: Vec<InstructionList>,
+    ) -> Result<()> {
+        validate_instruction_lists(&lists)?;
+
+        let proposal_policy = &mut self.proposal_policy;
+        proposal_policy.governor = self.governor.key();
+        proposal_policy.bump = bump;
+        proposal_policy.allow_unlisted = allow_unlisted;
+        proposal_policy.lists = lists.clone();
+
+        self.governor.proposal_policy = proposal_policy.key();
+
+        emit!(ProposalPolicySetEvent {
+            governor: self.governor.key(),
+            proposal_policy: proposal_policy.key(),
+            allow_unlisted,
+            lists,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateProposalPolicy<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Accounts for [govern::set_proposal_policy].
+#[derive(Accounts)]
+#[instruction(allow_unlisted: bool, lists: Vec<InstructionList>)]
+pub struct SetProposalPolicy<'info> {
+    /// The [Governor]
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [ProposalPolicy].
+    #[account(
+        mut,
+        has_one = governor,
+        realloc = 8 + ProposalPolicy::space(&lists),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub proposal_policy: Box<Account<'info, ProposalPolicy>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+    /// Payer of a larger [ProposalPolicy], e.g. the owner executing the transaction.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetProposalPolicy<'info> {
+    pub fn set_proposal_policy(
+        &mut self,
+        allow_unlisted: bool,
+        lists: Vec<InstructionList>,
+    ) -> Result<()> {
+        validate_instruction_lists(&lists)?;
+
+        let proposal_policy = &mut self.proposal_policy;
+        proposal_policy.allow_unlisted = allow_unlisted;
+        proposal_policy.lists = lists.clone();
+
+        emit!(ProposalPolicySetEvent {
+            governor: self.governor.key(),
+            proposal_policy: proposal_policy.key(),
+            allow_unlisted,
+            lists,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetProposalPolicy<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.proposal_policy, self.governor.proposal_policy);
+        Ok(())
+    }
+}
+
+/// Checks the [InstructionList::params] are within bounds.
+fn validate_instruction_lists(lists: &[InstructionList]) -> Result<()> {
+    for params in lists.iter().filter_map(|list| list.params.as_ref()) {
+        invariant!(
+            params.timelock_delay_seconds >= 0,
+            "timelock delay must be at least 0 seconds"
+        );
+        params.validate()?;
+    }
+    Ok(())
+}
+
+/// Event called in [govern::create_proposal_policy] and [govern::set_proposal_policy].
+#[event]
+pub struct ProposalPolicySetEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The [ProposalPolicy].
+    pub proposal_policy: Pubkey,
+    /// Whether proposals may call unlisted instructions.
+    pub allow_unlisted: bool,
+    /// The lists of instructions.
+    pub lists: Vec<InstructionList>,
+}
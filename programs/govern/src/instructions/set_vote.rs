@@ -17,30 +17,94 @@ pub struct SetVote<'info> {
 impl<'info> SetVote<'info> {
     /// Queues a Transaction into the Smart Wallet.
     pub fn set_vote(&mut self, side: u8, voting_power: u64) -> Result<()> {
-        let vote = &self.vote;
-
-        let proposal = &mut self.proposal;
-        proposal.subtract_vote_weight(vote.side, vote.voting_power)?;
-        proposal.add_vote_weight(side, voting_power)?;
-
-        let vote = &mut self.vote;
-        vote.side = side;
-        vote.voting_power = voting_power;
+        // on proposals with vote choices, a single side is a ballot with one choice
+        let mut choices = [0u8; MAX_OPTION as usize];
+        let proposal_type = ProposalType::try_from(self.proposal.proposal_type)?;
+        if proposal_type.has_vote_choices() {
+            choices[0] = side;
+        }
+        update_ballot(
+            &mut self.proposal,
+            &mut self.vote,
+            side,
+            choices,
+            voting_power,
+        )
+    }
 
-        emit!(VoteSetEvent {
-            governor: proposal.governor,
-            proposal: proposal.key(),
-            voter: vote.voter,
-            vote: vote.key(),
+    /// Sets the choices of a vote on an [ProposalType::Approval] or [ProposalType::RankedChoice] proposal.
+    pub fn set_vote_choices(&mut self, choices: Vec<u8>, voting_power: u64) -> Result<()> {
+        let choices = self.proposal.to_vote_choices(&choices)?;
+        // ranked-choice votes count towards their first choice until it is eliminated
+        let side = choices[0];
+        update_ballot(
+            &mut self.proposal,
+            &mut self.vote,
             side,
+            choices,
             voting_power,
-        });
+        )
+    }
+}
 
+impl<'info> Validate<'info> for SetVote<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.governor.locker, self.locker);
+        assert_keys_eq!(
+            self.governor,
+            self.proposal.governor,
+            "proposal should be under the governor"
+        );
+        assert_keys_eq!(
+            self.vote.proposal,
+            self.proposal,
+            "vote proposal should match"
+        );
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Active,
+            ProposalNotActive
+        );
+        if self.vote.cast_at != 0 {
+            invariant!(
+                !self.proposal.is_vote_locked(Clock::get()?.unix_timestamp),
+                VoteLocked
+            );
+        }
         Ok(())
     }
 }
 
-impl<'info> Validate<'info> for SetVote<'info> {
+/// Accounts for [govern::set_vote_power].
+#[derive(Accounts)]
+pub struct SetVotePower<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal].
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Vote].
+    #[account(mut)]
+    pub vote: Box<Account<'info, Vote>>,
+    /// The [Governor::locker].
+    pub locker: Signer<'info>,
+}
+
+impl<'info> SetVotePower<'info> {
+    /// Sets the voting power of a cast [Vote], keeping its side and choices.
+    pub fn set_vote_power(&mut self, voting_power: u64) -> Result<()> {
+        let side = self.vote.side;
+        let choices = self.vote.choices;
+        update_ballot(
+            &mut self.proposal,
+            &mut self.vote,
+            side,
+            choices,
+            voting_power,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for SetVotePower<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.governor.locker, self.locker);
         assert_keys_eq!(
@@ -57,10 +121,58 @@ impl<'info> Validate<'info> for SetVote<'info> {
             self.proposal.get_state()? == ProposalState::Active,
             ProposalNotActive
         );
+        // the voter does not change their ballot, so the vote lock does not apply
+        invariant!(self.vote.cast_at != 0, "vote must be cast");
         Ok(())
     }
 }
 
+/// Replaces the ballot of the [Vote] in the tally of the [Proposal].
+fn update_ballot(
+    proposal: &mut Account<Proposal>,
+    vote: &mut Account<Vote>,
+    side: u8,
+    choices: [u8; MAX_OPTION as usize],
+    voting_power: u64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let leading_side = proposal.leading_side();
+    let met_quorum = unwrap_opt!(proposal.meets_quorum());
+    proposal.subtract_ballot(vote)?;
+
+    if vote.cast_at == 0 {
+        vote.cast_at = now;
+    }
+    vote.side = side;
+    vote.choices = choices;
+    vote.voting_power = voting_power;
+    proposal.add_ballot(vote)?;
+
+    emit!(VoteSetEvent {
+        governor: proposal.governor,
+        proposal: proposal.key(),
+        voter: vote.voter,
+        vote: vote.key(),
+        side,
+        voting_power,
+    });
+
+    // votes tipping the proposal late give others time to react
+    let reached_quorum = !met_quorum && unwrap_opt!(proposal.meets_quorum());
+    if (reached_quorum || proposal.leading_side() != leading_side)
+        && unwrap_opt!(proposal.extend_voting(now))
+    {
+        emit!(VotingExtendedEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            voting_ends_at: proposal.voting_ends_at,
+            reached_quorum,
+        });
+    }
+
+    Ok(())
+}
+
 /// Event called in [govern::set_vote].
 #[event]
 pub struct VoteSetEvent {
@@ -82,3 +194,18 @@ pub struct VoteSetEvent {
     /// The vote's voting_power.
     pub voting_power: u64,
 }
+
+/// Event called in [govern::set_vote] when a late vote extends voting.
+#[event]
+pub struct VotingExtendedEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal being voted on.
+    #[index]
+    pub proposal: Pubkey,
+    /// When voting now ends.
+    pub voting_ends_at: i64,
+    /// True if the vote reached quorum, false if it changed the leading side.
+    pub reached_quorum: bool,
+}
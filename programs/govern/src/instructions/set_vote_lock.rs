@@ -0,0 +1,49 @@
+use crate::*;
+
+/// Accounts for [govern::set_vote_lock].
+#[derive(Accounts)]
+pub struct SetVoteLock<'info> {
+    /// The [Governor]
+    #[account(mut, has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetVoteLock<'info> {
+    pub fn set_vote_lock(&mut self, vote_lock_period: i64, votes_final: bool) -> Result<()> {
+        // proposals snapshot the period as a u32
+        invariant!(
+            vote_lock_period >= 0 && vote_lock_period <= u32::MAX.into(),
+            "vote lock period must be between 0 and u32::MAX"
+        );
+        self.governor.vote_lock_period = vote_lock_period;
+        self.governor.votes_final = votes_final;
+
+        emit!(GovernorSetVoteLock {
+            governor: self.governor.key(),
+            vote_lock_period,
+            votes_final,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetVoteLock<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Event called in [govern::set_vote_lock].
+#[event]
+pub struct GovernorSetVoteLock {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// Seconds before voting ends during which votes already cast cannot be changed.
+    pub vote_lock_period: i64,
+    /// If true, a vote cannot be changed once cast.
+    pub votes_final: bool,
+}
@@ -0,0 +1,53 @@
+use crate::*;
+
+/// Accounts for [govern::set_voting_reward_params].
+#[derive(Accounts)]
+pub struct SetVotingRewardParams<'info> {
+    /// The [Governor]
+    #[account(mut, has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Signer<'info>,
+}
+
+impl<'info> SetVotingRewardParams<'info> {
+    pub fn set_voting_reward_params(
+        &mut self,
+        reward_claim_period: i64,
+        reward_policy: u8,
+    ) -> Result<()> {
+        invariant!(
+            reward_claim_period >= 0,
+            "claim period must not be negative"
+        );
+        RewardPolicy::try_from(reward_policy)?;
+        self.governor.reward_claim_period = reward_claim_period;
+        self.governor.reward_policy = reward_policy;
+
+        emit!(GovernorSetVotingRewardParams {
+            governor: self.governor.key(),
+            reward_claim_period,
+            reward_policy,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetVotingRewardParams<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Event called in [govern::set_voting_reward_params].
+#[event]
+pub struct GovernorSetVotingRewardParams {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// Seconds after voting ends during which rewards may be claimed. 0 if forever.
+    pub reward_claim_period: i64,
+    /// How the reward is shared among voters. See [RewardPolicy].
+    pub reward_policy: u8,
+}
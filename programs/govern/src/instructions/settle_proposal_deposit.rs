@@ -0,0 +1,144 @@
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+/// Accounts for [govern::settle_proposal_deposit].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SettleProposalDeposit<'info> {
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal].
+    #[account(mut, has_one = governor, has_one = proposer)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// Escrow of the deposit.
+    #[account(
+        mut,
+        seeds = [
+            b"ProposalDeposit".as_ref(),
+            proposal.key().as_ref()
+        ],
+        bump,
+    )]
+    pub deposit_vault: Box<Account<'info, TokenAccount>>,
+    /// The [Proposal::proposer], who receives the rent of the escrow.
+    /// CHECK: checked by `has_one` on the proposal.
+    #[account(mut)]
+    pub proposer: UncheckedAccount<'info>,
+    /// Token account of the proposer, which receives a refunded deposit.
+    #[account(mut)]
+    pub proposer_token_account: Box<Account<'info, TokenAccount>>,
+    /// Token account of the [Governor::smart_wallet], which receives a slashed deposit.
+    #[account(mut)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SettleProposalDeposit<'info> {
+    /// Refunds or slashes the deposit, then closes the escrow.
+    pub fn settle_proposal_deposit(&mut self) -> Result<ProposalDepositSettleEvent> {
+        let proposal_state = self.proposal.get_state()?;
+        let refunded = unwrap_opt!(
+            self.proposal.is_deposit_refundable(&proposal_state),
+            "Math is overflow"
+        );
+        let destination = if refunded {
+            self.proposer_token_account.to_account_info()
+        } else {
+            self.treasury.to_account_info()
+        };
+
+        release_proposal_deposit(
+            &self.governor,
+            self.deposit_vault.to_account_info(),
+            destination,
+            self.proposer.to_account_info(),
+            self.token_program.to_account_info(),
+            self.proposal.deposit_amount,
+        )?;
+
+        let proposal = &mut self.proposal;
+        proposal.deposit_settled_at = Clock::get()?.unix_timestamp;
+
+        Ok(ProposalDepositSettleEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            proposer: proposal.proposer,
+            deposit_amount: proposal.deposit_amount,
+            refunded,
+        })
+    }
+}
+
+/// Transfers the deposit escrowed in `deposit_vault` to `destination`,
+/// then closes the escrow, sending its rent to `rent_receiver`.
+pub(crate) fn release_proposal_deposit<'info>(
+    governor: &Account<'info, Governor>,
+    deposit_vault: AccountInfo<'info>,
+    destination: AccountInfo<'info>,
+    rent_receiver: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+    deposit_amount: u64,
+) -> Result<()> {
+    let seeds = governor_seeds!(governor);
+    let signer_seeds = &[&seeds[..]];
+    token::transfer(
+        CpiContext::new_with_signer(
+            token_program.clone(),
+            token::Transfer {
+                from: deposit_vault.clone(),
+                to: destination,
+                authority: governor.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        deposit_amount,
+    )?;
+    token::close_account(CpiContext::new_with_signer(
+        token_program,
+        token::CloseAccount {
+            account: deposit_vault,
+            destination: rent_receiver,
+            authority: governor.to_account_info(),
+        },
+        signer_seeds,
+    ))
+}
+
+impl<'info> Validate<'info> for SettleProposalDeposit<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.proposal.has_unsettled_deposit(),
+            "proposal has no unsettled deposit"
+        );
+        let proposal_state = self.proposal.get_state()?;
+        invariant!(
+            proposal_state != ProposalState::Draft && proposal_state != ProposalState::Active,
+            "proposal must be canceled or voting must be over"
+        );
+
+        let deposit_mint = self.deposit_vault.mint;
+        assert_keys_eq!(self.proposer_token_account.owner, self.proposal.proposer);
+        assert_keys_eq!(self.proposer_token_account.mint, deposit_mint);
+        assert_keys_eq!(self.treasury.owner, self.governor.smart_wallet);
+        assert_keys_eq!(self.treasury.mint, deposit_mint);
+        Ok(())
+    }
+}
+
+/// Event called in [govern::settle_proposal_deposit].
+#[event]
+pub struct ProposalDepositSettleEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// The proposer.
+    pub proposer: Pubkey,
+    /// The deposit.
+    pub deposit_amount: u64,
+    /// True if the deposit was refunded to the proposer, false if slashed to the treasury.
+    pub refunded: bool,
+}
@@ -0,0 +1,130 @@
+use crate::*;
+use anchor_spl::token::{self, Token, TokenAccount};
+
+/// Accounts for [govern::sweep_voting_reward].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SweepVotingReward<'info> {
+    /// The [Governor].
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal].
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [VotingReward::reward_vault] of the proposal.
+    #[account(mut)]
+    pub reward_vault: Box<Account<'info, TokenAccount>>,
+    /// Token account of the [Governor::smart_wallet] receiving the unclaimed reward.
+    #[account(mut)]
+    pub treasury: Box<Account<'info, TokenAccount>>,
+    /// The [Governor::smart_wallet].
+    pub smart_wallet: Signer<'info>,
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> SweepVotingReward<'info> {
+    pub fn sweep_voting_reward(&mut self) -> Result<ProposalRewardSweepEvent> {
+        sweep_unclaimed_reward(
+            &self.governor,
+            &mut self.proposal,
+            self.reward_vault.to_account_info(),
+            self.treasury.to_account_info(),
+            self.token_program.to_account_info(),
+        )
+    }
+}
+
+impl<'info> Validate<'info> for SweepVotingReward<'info> {
+    fn validate(&self) -> Result<()> {
+        validate_sweep_accounts(
+            &self.proposal,
+            self.smart_wallet.key(),
+            &self.reward_vault,
+            &self.treasury,
+        )?;
+
+        invariant!(self.proposal.reward_swept_at == 0, "reward has been swept");
+        let now = Clock::get()?.unix_timestamp;
+        let proposal_state = unwrap_opt!(self.proposal.state(now), "invalid state");
+        invariant!(
+            unwrap_opt!(
+                self.proposal.is_reward_window_over(&proposal_state, now),
+                "Math is overflow"
+            ),
+            "claim window must be over"
+        );
+        Ok(())
+    }
+}
+
+/// Transfers the voting reward of the proposal which has not been claimed to the treasury.
+pub(crate) fn sweep_unclaimed_reward<'info>(
+    governor: &Account<'info, Governor>,
+    proposal: &mut Account<'info, Proposal>,
+    reward_vault: AccountInfo<'info>,
+    treasury: AccountInfo<'info>,
+    token_program: AccountInfo<'info>,
+) -> Result<ProposalRewardSweepEvent> {
+    let swept_reward = unwrap_opt!(proposal.unswept_reward(), "Math is overflow");
+
+    if swept_reward != 0 {
+        let seeds = governor_seeds!(governor);
+        let signer_seeds = &[&seeds[..]];
+        token::transfer(
+            CpiContext::new_with_signer(
+                token_program,
+                token::Transfer {
+                    from: reward_vault,
+                    to: treasury,
+                    authority: governor.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            swept_reward,
+        )?;
+    }
+
+    proposal.reward_swept_at = Clock::get()?.unix_timestamp;
+    proposal.swept_reward = swept_reward;
+
+    Ok(ProposalRewardSweepEvent {
+        governor: proposal.governor,
+        proposal: proposal.key(),
+        reward_mint: proposal.voting_reward.reward_mint,
+        total_claimed_reward: proposal.total_claimed_reward,
+        swept_reward,
+    })
+}
+
+/// Validates the accounts needed to sweep the unclaimed voting reward of a proposal
+/// to the token account of the [Governor::smart_wallet].
+pub(crate) fn validate_sweep_accounts(
+    proposal: &Proposal,
+    smart_wallet: Pubkey,
+    reward_vault: &Account<TokenAccount>,
+    treasury: &Account<TokenAccount>,
+) -> Result<()> {
+    let voting_reward = proposal.voting_reward;
+    assert_keys_eq!(voting_reward.reward_vault, reward_vault.key());
+    assert_keys_eq!(treasury.owner, smart_wallet);
+    assert_keys_eq!(treasury.mint, voting_reward.reward_mint);
+    Ok(())
+}
+
+/// Event called in [govern::sweep_voting_reward] and [govern::close_proposal].
+#[event]
+pub struct ProposalRewardSweepEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal.
+    #[index]
+    pub proposal: Pubkey,
+    /// Reward mint
+    pub reward_mint: Pubkey,
+    /// Voting reward claimed by voters
+    pub total_claimed_reward: u64,
+    /// Unclaimed voting reward swept back to the treasury
+    pub swept_reward: u64,
+}
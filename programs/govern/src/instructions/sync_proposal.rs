@@ -0,0 +1,76 @@
+use crate::*;
+use smart_wallet::Transaction;
+
+/// Accounts for [govern::sync_proposal].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct SyncProposal<'info> {
+    /// The [Governor].
+    #[account(has_one = smart_wallet)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal] to sync.
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Proposal::queued_transaction].
+    pub transaction: Box<Account<'info, Transaction>>,
+    /// The Smart Wallet.
+    pub smart_wallet: Box<Account<'info, SmartWallet>>,
+}
+
+impl<'info> SyncProposal<'info> {
+    /// Records the execution or expiry of the queued transaction on the [Proposal].
+    pub fn sync_proposal(&mut self) -> Result<ProposalSyncEvent> {
+        let transaction = &self.transaction;
+        let proposal = &mut self.proposal;
+
+        if transaction.executed_at != -1 {
+            proposal.executed_at = transaction.executed_at;
+        } else {
+            let now = Clock::get()?.unix_timestamp;
+            invariant!(
+                transaction.eta != smart_wallet::NO_ETA
+                    && now
+                        > unwrap_int!(transaction.eta.checked_add(self.smart_wallet.grace_period)),
+                "queued transaction may still be executed"
+            );
+            proposal.expired_at = now;
+        }
+
+        Ok(ProposalSyncEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            transaction: transaction.key(),
+            executed_at: proposal.executed_at,
+            expired_at: proposal.expired_at,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for SyncProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Queued,
+            "proposal must be queued"
+        );
+        assert_keys_eq!(self.transaction, self.proposal.queued_transaction);
+        assert_keys_eq!(self.transaction.smart_wallet, self.smart_wallet);
+        Ok(())
+    }
+}
+
+/// Event called in [govern::sync_proposal].
+#[event]
+pub struct ProposalSyncEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal being synced.
+    #[index]
+    pub proposal: Pubkey,
+    /// The queued transaction of the proposal.
+    pub transaction: Pubkey,
+    /// When the transaction was executed. 0 if not executed.
+    pub executed_at: i64,
+    /// When the proposal was found expired. 0 if not expired.
+    pub expired_at: i64,
+}
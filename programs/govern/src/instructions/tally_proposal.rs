@@ -0,0 +1,83 @@
+use crate::*;
+
+/// Accounts for [govern::tally_proposal].
+///
+/// For [ProposalType::RankedChoice] proposals, the [Vote]s of eliminated options
+/// are passed in the remaining accounts.
+#[event_cpi]
+#[derive(Accounts)]
+pub struct TallyProposal<'info> {
+    /// The [Proposal] to tally.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+}
+
+impl<'info> TallyProposal<'info> {
+    /// Tallies the [Proposal]. Returns an event once the winning option is decided.
+    pub fn tally_proposal(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<Option<ProposalTallyEvent>> {
+        let proposal_key = self.proposal.key();
+        let proposal = &mut self.proposal;
+
+        let winning_option = if proposal.proposal_type == u8::from(ProposalType::RankedChoice) {
+            for vote_info in remaining_accounts {
+                let mut vote = Account::<Vote>::try_from(vote_info)?;
+                assert_keys_eq!(vote.proposal, proposal_key, "vote proposal should match");
+                proposal.transfer_ranked_vote(&mut vote)?;
+                vote.exit(&crate::ID)?;
+            }
+            proposal.tally_ranked_choice()
+        } else {
+            proposal.tally_plurality()
+        };
+
+        let winning_option = match winning_option {
+            Some(winning_option) => winning_option,
+            None => {
+                msg!(
+                    "eliminated options: {:#b}, more votes must be transferred",
+                    proposal.eliminated_options
+                );
+                return Ok(None);
+            }
+        };
+        proposal.tallied_at = Clock::get()?.unix_timestamp;
+
+        Ok(Some(ProposalTallyEvent {
+            governor: proposal.governor,
+            proposal: proposal_key,
+            winning_option,
+        }))
+    }
+}
+
+impl<'info> Validate<'info> for TallyProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        let proposal_type = ProposalType::try_from(self.proposal.proposal_type)?;
+        invariant!(proposal_type.is_multi_option(), NotOptionProposal);
+        invariant!(self.proposal.tallied_at == 0, ProposalAlreadyTallied);
+
+        let now = Clock::get()?.unix_timestamp;
+        let proposal_state = unwrap_opt!(self.proposal.state(now), "invalid state");
+        invariant!(
+            proposal_state == ProposalState::Succeeded,
+            "proposal must be succeeded to be tallied"
+        );
+        Ok(())
+    }
+}
+
+/// Event called in [govern::tally_proposal].
+#[event]
+pub struct ProposalTallyEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal being tallied.
+    #[index]
+    pub proposal: Pubkey,
+    /// The winning option, or 0 if no option has won.
+    pub winning_option: u8,
+}
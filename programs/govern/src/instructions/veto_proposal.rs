@@ -0,0 +1,104 @@
+use crate::*;
+use smart_wallet::Transaction;
+
+/// Accounts for [govern::veto_proposal].
+#[event_cpi]
+#[derive(Accounts)]
+pub struct VetoProposal<'info> {
+    /// The [Governor].
+    #[account(mut, has_one = smart_wallet, has_one = veto_authority)]
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [Proposal] to veto.
+    #[account(mut, has_one = governor)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Governor::veto_authority].
+    pub veto_authority: Signer<'info>,
+    /// The [Proposal::queued_transaction], if the proposal is queued.
+    #[account(mut)]
+    pub transaction: Option<Box<Account<'info, Transaction>>>,
+    /// The payer of the [Proposal::queued_transaction], who receives its rent.
+    /// CHECK: checked by the [smart_wallet] program.
+    #[account(mut)]
+    pub transaction_payer: Option<UncheckedAccount<'info>>,
+    /// The Smart Wallet.
+    #[account(mut)]
+    pub smart_wallet: Box<Account<'info, SmartWallet>>,
+    /// The Smart Wallet program.
+    pub smart_wallet_program: Program<'info, smart_wallet::program::SmartWallet>,
+}
+
+impl<'info> VetoProposal<'info> {
+    /// Vetoes the [Proposal].
+    ///
+    /// If the proposal is queued, the [Governor] cancels the queued transaction, whatever
+    /// the approvals of the other owners of the smart wallet, so that it cannot be executed.
+    pub fn veto_proposal(&mut self) -> Result<ProposalVetoEvent> {
+        if let (Some(transaction), Some(transaction_payer)) =
+            (&self.transaction, &self.transaction_payer)
+        {
+            let seeds = governor_seeds!(self.governor);
+            let signer_seeds = &[&seeds[..]];
+            smart_wallet::cpi::cancel_transaction(CpiContext::new_with_signer(
+                self.smart_wallet_program.to_account_info(),
+                smart_wallet::cpi::accounts::CancelTransaction {
+                    smart_wallet: self.smart_wallet.to_account_info(),
+                    transaction: transaction.to_account_info(),
+                    proposer: self.governor.to_account_info(),
+                    payer: transaction_payer.to_account_info(),
+                },
+                signer_seeds,
+            ))?;
+        }
+
+        let proposal = &mut self.proposal;
+        proposal.vetoed_at = Clock::get()?.unix_timestamp;
+
+        Ok(ProposalVetoEvent {
+            governor: proposal.governor,
+            proposal: proposal.key(),
+            veto_authority: self.veto_authority.key(),
+            transaction: proposal.queued_transaction,
+        })
+    }
+}
+
+impl<'info> Validate<'info> for VetoProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        let proposal_state = self.proposal.get_state()?;
+        invariant!(
+            proposal_state == ProposalState::Active
+                || proposal_state == ProposalState::Succeeded
+                || proposal_state == ProposalState::Queued,
+            "proposal must be in voting or timelock"
+        );
+        if proposal_state == ProposalState::Queued {
+            invariant!(
+                self.transaction.is_some(),
+                "queued transaction must be provided"
+            );
+        }
+        if let Some(transaction) = &self.transaction {
+            assert_keys_eq!(transaction.key(), self.proposal.queued_transaction);
+            invariant!(
+                self.transaction_payer.is_some(),
+                "payer of the queued transaction must be provided"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Event called in [govern::veto_proposal].
+#[event]
+pub struct ProposalVetoEvent {
+    /// The governor.
+    #[index]
+    pub governor: Pubkey,
+    /// The proposal being vetoed.
+    #[index]
+    pub proposal: Pubkey,
+    /// The [Governor::veto_authority].
+    pub veto_authority: Pubkey,
+    /// The queued transaction of the proposal, canceled on the smart wallet, if any.
+    pub transaction: Pubkey,
+}
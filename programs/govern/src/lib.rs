@@ -12,11 +12,13 @@ use vipers::prelude::*;
 mod constants;
 mod instructions;
 mod proposal;
+mod signed_vote;
 mod state;
 
 pub use constants::*;
 pub use instructions::*;
 pub use proposal::*;
+pub use signed_vote::*;
 pub use state::*;
 
 declare_id!("GovaE4iu227srtG2s3tZzB4RmWBzw8sTwrCLZz7kN7rY");
@@ -49,6 +51,7 @@ pub mod govern {
     ) -> Result<()> {
         let event = ctx.accounts.create_proposal(
             unwrap_bump!(ctx, "proposal"),
+            unwrap_bump!(ctx, "proposal_params"),
             proposal_type,
             max_option,
             instructions,
@@ -57,16 +60,35 @@ pub mod govern {
         Ok(())
     }
 
+    /// Creates the [ProposalParams] of a [Proposal] created before they existed,
+    /// from the current [Governor::params]. Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_proposal_params(ctx: Context<CreateProposalParams>) -> Result<()> {
+        let event = ctx
+            .accounts
+            .create_proposal_params(unwrap_bump!(ctx, "proposal_params"))?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
     /// Activates a proposal.
     /// Only the [Governor::voter] may call this; that program
     /// may ensure that only certain types of users can activate proposals.
+    /// The locker also reports its total voting power, from which the quorum is
+    /// snapshotted if [GovernanceParameters::quorum_bps] is set.
     #[access_control(ctx.accounts.validate())]
-    pub fn activate_proposal(ctx: Context<ActivateProposal>) -> Result<()> {
-        ctx.accounts.activate_proposal()
+    pub fn activate_proposal(
+        ctx: Context<ActivateProposal>,
+        total_voting_power: u64,
+    ) -> Result<()> {
+        ctx.accounts.activate_proposal(total_voting_power)
     }
 
-    /// Cancels a proposal.
+    /// Cancels a proposal, while it is a draft or before any votes are cast.
     /// This is only callable by the creator of the proposal.
+    /// The rent of the proposal goes back to its payer and an unsettled deposit is slashed.
+    /// A proposal with [ProposalReward]s is kept until they are refunded and it is closed
+    /// with [govern::close_proposal].
     #[access_control(ctx.accounts.validate())]
     pub fn cancel_proposal(ctx: Context<CancelProposal>) -> Result<()> {
         let event = ctx.accounts.cancel_proposal()?;
@@ -74,10 +96,58 @@ pub mod govern {
         Ok(())
     }
 
+    /// Cancels a draft or active proposal.
+    /// This may only be called by the [Governor::smart_wallet].
+    /// The proposal is kept until closed with [govern::close_proposal].
+    #[access_control(ctx.accounts.validate())]
+    pub fn force_cancel_proposal(ctx: Context<ForceCancelProposal>) -> Result<()> {
+        let event = ctx.accounts.force_cancel_proposal()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
     /// Queues a proposal for execution by the [SmartWallet].
+    /// The lookup tables of its [ProposalParams::lookup_instructions] are passed as remaining accounts.
     #[access_control(ctx.accounts.validate())]
     pub fn queue_proposal(ctx: Context<QueueProposal>) -> Result<()> {
-        let event = ctx.accounts.queue_transaction()?;
+        let event = ctx.accounts.queue_transaction(ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Appends the next chunk of instructions of a succeeded [Proposal] too large to be
+    /// queued at once to its Smart Wallet transaction buffer, which [govern::queue_proposal]
+    /// then turns into the queued transaction. Anyone can call this.
+    /// The lookup tables of its [ProposalParams::lookup_instructions] are passed as remaining accounts.
+    #[access_control(ctx.accounts.validate())]
+    pub fn append_queued_instructions(ctx: Context<AppendQueuedInstructions>) -> Result<()> {
+        ctx.accounts
+            .append_queued_instructions(ctx.remaining_accounts)
+    }
+
+    /// Appends instructions to a draft [Proposal] too large to be created in one transaction.
+    /// Instructions referencing accounts through address lookup tables are kept in the
+    /// [ProposalParams] and resolved when the proposal is queued; the tables are passed
+    /// as remaining accounts to check the instructions against the proposal policies.
+    /// This unseals the proposal; it must be sealed with [govern::seal_proposal_instructions]
+    /// before it is activated. Only the proposer may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn append_proposal_instructions(
+        ctx: Context<AppendProposalInstructions>,
+        instructions: Vec<smart_wallet::TXLookupInstruction>,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .append_proposal_instructions(instructions, ctx.remaining_accounts)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Seals the instructions of a draft [Proposal], storing their hash.
+    /// Only the proposer may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn seal_proposal_instructions(ctx: Context<SealProposalInstructions>) -> Result<()> {
+        let event = ctx.accounts.seal_proposal_instructions()?;
         emit_cpi!(event);
         Ok(())
     }
@@ -95,6 +165,63 @@ pub mod govern {
         ctx.accounts.set_vote(side, weight)
     }
 
+    /// Sets a [Vote] weight and choices on an approval or ranked-choice [Proposal].
+    /// Choices are given in order of preference; an empty list is an abstain vote.
+    /// This may only be called by the [Governor::voter].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_vote_choices(ctx: Context<SetVote>, choices: Vec<u8>, weight: u64) -> Result<()> {
+        ctx.accounts.set_vote_choices(choices, weight)
+    }
+
+    /// Sets the weight of a cast [Vote], keeping its side and choices.
+    /// Unlike [govern::set_vote], this is allowed during the vote lock, since the voter does
+    /// not change their ballot, e.g. when voting power is delegated to or taken from them.
+    /// This may only be called by the [Governor::voter].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_vote_power(ctx: Context<SetVotePower>, weight: u64) -> Result<()> {
+        ctx.accounts.set_vote_power(weight)
+    }
+
+    /// Creates the [OptionInstructions] executed if an option of a multi-option [Proposal] wins.
+    /// This unseals the proposal; it must be sealed with [govern::seal_proposal_instructions]
+    /// before it is activated. This may only be called by the proposer while the proposal is a draft.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_option_instructions(
+        ctx: Context<CreateOptionInstructions>,
+        option: u8,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        let event = ctx
+            .accounts
+            .create_option_instructions(option, instructions)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Syncs a queued [Proposal] with its [Proposal::queued_transaction],
+    /// recording whether it was executed or has expired.
+    /// Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn sync_proposal(ctx: Context<SyncProposal>) -> Result<()> {
+        let event = ctx.accounts.sync_proposal()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Tallies a multi-option [Proposal] once voting is over, recording the winning option.
+    /// For ranked-choice proposals, remaining accounts are the [Vote]s to transfer
+    /// from eliminated options; the instruction may be called repeatedly until a winner is found.
+    /// Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn tally_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, TallyProposal<'info>>,
+    ) -> Result<()> {
+        if let Some(event) = ctx.accounts.tally_proposal(ctx.remaining_accounts)? {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
     /// Sets the [GovernanceParameters].
     /// This may only be called by the [Governor::smart_wallet].
     #[access_control(ctx.accounts.validate())]
@@ -115,20 +242,255 @@ pub mod govern {
         ctx.accounts.set_voting_reward(reward_per_proposal)
     }
 
-    /// Claim rewards, for voter
+    /// Sets how long voting rewards may be claimed and how they are shared among voters.
+    /// Applies to proposals created afterwards.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_voting_reward_params(
+        ctx: Context<SetVotingRewardParams>,
+        reward_claim_period: i64,
+        reward_policy: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_voting_reward_params(reward_claim_period, reward_policy)
+    }
+
+    /// Sets when votes may no longer be changed. Applies to proposals created afterwards.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_vote_lock(
+        ctx: Context<SetVoteLock>,
+        vote_lock_period: i64,
+        votes_final: bool,
+    ) -> Result<()> {
+        ctx.accounts.set_vote_lock(vote_lock_period, votes_final)
+    }
+
+    /// Sets how long voting is extended by when quorum is reached, or the leading side
+    /// changes, late in the voting period. Applies to proposals created afterwards.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_late_quorum_extension(
+        ctx: Context<SetLateQuorumExtension>,
+        late_quorum_extension: i64,
+        max_voting_extension: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_late_quorum_extension(late_quorum_extension, max_voting_extension)
+    }
+
+    /// Sweeps the voting reward of a [Proposal] which was not claimed within the claim window
+    /// back to the treasury.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn sweep_voting_reward(ctx: Context<SweepVotingReward>) -> Result<()> {
+        let event = ctx.accounts.sweep_voting_reward()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Sets the deposit escrowed when a proposal is created.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_proposal_deposit(
+        ctx: Context<SetProposalDeposit>,
+        deposit_amount: u64,
+        refund_threshold_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_proposal_deposit(deposit_amount, refund_threshold_bps)
+    }
+
+    /// Creates a [ProposalClass] with its own [GovernanceParameters],
+    /// for proposals calling the instructions matching its filters.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_proposal_class(
+        ctx: Context<CreateProposalClass>,
+        name: String,
+        params: GovernanceParameters,
+        filters: Vec<InstructionFilter>,
+    ) -> Result<()> {
+        ctx.accounts.create_proposal_class(
+            unwrap_bump!(ctx, "proposal_class"),
+            name,
+            params,
+            filters,
+        )
+    }
+
+    /// Sets the [GovernanceParameters] and the instruction filters of a [ProposalClass].
+    /// Proposals already created keep the parameters they were created with.
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_proposal_class_params(
+        ctx: Context<SetProposalClassParams>,
+        params: GovernanceParameters,
+        filters: Vec<InstructionFilter>,
+    ) -> Result<()> {
+        ctx.accounts.set_proposal_class_params(params, filters)
+    }
+
+    /// Creates the [ProposalPolicy] restricting the instructions of proposals.
+    /// This may only be called by the [Governor::smart_wallet].
     #[access_control(ctx.accounts.validate())]
-    pub fn claim_reward(ctx: Context<ClaimReward>) -> Result<()> {
-        let event = ctx.accounts.claim_reward()?;
+    pub fn create_proposal_policy(
+        ctx: Context<CreateProposalPolicy>,
+        allow_unlisted: bool,
+        lists: Vec<InstructionList>,
+    ) -> Result<()> {
+        ctx.accounts.create_proposal_policy(
+            unwrap_bump!(ctx, "proposal_policy"),
+            allow_unlisted,
+            lists,
+        )
+    }
+
+    /// Replaces the lists of the [ProposalPolicy].
+    /// This may only be called by the [Governor::smart_wallet].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_proposal_policy(
+        ctx: Context<SetProposalPolicy>,
+        allow_unlisted: bool,
+        lists: Vec<InstructionList>,
+    ) -> Result<()> {
+        ctx.accounts.set_proposal_policy(allow_unlisted, lists)
+    }
+
+    /// Refunds the deposit of a [Proposal] to the proposer, or slashes it to the treasury
+    /// if the proposal was canceled or its votes did not reach the refund threshold.
+    /// Anyone can call this once voting is over.
+    #[access_control(ctx.accounts.validate())]
+    pub fn settle_proposal_deposit(ctx: Context<SettleProposalDeposit>) -> Result<()> {
+        let event = ctx.accounts.settle_proposal_deposit()?;
         emit_cpi!(event);
         Ok(())
     }
 
+    /// Claim rewards, for voter.
+    /// Remaining accounts are `[proposal_reward, reward_vault, voter_token_account]` triples,
+    /// one for each [ProposalReward] to claim besides the [Governor::voting_reward].
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_reward<'info>(ctx: Context<'_, '_, '_, 'info, ClaimReward<'info>>) -> Result<()> {
+        let (event, reward_events) = ctx.accounts.claim_reward(ctx.remaining_accounts)?;
+        if let Some(event) = event {
+            emit_cpi!(event);
+        }
+        for event in reward_events {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Creates a [ProposalReward] co-sponsoring a [Proposal] with any SPL token.
+    /// This may be called by anyone while the proposal is a draft or active.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_proposal_reward(ctx: Context<CreateProposalReward>) -> Result<()> {
+        let event = ctx
+            .accounts
+            .create_proposal_reward(unwrap_bump!(ctx, "proposal_reward"))?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Tops up a [ProposalReward].
+    /// This may be called by anyone while the proposal is a draft or active.
+    #[access_control(ctx.accounts.validate())]
+    pub fn fund_proposal_reward(ctx: Context<FundProposalReward>, amount: u64) -> Result<()> {
+        let event = ctx.accounts.fund_proposal_reward(amount)?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Refunds the unclaimed reward of a [ProposalReward] to its sponsor once the claim window
+    /// is over, closing it. A [Proposal] cannot be closed until all its rewards are refunded.
+    /// This may be called by anyone.
+    #[access_control(ctx.accounts.validate())]
+    pub fn refund_proposal_reward(ctx: Context<RefundProposalReward>) -> Result<()> {
+        let event = ctx.accounts.refund_proposal_reward()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Claim rewards on behalf of a voter, e.g. by a vote delegate or a cranker.
+    /// The rewards are always paid to token accounts owned by the [Vote::voter].
+    /// Remaining accounts are `[proposal_reward, reward_vault, voter_token_account]` triples,
+    /// one for each [ProposalReward] to claim besides the [Governor::voting_reward].
+    #[access_control(ctx.accounts.validate())]
+    pub fn claim_reward_for_voter<'info>(
+        ctx: Context<'_, '_, '_, 'info, ClaimRewardForVoter<'info>>,
+    ) -> Result<()> {
+        let (event, reward_events) = ctx
+            .accounts
+            .claim_reward_for_voter(ctx.remaining_accounts)?;
+        if let Some(event) = event {
+            emit_cpi!(event);
+        }
+        for event in reward_events {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
+    /// Claim rewards for many [Vote]s of a [Proposal].
+    /// Remaining accounts are `[vote, voter_token_account]` pairs.
+    #[access_control(ctx.accounts.validate())]
+    pub fn batch_claim_reward<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchClaimReward<'info>>,
+    ) -> Result<()> {
+        let events = ctx.accounts.batch_claim_reward(ctx.remaining_accounts)?;
+        for event in events {
+            emit_cpi!(event);
+        }
+        Ok(())
+    }
+
     /// Sets the locker of the [Governor].
     #[access_control(ctx.accounts.validate())]
     pub fn set_locker(ctx: Context<SetGovernanceParams>, new_locker: Pubkey) -> Result<()> {
         ctx.accounts.set_locker(new_locker)
     }
 
+    /// Sets the veto authority of the [Governor].
+    /// [Pubkey::default] removes the veto authority.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_veto_authority(
+        ctx: Context<SetGovernanceParams>,
+        new_veto_authority: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.set_veto_authority(new_veto_authority)
+    }
+
+    /// Vetoes a [Proposal] during voting or the timelock, canceling its queued transaction.
+    /// This may only be called by the [Governor::veto_authority]. A veto cannot be undone.
+    #[access_control(ctx.accounts.validate())]
+    pub fn veto_proposal(ctx: Context<VetoProposal>) -> Result<()> {
+        let event = ctx.accounts.veto_proposal()?;
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Closes a finished [Proposal], along with its [ProposalMeta] and [OptionProposalMeta].
+    /// The rent of the proposal and of its metadata goes back to their payers.
+    /// Anyone can call this once the deposit is settled and rewards can no longer be claimed.
+    /// The voting reward which was not claimed is swept to the treasury.
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_proposal(ctx: Context<CloseProposal>) -> Result<()> {
+        let (event, sweep_event) = ctx.accounts.close_proposal()?;
+        if let Some(sweep_event) = sweep_event {
+            emit_cpi!(sweep_event);
+        }
+        emit_cpi!(event);
+        Ok(())
+    }
+
+    /// Closes a [Vote] once voting is over and its reward has been claimed or can no longer be claimed.
+    /// The rent goes to the voter, since the [Vote] does not record its payer.
+    #[access_control(ctx.accounts.validate())]
+    pub fn close_vote(ctx: Context<CloseVote>) -> Result<()> {
+        ctx.accounts.close_vote()
+    }
+
     /// Creates a [ProposalMeta].
     #[access_control(ctx.accounts.validate())]
     pub fn create_proposal_meta(
@@ -178,4 +540,48 @@ pub enum ErrorCode {
     NotOptionProposal,
     #[msg("Invalid option descriptions.")]
     InvalidOptionDescriptions,
+    #[msg("Vote choices must be distinct options of the proposal.")]
+    InvalidVoteChoices,
+    #[msg("The proposal has already been tallied.")]
+    ProposalAlreadyTallied,
+    #[msg("The proposal must be tallied before it can be queued.")]
+    ProposalNotTallied,
+    #[msg("Invalid instructions for the option.")]
+    InvalidOptionInstructions,
+    #[msg("Approval threshold must be at most 10000 basis points.")]
+    InvalidApprovalThreshold,
+    #[msg("Quorum must be at most 10000 basis points.")]
+    InvalidQuorumBps,
+    #[msg("Refund threshold must be at most 10000 basis points.")]
+    InvalidRefundThreshold,
+    #[msg("The proposal has already received votes.")]
+    ProposalHasVotes,
+    #[msg("The proposal is not finished, or its deposit or rewards are pending.")]
+    ProposalNotClosable,
+    #[msg("Voting is not over, or the reward of the vote is pending.")]
+    VoteNotClosable,
+    #[msg("The claim window of the voting reward is over.")]
+    RewardClaimWindowOver,
+    #[msg("The proposal has the maximum number of rewards.")]
+    TooManyProposalRewards,
+    #[msg("Invalid reward policy.")]
+    InvalidRewardPolicy,
+    #[msg("The proposal policy denies an instruction of the proposal.")]
+    ProposalInstructionDenied,
+    #[msg("The proposal policy does not allow an instruction of the proposal.")]
+    ProposalInstructionNotAllowed,
+    #[msg("Invalid proposal class name.")]
+    InvalidProposalClassName,
+    #[msg("The instructions of the proposal are not sealed.")]
+    ProposalNotSealed,
+    #[msg("The instructions of the proposal are already sealed.")]
+    ProposalSealed,
+    #[msg("Votes on the proposal can no longer be changed.")]
+    VoteLocked,
+    #[msg("Invalid signed vote.")]
+    InvalidSignedVote,
+    #[msg("An instruction of the proposal is not one of its proposal class.")]
+    ProposalInstructionNotInClass,
+    #[msg("Only draft or active proposals can be canceled.")]
+    ProposalNotCancelable,
 }
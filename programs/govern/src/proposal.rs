@@ -1,11 +1,13 @@
 //! Proposal logic.
 use crate::ErrorCode::InvalidVoteSide;
 use crate::*;
+use anchor_lang::solana_program::hash::hash;
+use smart_wallet::TXLookupInstruction;
 
 /// The state of a proposal.
 ///
-/// The `expired` state from Compound is missing here, because the
-/// Smart Wallet handles execution.
+/// The Smart Wallet handles execution; [ProposalState::Executed] and [ProposalState::Expired]
+/// are recorded on the proposal by [govern::sync_proposal].
 #[derive(Debug, Eq, PartialEq)]
 #[repr(C)]
 pub enum ProposalState {
@@ -17,18 +19,29 @@ pub enum ProposalState {
     /// is to require the user to have a minimum amount of tokens.
     /// An [ProposalState::Active] proposal is one that is surfaced to the community to put up for voting.
     Active,
-    /// If a proposal is still a [ProposalState::Draft], a proposal may be canceled by its creator.
-    /// A canceled proposal cannot be reactivated; it simply just exists as a record.
+    /// A [ProposalState::Draft] proposal, or an [ProposalState::Active] one without votes, may be canceled
+    /// by its creator, and the [Governor::smart_wallet] may cancel any proposal which is not queued.
+    /// A canceled proposal cannot be reactivated; it is closed when canceled.
     Canceled,
     /// After the voting period ends, votes are tallied up. A proposal is [ProposalState::Defeated] if one of
-    /// two scenarios happen:
+    /// these scenarios happen:
     /// - More or equal votes are [VoteSide::Against] than [VoteSide::For].
     /// - The sum of all votes does not meet quorum.
+    /// - No option has won the tally of a multi-option proposal.
+    /// - The winning side does not reach the approval threshold.
     Defeated,
     /// A proposal is [ProposalState::Succeeded] if it is not defeated and voting is over.
     Succeeded,
     /// A succeeded proposal may be [ProposalState::Queued] into the [SmartWallet].
     Queued,
+    /// The queued transaction of the proposal has been executed by the [SmartWallet].
+    Executed,
+    /// The queued transaction of the proposal was not executed before the grace period
+    /// of the [SmartWallet] ended.
+    Expired,
+    /// The proposal was vetoed by the [Governor::veto_authority] during voting or the timelock.
+    /// A vetoed proposal can never be queued or executed.
+    Vetoed,
 }
 
 impl Default for ProposalState {
@@ -38,6 +51,32 @@ impl Default for ProposalState {
 }
 
 impl Proposal {
+    /// Snapshots the [GovernanceParameters] the proposal is decided with.
+    pub(crate) fn set_params(
+        &mut self,
+        proposal_params: &mut ProposalParams,
+        params: &GovernanceParameters,
+    ) {
+        proposal_params.voting_delay = params.voting_delay;
+        proposal_params.voting_period = params.voting_period;
+        self.quorum_votes = params.quorum_votes;
+        proposal_params.timelock_delay_seconds = params.timelock_delay_seconds;
+        self.approval_threshold_bps = params.approval_threshold_bps;
+        self.quorum_bps = params.quorum_bps;
+    }
+
+    /// The [GovernanceParameters] the proposal is decided with.
+    pub fn params(&self, proposal_params: &ProposalParams) -> GovernanceParameters {
+        GovernanceParameters {
+            voting_delay: proposal_params.voting_delay,
+            voting_period: proposal_params.voting_period,
+            quorum_votes: self.quorum_votes,
+            timelock_delay_seconds: proposal_params.timelock_delay_seconds,
+            approval_threshold_bps: self.approval_threshold_bps,
+            quorum_bps: self.quorum_bps,
+        }
+    }
+
     // /// Subtracts from the total weight of a vote for a [Proposal].
     pub(crate) fn subtract_vote_weight(&mut self, side: u8, voting_power: u64) -> Result<()> {
         if voting_power == 0 {
@@ -64,6 +103,151 @@ impl Proposal {
         Ok(())
     }
 
+    /// Removes the weight of a [Vote] from the tally.
+    pub(crate) fn subtract_ballot(&mut self, vote: &Vote) -> Result<()> {
+        if vote.voting_power > 0 {
+            self.voter_count = unwrap_int!(self.voter_count.checked_sub(1));
+            self.sqrt_voting_power =
+                unwrap_int!(self.sqrt_voting_power.checked_sub(sqrt(vote.voting_power)));
+        }
+        if self.proposal_type == u8::from(ProposalType::Approval) && vote.choices[0] != 0 {
+            for option in vote.choices() {
+                self.subtract_vote_weight(option, vote.voting_power)?;
+            }
+            self.approval_votes = unwrap_int!(self.approval_votes.checked_sub(vote.voting_power));
+            return Ok(());
+        }
+        // ranked-choice votes only count towards the option currently holding their weight
+        self.subtract_vote_weight(vote.side, vote.voting_power)
+    }
+
+    /// Adds the weight of a [Vote] to the tally.
+    pub(crate) fn add_ballot(&mut self, vote: &Vote) -> Result<()> {
+        if vote.voting_power > 0 {
+            self.voter_count = unwrap_int!(self.voter_count.checked_add(1));
+            self.sqrt_voting_power =
+                unwrap_int!(self.sqrt_voting_power.checked_add(sqrt(vote.voting_power)));
+        }
+        if self.proposal_type == u8::from(ProposalType::Approval) && vote.choices[0] != 0 {
+            for option in vote.choices() {
+                self.add_vote_weight(option, vote.voting_power)?;
+            }
+            self.approval_votes = unwrap_int!(self.approval_votes.checked_add(vote.voting_power));
+            return Ok(());
+        }
+        self.add_vote_weight(vote.side, vote.voting_power)
+    }
+
+    /// Validates the choices of an [ProposalType::Approval] or [ProposalType::RankedChoice] vote
+    /// and pads them into the layout of [Vote::choices].
+    /// An empty list of choices is an abstain vote.
+    pub(crate) fn to_vote_choices(&self, choices: &[u8]) -> Result<[u8; MAX_OPTION as usize]> {
+        let proposal_type = ProposalType::try_from(self.proposal_type)?;
+        invariant!(proposal_type.has_vote_choices(), InvalidProposalType);
+        invariant!(
+            choices.len() <= self.max_option as usize,
+            InvalidVoteChoices
+        );
+
+        let mut vote_choices = [0u8; MAX_OPTION as usize];
+        for (i, &option) in choices.iter().enumerate() {
+            invariant!(
+                option != 0 && option <= self.max_option && !choices[..i].contains(&option),
+                InvalidVoteChoices
+            );
+            vote_choices[i] = option;
+        }
+        Ok(vote_choices)
+    }
+
+    /// Returns true if the option has been eliminated by an instant-runoff round.
+    pub fn is_eliminated(&self, option: u8) -> bool {
+        self.eliminated_options & (1 << option) != 0
+    }
+
+    /// Moves the weight of a [ProposalType::RankedChoice] vote from an eliminated option
+    /// to its next preferred option which is still running.
+    /// If every option of the vote is eliminated, the vote is exhausted and counted as abstain.
+    pub(crate) fn transfer_ranked_vote(&mut self, vote: &mut Vote) -> Result<()> {
+        if vote.side == ABSTAIN_VOTE_INDEX as u8 || !self.is_eliminated(vote.side) {
+            return Ok(());
+        }
+        let next_side = vote
+            .choices()
+            .find(|&option| !self.is_eliminated(option))
+            .unwrap_or(ABSTAIN_VOTE_INDEX as u8);
+
+        self.subtract_vote_weight(vote.side, vote.voting_power)?;
+        self.add_vote_weight(next_side, vote.voting_power)?;
+        vote.side = next_side;
+        Ok(())
+    }
+
+    /// The option with the most votes; ties are won by the lower option.
+    /// Returns 0 if no option has any votes.
+    pub fn plurality_winner(&self) -> u8 {
+        let mut winner = 0;
+        let mut winner_votes = 0;
+        for option in 1..=self.max_option {
+            let votes = self.option_votes[option as usize];
+            if votes > winner_votes && !self.is_eliminated(option) {
+                winner = option;
+                winner_votes = votes;
+            }
+        }
+        winner
+    }
+
+    /// Tallies a [ProposalType::Option] or [ProposalType::Approval] proposal.
+    pub fn tally_plurality(&mut self) -> Option<u8> {
+        self.winning_option = self.plurality_winner();
+        Some(self.winning_option)
+    }
+
+    /// Runs instant-runoff rounds on a [ProposalType::RankedChoice] proposal.
+    ///
+    /// Each round, if no running option holds a majority of the votes of all running options,
+    /// the option with the fewest votes is eliminated (ties eliminate the higher option).
+    /// Votes of an eliminated option must then be moved with [Proposal::transfer_ranked_vote]
+    /// before the next round can run.
+    ///
+    /// Returns the winning option once decided, or None while votes are waiting to be transferred.
+    pub fn tally_ranked_choice(&mut self) -> Option<u8> {
+        loop {
+            let mut running_votes: u64 = 0;
+            let mut running_options = 0;
+            let mut loser = 0;
+            for option in 1..=self.max_option {
+                let votes = self.option_votes[option as usize];
+                if self.is_eliminated(option) {
+                    if votes > 0 {
+                        // votes of the eliminated option have not been transferred yet
+                        return None;
+                    }
+                    continue;
+                }
+                running_votes = running_votes.checked_add(votes)?;
+                running_options += 1;
+                if loser == 0 || votes <= self.option_votes[loser as usize] {
+                    loser = option;
+                }
+            }
+
+            let leader = self.plurality_winner();
+            if leader == 0 {
+                // every vote is exhausted or abstains
+                self.winning_option = 0;
+                return Some(0);
+            }
+            let leader_votes = self.option_votes[leader as usize] as u128;
+            if leader_votes * 2 > running_votes as u128 || running_options == 1 {
+                self.winning_option = leader;
+                return Some(leader);
+            }
+            self.eliminated_options |= 1 << loser;
+        }
+    }
+
     // Gets the state.
     pub fn get_state(&self) -> Result<ProposalState> {
         Ok(unwrap_opt!(
@@ -74,12 +258,168 @@ impl Proposal {
 
     /// total votes
     pub fn total_votes(&self) -> Option<u64> {
+        if self.proposal_type == u8::from(ProposalType::Approval) {
+            return self.option_votes[ABSTAIN_VOTE_INDEX].checked_add(self.approval_votes);
+        }
         let total_vote = self.option_votes.iter().sum();
         Some(total_vote)
     }
 
     /// Checks if the proposal meets quorum; that is,
     /// enough votes were made on the proposal.
+    /// Quorum as [Proposal::quorum_bps] of the total voting power of the locker.
+    pub fn quorum_from_voting_power(&self, total_voting_power: u64) -> Option<u64> {
+        (total_voting_power as u128)
+            .checked_mul(self.quorum_bps.into())?
+            .checked_div(MAX_BPS.into())?
+            .to_u64()
+    }
+
+    /// The side currently winning the vote: [FOR_VOTE_INDEX] or [AGAINST_VOTE_INDEX] on a
+    /// [ProposalType::YesNo] proposal, else the [Proposal::plurality_winner].
+    pub fn leading_side(&self) -> u8 {
+        if self.proposal_type == u8::from(ProposalType::YesNo) {
+            if self.option_votes[FOR_VOTE_INDEX] > self.option_votes[AGAINST_VOTE_INDEX] {
+                return FOR_VOTE_INDEX as u8;
+            }
+            return AGAINST_VOTE_INDEX as u8;
+        }
+        self.plurality_winner()
+    }
+
+    /// Extends voting to [Proposal::late_quorum_extension] seconds from now, if voting ends
+    /// sooner than that, using up to [Proposal::remaining_voting_extension].
+    /// Returns true if [Proposal::voting_ends_at] was pushed back.
+    pub(crate) fn extend_voting(&mut self, now: i64) -> Option<bool> {
+        if self.late_quorum_extension == 0 {
+            return Some(false);
+        }
+        let voting_ends_at = now.checked_add(self.late_quorum_extension.into())?.min(
+            self.voting_ends_at
+                .checked_add(self.remaining_voting_extension.into())?,
+        );
+        if voting_ends_at <= self.voting_ends_at {
+            return Some(false);
+        }
+        let extension = u32::try_from(voting_ends_at.checked_sub(self.voting_ends_at)?).ok()?;
+        self.remaining_voting_extension = self.remaining_voting_extension.checked_sub(extension)?;
+        self.voting_ends_at = voting_ends_at;
+        Some(true)
+    }
+
+    /// Returns true if votes already cast on the proposal can no longer be changed.
+    pub fn is_vote_locked(&self, now: i64) -> bool {
+        self.votes_final
+            || (self.vote_lock_period > 0
+                && now
+                    >= self
+                        .voting_ends_at
+                        .saturating_sub(self.vote_lock_period.into()))
+    }
+
+    /// When voting rewards can no longer be claimed, or [None] if they may be claimed forever.
+    pub fn reward_claim_ends_at(&self) -> Option<i64> {
+        if self.reward_claim_period == 0 {
+            return None;
+        }
+        Some(self.voting_ends_at.saturating_add(self.reward_claim_period))
+    }
+
+    /// Returns true if no voting reward of the proposal in the given state can be claimed anymore.
+    pub fn is_reward_window_over(&self, state: &ProposalState, now: i64) -> Option<bool> {
+        if self.voting_reward.reward_per_proposal == 0 && self.reward_count == 0 {
+            return Some(true);
+        }
+        match state {
+            ProposalState::Draft | ProposalState::Active => Some(false),
+            ProposalState::Canceled | ProposalState::Vetoed => Some(true),
+            _ => {
+                if !self.meets_quorum()? {
+                    return Some(true);
+                }
+                Some(matches!(self.reward_claim_ends_at(), Some(ends_at) if now > ends_at))
+            }
+        }
+    }
+
+    /// Voting reward which has neither been claimed nor swept back to the treasury.
+    pub fn unswept_reward(&self) -> Option<u64> {
+        if self.reward_swept_at > 0 {
+            return Some(0);
+        }
+        self.voting_reward
+            .reward_per_proposal
+            .checked_sub(self.total_claimed_reward)
+    }
+
+    /// Returns true if the proposal is finished and may be closed: it is not awaiting a
+    /// decision or execution, its deposit is settled, its rewards can no longer be claimed
+    /// and its [ProposalReward]s refunded. The unswept voting reward is swept on close.
+    pub fn is_closable(&self, now: i64) -> Option<bool> {
+        let state = self.state(now)?;
+        let finished = matches!(
+            state,
+            ProposalState::Canceled
+                | ProposalState::Defeated
+                | ProposalState::Vetoed
+                | ProposalState::Queued
+                | ProposalState::Executed
+                | ProposalState::Expired
+        );
+        Some(
+            finished
+                && !self.has_unsettled_deposit()
+                && self.is_reward_window_over(&state, now)?
+                && !self.has_unrefunded_rewards(),
+        )
+    }
+
+    /// Returns true if some [ProposalReward]s of the proposal have not been refunded to their sponsors.
+    pub fn has_unrefunded_rewards(&self) -> bool {
+        self.refunded_reward_count < self.reward_count
+    }
+
+    /// Returns true if a [Vote] on the proposal may be closed:
+    /// voting is over, and its reward has been claimed or can no longer be claimed.
+    pub fn is_vote_closable(&self, vote: &Vote, now: i64) -> Option<bool> {
+        let state = self.state(now)?;
+        if state == ProposalState::Draft || state == ProposalState::Active {
+            return Some(false);
+        }
+        Some(
+            vote.has_claimed_all_rewards(self.reward_count)
+                || self.is_reward_window_over(&state, now)?,
+        )
+    }
+
+    /// Receiver of the rent of the proposal: its [Proposal::payer], or its
+    /// [Proposal::proposer] if it was created before the payer was recorded.
+    pub fn rent_payer(&self) -> Pubkey {
+        if self.payer == Pubkey::default() {
+            self.proposer
+        } else {
+            self.payer
+        }
+    }
+
+    /// Returns true if the proposal holds a deposit which has not been refunded or slashed.
+    pub fn has_unsettled_deposit(&self) -> bool {
+        self.deposit_amount > 0 && self.deposit_settled_at == 0
+    }
+
+    /// Returns true if the deposit of a proposal in the given state is refunded to the proposer.
+    /// Deposits of canceled proposals, or of proposals whose votes did not reach
+    /// [Proposal::deposit_refund_threshold_bps] of the quorum, are slashed.
+    pub fn is_deposit_refundable(&self, state: &ProposalState) -> Option<bool> {
+        if *state == ProposalState::Canceled {
+            return Some(false);
+        }
+        let votes = (self.total_votes()? as u128).checked_mul(MAX_BPS.into())?;
+        let threshold =
+            (self.quorum_votes as u128).checked_mul(self.deposit_refund_threshold_bps.into())?;
+        Some(votes >= threshold)
+    }
+
     pub fn meets_quorum(&self) -> Option<bool> {
         Some(self.total_votes()? >= self.quorum_votes)
     }
@@ -89,12 +429,18 @@ impl Proposal {
     pub fn state(&self, current_time: i64) -> Option<ProposalState> {
         if self.canceled_at > 0 {
             return Some(ProposalState::Canceled);
+        } else if self.vetoed_at > 0 {
+            return Some(ProposalState::Vetoed);
         } else if self.activated_at == 0 {
             return Some(ProposalState::Draft);
         } else if current_time < self.voting_ends_at {
             return Some(ProposalState::Active);
         } else if !self.meets_quorum()? {
             return Some(ProposalState::Defeated);
+        } else if self.executed_at > 0 {
+            return Some(ProposalState::Executed);
+        } else if self.expired_at > 0 {
+            return Some(ProposalState::Expired);
         } else if self.queued_at > 0 {
             return Some(ProposalState::Queued);
         }
@@ -103,13 +449,80 @@ impl Proposal {
             if self.option_votes[FOR_VOTE_INDEX] <= self.option_votes[AGAINST_VOTE_INDEX] {
                 return Some(ProposalState::Defeated);
             }
+        } else if self.tallied_at > 0 && self.winning_option == 0 {
+            // no option has won the tally
+            return Some(ProposalState::Defeated);
+        }
+        if !self.meets_approval_threshold()? {
+            return Some(ProposalState::Defeated);
         }
         Some(ProposalState::Succeeded)
     }
 
+    /// Returns true if the winning side holds at least [Proposal::approval_threshold_bps]
+    /// of the votes which are not abstentions.
+    /// Multi-option proposals are only checked once tallied.
+    pub fn meets_approval_threshold(&self) -> Option<bool> {
+        if self.approval_threshold_bps == 0 {
+            return Some(true);
+        }
+        let (winning_votes, decided_votes) = if self.proposal_type == u8::from(ProposalType::YesNo)
+        {
+            let for_votes = self.option_votes[FOR_VOTE_INDEX];
+            (
+                for_votes,
+                for_votes.checked_add(self.option_votes[AGAINST_VOTE_INDEX])?,
+            )
+        } else {
+            if self.tallied_at == 0 || self.winning_option == 0 {
+                return Some(true);
+            }
+            let decided_votes = if self.proposal_type == u8::from(ProposalType::Approval) {
+                self.approval_votes
+            } else {
+                self.total_votes()?
+                    .checked_sub(self.option_votes[ABSTAIN_VOTE_INDEX])?
+            };
+            (
+                self.option_votes[self.winning_option as usize],
+                decided_votes,
+            )
+        };
+
+        let winning_votes = (winning_votes as u128).checked_mul(MAX_BPS.into())?;
+        let required_votes =
+            (decided_votes as u128).checked_mul(self.approval_threshold_bps.into())?;
+        Some(winning_votes >= required_votes)
+    }
+
+    /// Returns true if the option has an [OptionInstructions] bundle.
+    pub fn has_option_instructions(&self, option: u8) -> bool {
+        self.option_instruction_sets & (1 << option) != 0
+    }
+
     /// Converts this proposal to Smart Wallet [smart_wallet::TXInstruction]s.
-    pub fn to_smart_wallet_instructions(&self) -> Vec<smart_wallet::TXInstruction> {
-        self.instructions
+    ///
+    /// For multi-option proposals, these are the instructions of the winning option,
+    /// which must be passed in if the option has a bundle.
+    /// A winning option without a bundle is a no-op.
+    pub fn to_smart_wallet_instructions(
+        &self,
+        option_instructions: Option<&OptionInstructions>,
+    ) -> Vec<smart_wallet::TXInstruction> {
+        let instructions = if self.proposal_type == u8::from(ProposalType::YesNo) {
+            &self.instructions[..]
+        } else {
+            match option_instructions {
+                Some(bundle)
+                    if self.has_option_instructions(self.winning_option)
+                        && bundle.option == self.winning_option =>
+                {
+                    &bundle.instructions[..]
+                }
+                _ => &[],
+            }
+        };
+        instructions
             .iter()
             .map(
                 |ProposalInstruction {
@@ -138,3 +551,71 @@ impl Proposal {
             .collect()
     }
 }
+
+impl ProposalParams {
+    /// Returns true if the [Proposal::instructions] are sealed and may no longer change.
+    pub fn is_sealed(&self) -> bool {
+        self.sealed_at > 0
+    }
+
+    /// Seals the [Proposal::instructions] and [ProposalParams::lookup_instructions],
+    /// storing their hash.
+    pub(crate) fn seal_instructions(&mut self, proposal: &Proposal, now: i64) -> Result<()> {
+        let mut instructions = proposal.instructions.try_to_vec()?;
+        if !self.lookup_instructions.is_empty() {
+            instructions.extend(self.lookup_instructions.try_to_vec()?);
+        }
+        self.instructions_hash = hash(&instructions).to_bytes();
+        self.sealed_at = now;
+        Ok(())
+    }
+
+    /// Returns true if the appended `instructions` are kept in [ProposalParams::lookup_instructions]
+    /// rather than resolved into [Proposal::instructions]: once an instruction references
+    /// a lookup table, it and all instructions appended after it are, to keep their order.
+    pub fn keeps_lookup_instructions(&self, instructions: &[TXLookupInstruction]) -> bool {
+        !self.lookup_instructions.is_empty() || instructions.iter().any(|ix| !ix.lookups.is_empty())
+    }
+
+    /// Space that the `instructions` take up in [ProposalParams::lookup_instructions].
+    pub fn lookup_instructions_space(instructions: &[TXLookupInstruction]) -> usize {
+        instructions
+            .iter()
+            .map(|ix| {
+                std::mem::size_of::<Pubkey>()
+                    + 4 // keys vector length
+                    + ix.keys.len() * std::mem::size_of::<smart_wallet::TXAccountMeta>()
+                    + 4 // data vector length
+                    + ix.data.len()
+                    + 4 // lookups vector length
+                    + ix.lookups.iter().map(|lookup| lookup.space()).sum::<usize>()
+            })
+            .sum()
+    }
+
+    /// Resolves the [ProposalParams::lookup_instructions] from the lookup tables among the `accounts`.
+    pub fn resolve_lookup_instructions(
+        &self,
+        accounts: &[AccountInfo],
+    ) -> Result<Vec<smart_wallet::TXInstruction>> {
+        self.lookup_instructions
+            .iter()
+            .map(|ix| ix.resolve(accounts))
+            .collect()
+    }
+}
+
+/// Integer square root, rounded down.
+pub(crate) fn sqrt(value: u64) -> u64 {
+    if value < 2 {
+        return value;
+    }
+    // Newton's method, starting above the root
+    let mut x = value;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
@@ -0,0 +1,105 @@
+//! Votes signed off-chain and settled in batches by the voter programs.
+use crate::*;
+use anchor_lang::solana_program::{ed25519_program, sysvar::instructions};
+
+/// Size of the offsets of each signature in an ed25519 program instruction.
+const ED25519_OFFSETS_SIZE: usize = 14;
+/// Start of the offsets in an ed25519 program instruction, after the signature count and padding.
+const ED25519_OFFSETS_START: usize = 2;
+/// Instruction index referring to the ed25519 program instruction itself.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// The message a vote delegate signs off-chain to vote on a [Proposal]:
+/// [SIGNED_VOTE_DOMAIN], the proposal key, the side and the little-endian nonce.
+pub fn signed_vote_message(proposal: &Pubkey, side: u8, nonce: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(SIGNED_VOTE_DOMAIN.len() + 32 + 1 + 8);
+    message.extend_from_slice(SIGNED_VOTE_DOMAIN);
+    message.extend_from_slice(proposal.as_ref());
+    message.push(side);
+    message.extend_from_slice(&nonce.to_le_bytes());
+    message
+}
+
+/// A message whose signature was checked by the ed25519 program.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct VerifiedSignature {
+    /// The signer.
+    pub pubkey: Pubkey,
+    /// The signed message.
+    pub message: Vec<u8>,
+}
+
+/// Loads the signatures checked by the ed25519 program instructions of the current transaction.
+/// The runtime fails the transaction if any of them is invalid.
+pub fn load_verified_signatures(
+    instructions_sysvar: &AccountInfo,
+) -> Result<Vec<VerifiedSignature>> {
+    let instruction_count = {
+        let data = instructions_sysvar.try_borrow_data()?;
+        invariant!(data.len() >= 2, InvalidSignedVote);
+        u16::from_le_bytes([data[0], data[1]])
+    };
+
+    let mut signatures = vec![];
+    for index in 0..instruction_count {
+        let ix = instructions::load_instruction_at_checked(index.into(), instructions_sysvar)?;
+        if ix.program_id == ed25519_program::ID {
+            signatures.extend(parse_ed25519_instruction(&ix.data)?);
+        }
+    }
+    Ok(signatures)
+}
+
+/// Parses the signatures of an ed25519 program instruction.
+/// Signatures must keep their public key and message in the instruction itself,
+/// so that the data read here is the data which was verified.
+pub fn parse_ed25519_instruction(data: &[u8]) -> Result<Vec<VerifiedSignature>> {
+    let read_u16 = |offset: usize| -> Result<u16> {
+        let bytes = unwrap_opt!(data.get(offset..offset + 2), InvalidSignedVote);
+        Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+    };
+    let read_slice = |offset: u16, size: usize| -> Result<&[u8]> {
+        let offset = offset as usize;
+        Ok(unwrap_opt!(
+            data.get(offset..offset + size),
+            InvalidSignedVote
+        ))
+    };
+
+    let count = *unwrap_opt!(data.first(), InvalidSignedVote) as usize;
+    let mut signatures = Vec::with_capacity(count);
+    for i in 0..count {
+        let start = ED25519_OFFSETS_START + i * ED25519_OFFSETS_SIZE;
+        let signature_instruction_index = read_u16(start + 2)?;
+        let public_key_offset = read_u16(start + 4)?;
+        let public_key_instruction_index = read_u16(start + 6)?;
+        let message_data_offset = read_u16(start + 8)?;
+        let message_data_size = read_u16(start + 10)?;
+        let message_instruction_index = read_u16(start + 12)?;
+        invariant!(
+            signature_instruction_index == CURRENT_INSTRUCTION
+                && public_key_instruction_index == CURRENT_INSTRUCTION
+                && message_instruction_index == CURRENT_INSTRUCTION,
+            InvalidSignedVote
+        );
+
+        let pubkey = Pubkey::try_from(read_slice(public_key_offset, 32)?).unwrap();
+        let message = read_slice(message_data_offset, message_data_size.into())?.to_vec();
+        signatures.push(VerifiedSignature { pubkey, message });
+    }
+    Ok(signatures)
+}
+
+/// Returns true if `signer` signed the vote in one of the `signatures`.
+pub fn has_signed_vote(
+    signatures: &[VerifiedSignature],
+    signer: &Pubkey,
+    proposal: &Pubkey,
+    side: u8,
+    nonce: u64,
+) -> bool {
+    let message = signed_vote_message(proposal, side, nonce);
+    signatures
+        .iter()
+        .any(|signature| signature.pubkey == *signer && signature.message == message)
+}
@@ -1,7 +1,12 @@
 //! Struct definitions for accounts that hold state.
 
+use crate::{
+    sqrt, ABSTAIN_VOTE_INDEX, MAX_BPS, MAX_OPTION, MAX_PROPOSAL_CLASS_NAME_LEN,
+    MAX_PROPOSAL_REWARDS,
+};
 use anchor_lang::prelude::*;
-use vipers::program_err;
+use smart_wallet::TXLookupInstruction;
+use vipers::{invariant, program_err};
 /// A Governor is the "DAO": it is the account that holds control over important protocol functions,
 /// including treasury, protocol parameters, and more.
 #[account]
@@ -23,23 +28,115 @@ pub struct Governor {
     /// This smart wallet executes proposals.
     pub smart_wallet: Pubkey,
 
-    /// Governance parameters.
-    pub params: GovernanceParameters,
+    /// Governance parameters. See [Governor::governance_params].
+    pub params: GovernorParameters,
 
     /// optional reward, can set by smartwallet
     pub voting_reward: VotingReward,
 
+    /// Optional authority, e.g. a security council [smart_wallet::SmartWallet],
+    /// which may veto proposals. [Pubkey::default] if there is none.
+    pub veto_authority: Pubkey,
+
+    /// optional deposit escrowed by proposers, can set by smartwallet
+    pub proposal_deposit: ProposalDeposit,
+
+    /// Seconds after voting ends during which voting rewards may be claimed.
+    /// Afterwards, the unclaimed reward may be swept back to the treasury.
+    /// 0 if rewards may be claimed forever.
+    pub reward_claim_period: i64,
+
+    /// The [ProposalPolicy] restricting the instructions of proposals.
+    /// [Pubkey::default] if proposals may call any instruction.
+    pub proposal_policy: Pubkey,
+
+    /// Seconds before voting ends during which votes already cast cannot be changed.
+    /// 0 if votes may be changed until voting ends.
+    pub vote_lock_period: i64,
+    /// If true, a vote cannot be changed once cast.
+    pub votes_final: bool,
+    /// padding for future use
+    pub vote_lock_padding: [u8; 7],
+
+    /// Seconds before voting ends during which quorum being first reached, or the
+    /// leading side changing, extends voting to this many seconds from the vote.
+    /// 0 if voting is never extended.
+    pub late_quorum_extension: i64,
+    /// Maximum number of seconds voting may be extended by in total.
+    pub max_voting_extension: i64,
+
+    /// The [GovernanceParameters::approval_threshold_bps] of the governor.
+    pub approval_threshold_bps: u16,
+    /// The [GovernanceParameters::quorum_bps] of the governor.
+    pub quorum_bps: u16,
+    /// How the [Governor::voting_reward] is shared among voters. See [RewardPolicy].
+    pub reward_policy: u8,
+    /// padding for future use
+    pub params_padding: [u8; 9],
+
     /// buffer for further use
-    pub buffers: [u128; 32],
+    pub buffers: [u128; 22],
 }
 impl Governor {
     /// LEN of Governor
-    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 3
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 5
+        + 1
+        + 8
+        + 8
+        + 8
         + 1
+        + 7
+        + 8
         + 8
-        + 16 * 32
-        + std::mem::size_of::<GovernanceParameters>()
-        + std::mem::size_of::<VotingReward>();
+        + 2
+        + 2
+        + 1
+        + 9
+        + 16 * 22
+        + std::mem::size_of::<GovernorParameters>()
+        + std::mem::size_of::<VotingReward>()
+        + ProposalDeposit::LEN;
+
+    /// The [GovernanceParameters] proposals are decided with, unless they have a [ProposalClass].
+    pub fn governance_params(&self) -> GovernanceParameters {
+        GovernanceParameters {
+            voting_delay: self.params.voting_delay,
+            voting_period: self.params.voting_period,
+            quorum_votes: self.params.quorum_votes,
+            timelock_delay_seconds: self.params.timelock_delay_seconds,
+            approval_threshold_bps: self.approval_threshold_bps,
+            quorum_bps: self.quorum_bps,
+        }
+    }
+
+    /// Sets the [GovernanceParameters] of the governor.
+    pub(crate) fn set_governance_params(&mut self, params: &GovernanceParameters) {
+        self.params = GovernorParameters {
+            voting_delay: params.voting_delay,
+            voting_period: params.voting_period,
+            quorum_votes: params.quorum_votes,
+            timelock_delay_seconds: params.timelock_delay_seconds,
+        };
+        self.approval_threshold_bps = params.approval_threshold_bps;
+        self.quorum_bps = params.quorum_bps;
+    }
+}
+
+/// Deposit escrowed when a [Proposal] is created, to protect against spam.
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ProposalDeposit {
+    /// Deposit mint
+    pub deposit_mint: Pubkey,
+    /// Amount escrowed per proposal. 0 if no deposit is required.
+    pub deposit_amount: u64,
+    /// Share of the quorum, in basis points, the votes on a proposal must reach for the deposit
+    /// to be refunded. Otherwise, or if the proposal is canceled, the deposit is slashed to the treasury.
+    pub refund_threshold_bps: u16,
+}
+
+impl ProposalDeposit {
+    /// LEN of ProposalDeposit
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() + 8 + 2;
 }
 
 /// Governance parameters.
@@ -53,6 +150,54 @@ pub struct VotingReward {
     pub reward_per_proposal: u64,
 }
 
+/// How a voting reward is shared among the voters of a [Proposal].
+#[derive(Debug, Eq, PartialEq)]
+#[repr(u8)]
+pub enum RewardPolicy {
+    /// Pro rata by voting power
+    ProRata = 0,
+    /// Pro rata by voting power, among votes which do not abstain
+    ExcludeAbstain = 1,
+    /// The same amount for each voter
+    Flat = 2,
+    /// Pro rata by the square root of voting power, which reduces whale capture
+    SquareRoot = 3,
+}
+
+impl From<RewardPolicy> for u8 {
+    fn from(reward_policy: RewardPolicy) -> Self {
+        reward_policy as u8
+    }
+}
+
+impl TryFrom<u8> for RewardPolicy {
+    type Error = Error;
+
+    fn try_from(value: u8) -> Result<Self> {
+        match value {
+            0 => Ok(RewardPolicy::ProRata),
+            1 => Ok(RewardPolicy::ExcludeAbstain),
+            2 => Ok(RewardPolicy::Flat),
+            3 => Ok(RewardPolicy::SquareRoot),
+            _ => program_err!(InvalidRewardPolicy),
+        }
+    }
+}
+
+/// The [GovernanceParameters] stored in [Governor::params], in the original layout of the account.
+/// Newer parameters are stored at the end of the [Governor].
+#[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct GovernorParameters {
+    /// The delay before voting on a proposal may take place, once proposed, in seconds
+    pub voting_delay: u64,
+    /// The duration of voting on a proposal, in seconds
+    pub voting_period: u64,
+    /// The number of votes in support of a proposal required in order for a quorum to be reached and for a vote to succeed
+    pub quorum_votes: u64,
+    /// The timelock delay of the DAO's created proposals.
+    pub timelock_delay_seconds: i64,
+}
+
 /// Governance parameters.
 #[derive(AnchorSerialize, AnchorDeserialize, Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct GovernanceParameters {
@@ -64,6 +209,189 @@ pub struct GovernanceParameters {
     pub quorum_votes: u64,
     /// The timelock delay of the DAO's created proposals.
     pub timelock_delay_seconds: i64,
+    /// The share of votes, in basis points, the winning side of a proposal must reach to succeed,
+    /// e.g. 6_600 for a two-thirds supermajority. 0 keeps a simple majority.
+    pub approval_threshold_bps: u16,
+    /// If set, the quorum is this share, in basis points, of the total voting power of the locker
+    /// when the proposal is activated, instead of [GovernanceParameters::quorum_votes].
+    pub quorum_bps: u16,
+}
+
+impl GovernanceParameters {
+    /// Checks that the parameters are within bounds.
+    pub fn validate(&self) -> Result<()> {
+        if self.approval_threshold_bps > MAX_BPS {
+            return program_err!(InvalidApprovalThreshold);
+        }
+        if self.quorum_bps > MAX_BPS {
+            return program_err!(InvalidQuorumBps);
+        }
+        Ok(())
+    }
+
+    /// The strictest of both sets of parameters, field by field.
+    pub fn strictest(&self, other: &GovernanceParameters) -> GovernanceParameters {
+        GovernanceParameters {
+            voting_delay: self.voting_delay.max(other.voting_delay),
+            voting_period: self.voting_period.max(other.voting_period),
+            quorum_votes: self.quorum_votes.max(other.quorum_votes),
+            timelock_delay_seconds: self
+                .timelock_delay_seconds
+                .max(other.timelock_delay_seconds),
+            approval_threshold_bps: self
+                .approval_threshold_bps
+                .max(other.approval_threshold_bps),
+            quorum_bps: self.quorum_bps.max(other.quorum_bps),
+        }
+    }
+}
+
+/// A named class of [Proposal]s, e.g. treasury spend, parameter change or emergency,
+/// decided with its own [GovernanceParameters] instead of [Governor::params].
+/// Proposals of the class may only call the instructions matching its filters.
+/// The [ProposalPolicy] still applies on top of the class.
+#[account]
+#[derive(Debug, Default)]
+pub struct ProposalClass {
+    /// The [Governor].
+    pub governor: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+    /// Name of the class, also a seed of the account.
+    pub name: String,
+    /// Parameters of the proposals of the class.
+    pub params: GovernanceParameters,
+    /// buffers for future use
+    pub buffers: [u128; 4],
+    /// The instructions proposals of the class may call.
+    /// Proposals of a class without filters cannot call any instruction.
+    pub filters: Vec<InstructionFilter>,
+}
+
+impl ProposalClass {
+    /// Space that the [ProposalClass] takes up.
+    pub fn space(filters: &[InstructionFilter]) -> usize {
+        std::mem::size_of::<Pubkey>()
+            + 1
+            + 4
+            + MAX_PROPOSAL_CLASS_NAME_LEN
+            + std::mem::size_of::<GovernanceParameters>()
+            + 16 * 4
+            + 4 // Vec discriminator
+            + filters.iter().map(|filter| filter.space()).sum::<usize>()
+    }
+
+    /// Checks that every instruction matches a filter of the class.
+    pub fn check_instructions(&self, instructions: &[ProposalInstruction]) -> Result<()> {
+        for ix in instructions.iter() {
+            invariant!(
+                self.filters.iter().any(|filter| filter.matches(ix)),
+                ProposalInstructionNotInClass
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Restricts the instructions [Proposal]s of a [Governor] may call.
+/// Only changeable by the [Governor::smart_wallet], i.e. through governance.
+#[account]
+#[derive(Debug, Default)]
+pub struct ProposalPolicy {
+    /// The [Governor].
+    pub governor: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+    /// If false, proposals may only call instructions of an allowed [InstructionList].
+    pub allow_unlisted: bool,
+    /// The lists of instructions.
+    pub lists: Vec<InstructionList>,
+}
+
+impl ProposalPolicy {
+    /// Space that the [ProposalPolicy] takes up.
+    pub fn space(lists: &[InstructionList]) -> usize {
+        std::mem::size_of::<Pubkey>()
+            + 1
+            + 1
+            + 4 // Vec discriminator
+            + lists.iter().map(|list| list.space()).sum::<usize>()
+    }
+
+    /// Checks the instructions against the policy.
+    /// Returns the strictest [InstructionList::params] of the lists the instructions fall into,
+    /// if any.
+    pub fn check_instructions(
+        &self,
+        instructions: &[ProposalInstruction],
+    ) -> Result<Option<GovernanceParameters>> {
+        let mut params: Option<GovernanceParameters> = None;
+        for ix in instructions.iter() {
+            let mut listed = false;
+            for list in self.lists.iter().filter(|list| list.matches(ix)) {
+                invariant!(list.allowed, ProposalInstructionDenied);
+                listed = true;
+                if let Some(list_params) = &list.params {
+                    params = Some(match params {
+                        Some(params) => params.strictest(list_params),
+                        None => *list_params,
+                    });
+                }
+            }
+            invariant!(listed || self.allow_unlisted, ProposalInstructionNotAllowed);
+        }
+        Ok(params)
+    }
+}
+
+/// A list of instructions in a [ProposalPolicy].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct InstructionList {
+    /// Whether proposals may call the instructions of the list.
+    pub allowed: bool,
+    /// Parameters of proposals calling an instruction of the list,
+    /// applied on top of [Governor::params] with [GovernanceParameters::strictest].
+    /// Only used by allowed lists.
+    pub params: Option<GovernanceParameters>,
+    /// The instructions of the list.
+    pub filters: Vec<InstructionFilter>,
+}
+
+impl InstructionList {
+    /// Space that an [InstructionList] takes up.
+    pub fn space(&self) -> usize {
+        1 + 1
+            + std::mem::size_of::<GovernanceParameters>()
+            + 4 // Vec discriminator
+            + self.filters.iter().map(|filter| filter.space()).sum::<usize>()
+    }
+
+    /// Returns true if the instruction matches any filter of the list.
+    pub fn matches(&self, ix: &ProposalInstruction) -> bool {
+        self.filters.iter().any(|filter| filter.matches(ix))
+    }
+}
+
+/// Matches the instructions of a program whose data starts with a discriminator.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct InstructionFilter {
+    /// The program called.
+    pub program_id: Pubkey,
+    /// Prefix of the instruction data, e.g. the 8-byte Anchor sighash.
+    /// Empty to match every instruction of the program.
+    pub discriminator: Vec<u8>,
+}
+
+impl InstructionFilter {
+    /// Space that an [InstructionFilter] takes up.
+    pub fn space(&self) -> usize {
+        std::mem::size_of::<Pubkey>() + 4 + self.discriminator.len()
+    }
+
+    /// Returns true if the instruction calls the program with the discriminator.
+    pub fn matches(&self, ix: &ProposalInstruction) -> bool {
+        ix.program_id == self.program_id && ix.data.starts_with(&self.discriminator)
+    }
 }
 
 /// Proposal type
@@ -74,6 +402,10 @@ pub enum ProposalType {
     YesNo = 0,
     /// Option
     Option = 1,
+    /// Approval voting: a vote may back several options
+    Approval = 2,
+    /// Ranked-choice voting, tallied with instant-runoff
+    RankedChoice = 3,
 }
 
 impl Default for ProposalType {
@@ -95,11 +427,25 @@ impl TryFrom<u8> for ProposalType {
         match value {
             0 => Ok(ProposalType::YesNo),
             1 => Ok(ProposalType::Option),
+            2 => Ok(ProposalType::Approval),
+            3 => Ok(ProposalType::RankedChoice),
             _ => program_err!(InvalidProposalType),
         }
     }
 }
 
+impl ProposalType {
+    /// Returns true if the proposal is decided by picking one of many options.
+    pub fn is_multi_option(&self) -> bool {
+        *self != ProposalType::YesNo
+    }
+
+    /// Returns true if a vote may pick more than one option.
+    pub fn has_vote_choices(&self) -> bool {
+        *self == ProposalType::Approval || *self == ProposalType::RankedChoice
+    }
+}
+
 /// A Yes/No Proposal is a pending transaction that may or may not be executed by the DAO.
 #[account]
 #[derive(Debug, Default)]
@@ -147,8 +493,72 @@ pub struct Proposal {
 
     pub proposal_type: u8,
 
-    /// buffers for future use
-    pub buffers: [u128; 10],
+    /// The timestamp when the winning option was recorded by [govern::tally_proposal].
+    pub tallied_at: i64,
+    /// Total voting power of [ProposalType::Approval] votes backing at least one option.
+    /// Each vote is counted once towards quorum, no matter how many options it approves.
+    pub approval_votes: u64,
+    /// The winning option, once tallied. 0 if no option has won.
+    pub winning_option: u8,
+    /// Bitmask of the options eliminated by instant-runoff rounds of a [ProposalType::RankedChoice] proposal.
+    pub eliminated_options: u16,
+    /// Bitmask of the options which have an [OptionInstructions] bundle.
+    pub option_instruction_sets: u16,
+    /// The [GovernanceParameters::approval_threshold_bps] at the time the proposal was created.
+    pub approval_threshold_bps: u16,
+    /// The [GovernanceParameters::quorum_bps] at the time the proposal was created.
+    /// If set, [Proposal::quorum_votes] is computed from the total voting power on activation.
+    pub quorum_bps: u16,
+    /// The [ProposalDeposit::refund_threshold_bps] at the time the proposal was created.
+    pub deposit_refund_threshold_bps: u16,
+    /// The [Governor::reward_claim_period] at the time the proposal was created.
+    pub reward_claim_period: i64,
+    /// Number of [ProposalReward]s co-sponsoring the proposal, besides [Proposal::voting_reward].
+    pub reward_count: u8,
+    /// Number of [ProposalReward]s refunded by [crate::govern::refund_proposal_reward].
+    pub refunded_reward_count: u8,
+    /// The [Governor::reward_policy] at the time the proposal was created.
+    pub reward_policy: u8,
+    /// padding for future use
+    pub padding: [u8; 1],
+
+    /// When the [Proposal::queued_transaction] was executed, as synced by [govern::sync_proposal].
+    pub executed_at: i64,
+    /// When [govern::sync_proposal] found the grace period of the [Proposal::queued_transaction] over.
+    pub expired_at: i64,
+    /// When the proposal was vetoed by the [Governor::veto_authority]. A veto cannot be undone.
+    pub vetoed_at: i64,
+
+    /// Amount escrowed by the proposer. See [ProposalDeposit].
+    pub deposit_amount: u64,
+    /// When the deposit was refunded or slashed by [govern::settle_proposal_deposit].
+    pub deposit_settled_at: i64,
+
+    /// Payer of the proposal, who receives its rent when it is closed.
+    pub payer: Pubkey,
+
+    /// When the unclaimed voting reward was swept back to the treasury by [govern::sweep_voting_reward].
+    pub reward_swept_at: i64,
+    /// Voting reward swept back to the treasury. Once swept,
+    /// [Proposal::total_claimed_reward] plus this is [VotingReward::reward_per_proposal].
+    pub swept_reward: u64,
+
+    /// Number of votes with voting power, for [RewardPolicy::Flat] rewards.
+    pub voter_count: u64,
+    /// Sum of the square roots of the voting power of votes, for [RewardPolicy::SquareRoot] rewards.
+    pub sqrt_voting_power: u64,
+
+    /// The [Governor::vote_lock_period] at the time the proposal was created.
+    pub vote_lock_period: u32,
+    /// The [Governor::late_quorum_extension] at the time the proposal was created.
+    pub late_quorum_extension: u32,
+    /// Seconds voting may still be extended by, starting from the
+    /// [Governor::max_voting_extension] at the time the proposal was created.
+    pub remaining_voting_extension: u32,
+    /// The [Governor::votes_final] at the time the proposal was created.
+    pub votes_final: bool,
+    /// padding for future use
+    pub vote_lock_padding: [u8; 4],
 
     /// The instructions associated with the proposal.
     pub instructions: Vec<ProposalInstruction>,
@@ -166,19 +576,109 @@ impl Proposal {
     }
 
     pub fn get_voting_reward(&self, vote: &Vote) -> Option<u64> {
-        let total_vote = self.total_votes()? as u128;
-        if total_vote == 0 {
+        self.get_reward_share(vote, self.voting_reward.reward_per_proposal)
+    }
+
+    /// Share of `total_reward` owed to the [Vote], according to the [Proposal::reward_policy].
+    pub fn get_reward_share(&self, vote: &Vote, total_reward: u64) -> Option<u64> {
+        if vote.voting_power == 0 {
             return Some(0);
         }
-        let reward_per_proposal = self.voting_reward.reward_per_proposal as u128;
-        let voting_power = vote.voting_power as u128;
-        let voting_reward = reward_per_proposal
-            .checked_mul(voting_power)?
-            .checked_div(total_vote)?;
-        return voting_reward.try_into().ok();
+        let (weight, total_weight) = match RewardPolicy::try_from(self.reward_policy).ok()? {
+            RewardPolicy::ProRata => (vote.voting_power, self.total_votes()?),
+            RewardPolicy::ExcludeAbstain => {
+                if vote.side == ABSTAIN_VOTE_INDEX as u8 {
+                    return Some(0);
+                }
+                (
+                    vote.voting_power,
+                    self.total_votes()?
+                        .checked_sub(self.option_votes[ABSTAIN_VOTE_INDEX])?,
+                )
+            }
+            RewardPolicy::Flat => (1, self.voter_count),
+            RewardPolicy::SquareRoot => (sqrt(vote.voting_power), self.sqrt_voting_power),
+        };
+        if total_weight == 0 {
+            return Some(0);
+        }
+        let reward = (total_reward as u128)
+            .checked_mul(weight as u128)?
+            .checked_div(total_weight as u128)?;
+        reward.try_into().ok()
     }
 }
 
+/// Parameters of a [Proposal] which do not fit into its buffers: the [GovernanceParameters]
+/// snapshotted when it was created, its [ProposalClass] and the seal of its instructions.
+/// Created with the [Proposal]; proposals created before it existed get theirs from
+/// [crate::govern::create_proposal_params].
+#[account]
+#[derive(Debug, Default)]
+pub struct ProposalParams {
+    /// The [Proposal].
+    pub proposal: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+    /// [GovernanceParameters::voting_delay] of the proposal.
+    pub voting_delay: u64,
+    /// [GovernanceParameters::voting_period] of the proposal.
+    pub voting_period: u64,
+    /// [GovernanceParameters::timelock_delay_seconds] of the proposal.
+    pub timelock_delay_seconds: i64,
+    /// The [ProposalClass] the proposal was created in, or [Pubkey::default] if
+    /// it uses [Governor::params].
+    pub proposal_class: Pubkey,
+    /// When [Proposal::instructions] were sealed. 0 while a draft is still
+    /// appending them with [crate::govern::append_proposal_instructions].
+    pub sealed_at: i64,
+    /// SHA-256 hash of the serialized [Proposal::instructions] and
+    /// [ProposalParams::lookup_instructions], set when sealed.
+    pub instructions_hash: [u8; 32],
+    /// buffers for future use
+    pub buffers: [u128; 4],
+    /// Instructions appended after [Proposal::instructions] which reference accounts through
+    /// address lookup tables, stored as table indices and resolved when the proposal is queued.
+    pub lookup_instructions: Vec<TXLookupInstruction>,
+}
+
+impl ProposalParams {
+    /// LEN of ProposalParams without [ProposalParams::lookup_instructions]
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 2 + 1 + 8 * 4 + 32 + 16 * 4 + 4;
+}
+
+/// An extra reward in any SPL token co-sponsoring a [Proposal], on top of its [VotingReward].
+/// Anyone may top it up while the proposal is a draft or active;
+/// it is shared among voters like the [VotingReward].
+#[account]
+#[derive(Debug, Default)]
+pub struct ProposalReward {
+    /// The [Proposal].
+    pub proposal: Pubkey,
+    /// Index of the reward on the proposal, the bit of [Vote::claimed_rewards] it is tracked with.
+    pub index: u8,
+    /// Bump seed
+    pub bump: u8,
+    /// Reward mint
+    pub reward_mint: Pubkey,
+    /// Vault holding the reward, owned by the [Governor].
+    pub reward_vault: Pubkey,
+    /// Total reward funded
+    pub total_reward: u64,
+    /// Total reward claimed by voters
+    pub total_claimed_reward: u64,
+    /// Creator of the reward, refunded the unclaimed reward and the rent by
+    /// [crate::govern::refund_proposal_reward].
+    pub sponsor: Pubkey,
+    /// buffers for future use
+    pub buffers: [u128; 2],
+}
+
+impl ProposalReward {
+    /// LEN of ProposalReward
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 4 + 1 + 1 + 8 + 8 + 16 * 2;
+}
+
 /// Metadata about a proposal.
 #[account]
 #[derive(Debug, Default)]
@@ -191,6 +691,46 @@ pub struct ProposalMeta {
     pub description_link: String,
 }
 
+/// Space after the data of a [ProposalMeta] or [OptionProposalMeta] recording its payer,
+/// who receives its rent when it is closed.
+pub const META_PAYER_SPACE: usize = std::mem::size_of::<Pubkey>();
+
+/// Records the payer after the first `space` bytes of the account `data` of a metadata account.
+fn record_meta_payer(data: &mut [u8], space: usize, payer: Pubkey) -> Option<()> {
+    data.get_mut(space..space.checked_add(META_PAYER_SPACE)?)?
+        .copy_from_slice(payer.as_ref());
+    Some(())
+}
+
+/// The payer recorded after the first `space` bytes of the account `data` of a metadata account,
+/// or [None] if it was created before payers were recorded.
+fn recorded_meta_payer(data: &[u8], space: usize) -> Option<Pubkey> {
+    if data.len() != space.checked_add(META_PAYER_SPACE)? {
+        return None;
+    }
+    Pubkey::try_from(data.get(space..)?).ok()
+}
+
+impl ProposalMeta {
+    /// Space that a [ProposalMeta] takes up.
+    pub fn space(title: &str, description_link: &str) -> usize {
+        std::mem::size_of::<Pubkey>() + 4 + title.len() + 4 + description_link.len()
+    }
+
+    /// Records the payer after the data of the [ProposalMeta] in its account `data`.
+    pub fn record_payer(&self, data: &mut [u8], payer: Pubkey) -> Option<()> {
+        let space = 8 + ProposalMeta::space(&self.title, &self.description_link);
+        record_meta_payer(data, space, payer)
+    }
+
+    /// The payer recorded in the account `data` of the [ProposalMeta],
+    /// or [None] if it was created before payers were recorded.
+    pub fn recorded_payer(&self, data: &[u8]) -> Option<Pubkey> {
+        let space = 8 + ProposalMeta::space(&self.title, &self.description_link);
+        recorded_meta_payer(data, space)
+    }
+}
+
 /// Metadata about an option proposal.
 #[account]
 #[derive(Debug, Default)]
@@ -202,6 +742,16 @@ pub struct OptionProposalMeta {
 }
 
 impl OptionProposalMeta {
+    /// Description of the [Proposal::winning_option], if the proposal has been tallied with a winner.
+    pub fn winning_option_description(&self, proposal: &Proposal) -> Option<&String> {
+        if proposal.tallied_at == 0 || proposal.winning_option == 0 {
+            return None;
+        }
+        // options start at 1, since index 0 is the abstain vote
+        self.option_descriptions
+            .get(proposal.winning_option as usize - 1)
+    }
+
     /// Space that a [ProposalInstruction] takes up.
     pub fn space(option_descriptions: &Vec<String>) -> usize {
         let mut total_size = std::mem::size_of::<Pubkey>() + 4;
@@ -210,6 +760,41 @@ impl OptionProposalMeta {
         }
         return total_size;
     }
+
+    /// Records the payer after the data of the [OptionProposalMeta] in its account `data`.
+    pub fn record_payer(&self, data: &mut [u8], payer: Pubkey) -> Option<()> {
+        let space = 8 + OptionProposalMeta::space(&self.option_descriptions);
+        record_meta_payer(data, space, payer)
+    }
+
+    /// The payer recorded in the account `data` of the [OptionProposalMeta],
+    /// or [None] if it was created before payers were recorded.
+    pub fn recorded_payer(&self, data: &[u8]) -> Option<Pubkey> {
+        let space = 8 + OptionProposalMeta::space(&self.option_descriptions);
+        recorded_meta_payer(data, space)
+    }
+}
+
+/// The instructions executed if an option of a multi-option [Proposal] wins.
+#[account]
+#[derive(Debug, Default)]
+pub struct OptionInstructions {
+    /// The [Proposal].
+    pub proposal: Pubkey,
+    /// The option these instructions belong to.
+    pub option: u8,
+    /// The instructions associated with the option.
+    pub instructions: Vec<ProposalInstruction>,
+}
+
+impl OptionInstructions {
+    /// Space that the [OptionInstructions] takes up.
+    pub fn space(instructions: &[ProposalInstruction]) -> usize {
+        std::mem::size_of::<Pubkey>()
+            + 1
+            + 4 // Vec discriminator
+            + instructions.iter().map(|ix| ix.space()).sum::<usize>()
+    }
 }
 
 /// A [Vote] is a vote made by a `voter`
@@ -228,12 +813,40 @@ pub struct Vote {
     pub voting_power: u64,
     /// Flag to check whether voter has claim the reward or not
     pub claimed: bool,
+    /// Options picked by an [ProposalType::Approval] or [ProposalType::RankedChoice] vote,
+    /// in order of preference, padded with zeroes.
+    /// For ranked-choice votes, [Vote::side] is the option currently holding the vote's weight.
+    pub choices: [u8; MAX_OPTION as usize],
+    /// Bitmask of the [ProposalReward]s claimed, by [ProposalReward::index].
+    pub claimed_rewards: u16,
+    /// When the vote was first cast, or 0 if it has not been cast.
+    pub cast_at: i64,
     /// buffers for future use
-    pub buffers: [u8; 32],
+    pub buffers: [u8; 12],
 }
 impl Vote {
     /// LEN of Vote
-    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 2 + 1 + 1 + 8 + 1 + 32;
+    pub const LEN: usize =
+        std::mem::size_of::<Pubkey>() * 2 + 1 + 1 + 8 + 1 + MAX_OPTION as usize + 2 + 8 + 12;
+
+    /// Returns true if the [ProposalReward] with the given index has been claimed.
+    pub fn has_claimed_reward(&self, index: u8) -> bool {
+        index < MAX_PROPOSAL_REWARDS && self.claimed_rewards & (1 << index) != 0
+    }
+
+    /// Returns true if the [Proposal::voting_reward] and the first `reward_count`
+    /// [ProposalReward]s have been claimed.
+    pub fn has_claimed_all_rewards(&self, reward_count: u8) -> bool {
+        self.claimed && (0..reward_count).all(|index| self.has_claimed_reward(index))
+    }
+
+    /// The options picked by the vote, in order of preference.
+    pub fn choices(&self) -> impl Iterator<Item = u8> + '_ {
+        self.choices
+            .iter()
+            .copied()
+            .take_while(|&option| option != 0)
+    }
 }
 
 /// Instruction.
@@ -258,6 +871,35 @@ impl ProposalInstruction {
     }
 }
 
+impl From<smart_wallet::TXInstruction> for ProposalInstruction {
+    fn from(
+        smart_wallet::TXInstruction {
+            program_id,
+            keys,
+            data,
+        }: smart_wallet::TXInstruction,
+    ) -> ProposalInstruction {
+        ProposalInstruction {
+            program_id,
+            keys: keys
+                .into_iter()
+                .map(
+                    |smart_wallet::TXAccountMeta {
+                         pubkey,
+                         is_signer,
+                         is_writable,
+                     }| ProposalAccountMeta {
+                        pubkey,
+                        is_signer,
+                        is_writable,
+                    },
+                )
+                .collect(),
+            data,
+        }
+    }
+}
+
 /// Account metadata used to define Instructions
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Copy, Clone)]
 pub struct ProposalAccountMeta {
@@ -272,6 +914,11 @@ pub struct ProposalAccountMeta {
 #[cfg(test)]
 mod state_test {
     use super::*;
+    use crate::{
+        has_signed_vote, parse_ed25519_instruction, signed_vote_message, ProposalState,
+        VerifiedSignature, AGAINST_VOTE_INDEX, FOR_VOTE_INDEX, MAX_INSTRUCTIONS_CHUNK_SPACE,
+        SIGNED_VOTE_DOMAIN,
+    };
     use anchor_lang::{prelude::Pubkey, AnchorSerialize, Discriminator};
     use std::assert_eq;
 
@@ -298,6 +945,78 @@ mod state_test {
         assert_eq!(serialized_bytes, proposal_ix_rent_space);
     }
 
+    #[test]
+    fn test_proposal_lookup_ix() {
+        // 24 accounts, referenced through a lookup table
+        let addresses: Vec<Pubkey> = (0..24).map(|_| Pubkey::new_unique()).collect();
+        let mut data = vec![0u8; smart_wallet::LOOKUP_TABLE_META_SIZE];
+        for address in addresses.iter() {
+            data.extend_from_slice(address.as_ref());
+        }
+        let table_key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let table = AccountInfo::new(
+            &table_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &smart_wallet::address_lookup_table_program::ID,
+            false,
+            0,
+        );
+        let lookup_ix = TXLookupInstruction {
+            program_id: Pubkey::default(),
+            data: vec![0u8; 64],
+            keys: vec![],
+            lookups: vec![smart_wallet::TXLookup {
+                table: table_key,
+                keys: (0..24)
+                    .map(|i| smart_wallet::TXLookupMeta {
+                        position: 23 - i,
+                        address_index: i,
+                        is_signer: false,
+                        is_writable: i == 0,
+                    })
+                    .collect(),
+            }],
+        };
+        let mut proposal_params = ProposalParams::default();
+        assert!(!proposal_params.keeps_lookup_instructions(&[]));
+        assert!(proposal_params.keeps_lookup_instructions(std::slice::from_ref(&lookup_ix)));
+
+        // the instruction is stored with table indices, a fraction of its resolved space
+        let plain_ix = TXLookupInstruction {
+            lookups: vec![],
+            ..lookup_ix.clone()
+        };
+        proposal_params.lookup_instructions = vec![lookup_ix.clone()];
+        assert!(proposal_params.keeps_lookup_instructions(std::slice::from_ref(&plain_ix)));
+        let instructions = vec![lookup_ix.clone(), plain_ix];
+        assert_eq!(
+            ProposalParams::lookup_instructions_space(&instructions),
+            instructions.try_to_vec().unwrap().len() - 4
+        );
+        assert_eq!(
+            ProposalParams::lookup_instructions_space(std::slice::from_ref(&lookup_ix)),
+            240
+        );
+        assert_eq!(lookup_ix.space(), 32 + 4 + 24 * 34 + 4 + 64);
+
+        // and resolved when queued
+        let resolved = proposal_params
+            .resolve_lookup_instructions(std::slice::from_ref(&table))
+            .unwrap();
+        let resolved_ix: ProposalInstruction = resolved[0].clone().into();
+        assert_eq!(resolved_ix.keys[23].pubkey, addresses[0]);
+        assert!(resolved_ix.keys[23].is_writable && !resolved_ix.keys[0].is_writable);
+        assert_eq!(resolved_ix.keys[0].pubkey, addresses[23]);
+        assert_eq!(resolved_ix.space(), lookup_ix.space());
+
+        // the table must be passed
+        assert!(proposal_params.resolve_lookup_instructions(&[]).is_err());
+    }
+
     #[test]
     fn test_proposal_empty_ix_space() {
         let empty_proposal = Proposal::default();
@@ -382,4 +1101,879 @@ mod state_test {
         println!("meta data size {}", proposal_ix_rent_space);
         assert_eq!(proposal_ix_rent_space, 396);
     }
+
+    #[test]
+    fn test_proposal_meta_payer() {
+        let meta = ProposalMeta {
+            proposal: Pubkey::new_unique(),
+            title: "Title".to_string(),
+            description_link: "https://example.com".to_string(),
+        };
+        let space = 8 + ProposalMeta::space(&meta.title, &meta.description_link);
+        assert_eq!(space, 8 + meta.try_to_vec().unwrap().len());
+
+        // the payer is recorded after the metadata
+        let payer = Pubkey::new_unique();
+        let mut data = vec![0u8; space + META_PAYER_SPACE];
+        meta.record_payer(&mut data, payer).unwrap();
+        assert_eq!(meta.recorded_payer(&data), Some(payer));
+        assert_eq!(meta.recorded_payer(&data[..space]), None);
+
+        let option_meta = OptionProposalMeta {
+            proposal: Pubkey::new_unique(),
+            option_descriptions: vec!["A".to_string(), "B".to_string()],
+        };
+        let space = 8 + OptionProposalMeta::space(&option_meta.option_descriptions);
+        let mut data = vec![0u8; space + META_PAYER_SPACE];
+        option_meta.record_payer(&mut data, payer).unwrap();
+        assert_eq!(option_meta.recorded_payer(&data), Some(payer));
+        assert_eq!(option_meta.recorded_payer(&data[..space]), None);
+    }
+
+    fn multi_option_proposal(proposal_type: ProposalType, max_option: u8) -> Proposal {
+        Proposal {
+            proposal_type: proposal_type.into(),
+            max_option,
+            option_votes: vec![0; (max_option + 1) as usize],
+            ..Default::default()
+        }
+    }
+
+    fn vote_with_choices(proposal: &Proposal, choices: &[u8], voting_power: u64) -> Vote {
+        let choices = proposal.to_vote_choices(choices).unwrap();
+        Vote {
+            side: choices[0],
+            choices,
+            voting_power,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_vote_space() {
+        let vote = Vote::default();
+        assert_eq!(vote.try_to_vec().unwrap().len(), Vote::LEN);
+    }
+
+    #[test]
+    fn test_invalid_vote_choices() {
+        let proposal = multi_option_proposal(ProposalType::Approval, 3);
+        assert!(proposal.to_vote_choices(&[1, 2, 3]).is_ok());
+        assert!(proposal.to_vote_choices(&[]).is_ok());
+        assert!(proposal.to_vote_choices(&[0]).is_err());
+        assert!(proposal.to_vote_choices(&[4]).is_err());
+        assert!(proposal.to_vote_choices(&[1, 1]).is_err());
+        assert!(proposal.to_vote_choices(&[1, 2, 3, 1]).is_err());
+
+        let proposal = multi_option_proposal(ProposalType::Option, 3);
+        assert!(proposal.to_vote_choices(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_approval_tally() {
+        let mut proposal = multi_option_proposal(ProposalType::Approval, 3);
+        let votes = [
+            vote_with_choices(&proposal, &[1, 2], 100),
+            vote_with_choices(&proposal, &[2, 3], 60),
+            vote_with_choices(&proposal, &[], 30),
+        ];
+        for vote in votes.iter() {
+            proposal.add_ballot(vote).unwrap();
+        }
+        assert_eq!(proposal.option_votes, vec![30, 100, 160, 60]);
+        // approvals are not double counted towards quorum
+        assert_eq!(proposal.total_votes(), Some(190));
+        assert_eq!(proposal.tally_plurality(), Some(2));
+
+        proposal.subtract_ballot(&votes[1]).unwrap();
+        assert_eq!(proposal.option_votes, vec![30, 100, 100, 0]);
+        assert_eq!(proposal.total_votes(), Some(130));
+        // ties are won by the lower option
+        assert_eq!(proposal.tally_plurality(), Some(1));
+    }
+
+    #[test]
+    fn test_plurality_tally_without_votes() {
+        let mut proposal = multi_option_proposal(ProposalType::Option, 3);
+        proposal.add_vote_weight(0, 100).unwrap();
+        assert_eq!(proposal.tally_plurality(), Some(0));
+    }
+
+    #[test]
+    fn test_ranked_choice_tally() {
+        let mut proposal = multi_option_proposal(ProposalType::RankedChoice, 3);
+        let mut votes = [
+            vote_with_choices(&proposal, &[1], 40),
+            vote_with_choices(&proposal, &[2, 1], 35),
+            vote_with_choices(&proposal, &[3, 2], 25),
+        ];
+        for vote in votes.iter() {
+            proposal.add_ballot(vote).unwrap();
+        }
+        assert_eq!(proposal.option_votes, vec![0, 40, 35, 25]);
+
+        // no majority, option 3 is eliminated and its votes must be transferred
+        assert_eq!(proposal.tally_ranked_choice(), None);
+        assert!(proposal.is_eliminated(3));
+        for vote in votes.iter_mut() {
+            proposal.transfer_ranked_vote(vote).unwrap();
+        }
+        assert_eq!(votes[2].side, 2);
+        assert_eq!(proposal.option_votes, vec![0, 40, 60, 0]);
+
+        assert_eq!(proposal.tally_ranked_choice(), Some(2));
+        assert_eq!(proposal.winning_option, 2);
+    }
+
+    #[test]
+    fn test_ranked_choice_exhausted_votes() {
+        let mut proposal = multi_option_proposal(ProposalType::RankedChoice, 3);
+        let mut votes = [
+            vote_with_choices(&proposal, &[1], 40),
+            vote_with_choices(&proposal, &[2], 35),
+            vote_with_choices(&proposal, &[3], 25),
+        ];
+        for vote in votes.iter() {
+            proposal.add_ballot(vote).unwrap();
+        }
+        assert_eq!(proposal.tally_ranked_choice(), None);
+        for vote in votes.iter_mut() {
+            proposal.transfer_ranked_vote(vote).unwrap();
+        }
+        // votes without a further preference are counted as abstentions
+        assert_eq!(votes[2].side, 0);
+        assert_eq!(proposal.option_votes, vec![25, 40, 35, 0]);
+        // the majority is taken over votes of running options only
+        assert_eq!(proposal.tally_ranked_choice(), Some(1));
+    }
+
+    #[test]
+    fn test_winning_option_description() {
+        let mut proposal = multi_option_proposal(ProposalType::Option, 2);
+        let meta = OptionProposalMeta {
+            proposal: Pubkey::default(),
+            option_descriptions: vec!["first".to_string(), "second".to_string()],
+        };
+        assert_eq!(meta.winning_option_description(&proposal), None);
+
+        proposal.tallied_at = 1;
+        proposal.winning_option = 2;
+        assert_eq!(
+            meta.winning_option_description(&proposal),
+            Some(&"second".to_string())
+        );
+    }
+
+    #[test]
+    fn test_option_instructions_space() {
+        let instructions = vec![ProposalInstruction {
+            program_id: Pubkey::default(),
+            data: vec![0u8; 24],
+            keys: vec![
+                ProposalAccountMeta {
+                    is_signer: false,
+                    is_writable: false,
+                    pubkey: Pubkey::default(),
+                };
+                8
+            ],
+        }];
+        let option_instructions = OptionInstructions {
+            instructions: instructions.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            option_instructions.try_to_vec().unwrap().len(),
+            OptionInstructions::space(&instructions)
+        );
+    }
+
+    #[test]
+    fn test_queue_winning_option_instructions() {
+        let mut proposal = multi_option_proposal(ProposalType::Option, 3);
+        let bundle = OptionInstructions {
+            option: 2,
+            instructions: vec![ProposalInstruction::default()],
+            ..Default::default()
+        };
+        proposal.option_instruction_sets = 1 << 2;
+        proposal.tallied_at = 1;
+
+        proposal.winning_option = 2;
+        assert_eq!(
+            proposal.to_smart_wallet_instructions(Some(&bundle)).len(),
+            1
+        );
+
+        // an option without a bundle is a no-op
+        proposal.winning_option = 1;
+        assert!(proposal
+            .to_smart_wallet_instructions(Some(&bundle))
+            .is_empty());
+        assert!(proposal.to_smart_wallet_instructions(None).is_empty());
+    }
+
+    #[test]
+    fn test_yes_no_approval_threshold() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![50, 34, 66],
+            approval_threshold_bps: 6_600,
+            ..Default::default()
+        };
+        // abstain votes do not count towards the threshold
+        assert_eq!(proposal.meets_approval_threshold(), Some(true));
+
+        proposal.option_votes = vec![0, 35, 65];
+        assert_eq!(proposal.meets_approval_threshold(), Some(false));
+
+        proposal.approval_threshold_bps = 0;
+        assert_eq!(proposal.meets_approval_threshold(), Some(true));
+    }
+
+    #[test]
+    fn test_option_approval_threshold() {
+        let mut proposal = multi_option_proposal(ProposalType::Option, 3);
+        proposal.approval_threshold_bps = 5_000;
+        proposal.option_votes = vec![100, 40, 30, 30];
+        // checked once tallied
+        assert_eq!(proposal.meets_approval_threshold(), Some(true));
+
+        proposal.tally_plurality();
+        proposal.tallied_at = 1;
+        assert_eq!(proposal.winning_option, 1);
+        assert_eq!(proposal.meets_approval_threshold(), Some(false));
+
+        proposal.option_votes = vec![100, 50, 30, 20];
+        assert_eq!(proposal.meets_approval_threshold(), Some(true));
+    }
+
+    #[test]
+    fn test_invalid_approval_threshold() {
+        let mut params = GovernanceParameters {
+            approval_threshold_bps: MAX_BPS,
+            ..Default::default()
+        };
+        assert!(params.validate().is_ok());
+        params.approval_threshold_bps = MAX_BPS + 1;
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_quorum_from_voting_power() {
+        let mut proposal = Proposal {
+            quorum_bps: 400,
+            ..Default::default()
+        };
+        assert_eq!(proposal.quorum_from_voting_power(1_000_000), Some(40_000));
+        assert_eq!(
+            proposal.quorum_from_voting_power(u64::MAX),
+            Some(u64::MAX / 25)
+        );
+
+        proposal.quorum_bps = MAX_BPS;
+        assert_eq!(
+            proposal.quorum_from_voting_power(1_000_000),
+            Some(1_000_000)
+        );
+
+        let params = GovernanceParameters {
+            quorum_bps: MAX_BPS + 1,
+            ..Default::default()
+        };
+        assert!(params.validate().is_err());
+    }
+
+    #[test]
+    fn test_executed_and_expired_states() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![0, 0, 10],
+            activated_at: 1,
+            voting_ends_at: 10,
+            queued_at: 11,
+            ..Default::default()
+        };
+        assert_eq!(proposal.state(12), Some(ProposalState::Queued));
+
+        proposal.executed_at = 12;
+        assert_eq!(proposal.state(13), Some(ProposalState::Executed));
+
+        proposal.executed_at = 0;
+        proposal.expired_at = 100;
+        assert_eq!(proposal.state(101), Some(ProposalState::Expired));
+    }
+
+    #[test]
+    fn test_vetoed_state() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![0, 0, 10],
+            activated_at: 1,
+            voting_ends_at: 10,
+            ..Default::default()
+        };
+        proposal.vetoed_at = 5;
+        assert_eq!(proposal.state(6), Some(ProposalState::Vetoed));
+
+        proposal.queued_at = 11;
+        assert_eq!(proposal.state(12), Some(ProposalState::Vetoed));
+    }
+
+    #[test]
+    fn test_deposit_refund_threshold() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![10, 20, 0],
+            quorum_votes: 100,
+            deposit_amount: 50,
+            deposit_refund_threshold_bps: 3_000,
+            ..Default::default()
+        };
+        // 30 votes reach 30% of the quorum
+        assert_eq!(
+            proposal.is_deposit_refundable(&ProposalState::Defeated),
+            Some(true)
+        );
+        proposal.option_votes = vec![10, 19, 0];
+        assert_eq!(
+            proposal.is_deposit_refundable(&ProposalState::Defeated),
+            Some(false)
+        );
+        // canceled proposals are always slashed
+        proposal.option_votes = vec![0, 0, 100];
+        assert_eq!(
+            proposal.is_deposit_refundable(&ProposalState::Canceled),
+            Some(false)
+        );
+        assert_eq!(
+            proposal.is_deposit_refundable(&ProposalState::Succeeded),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn test_reward_window() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![0, 0, 10],
+            quorum_votes: 10,
+            activated_at: 1,
+            voting_ends_at: 10,
+            ..Default::default()
+        };
+        // without a reward, a finished proposal and its votes may be closed right away
+        let vote = Vote::default();
+        assert_eq!(proposal.is_closable(5), Some(false));
+        assert_eq!(proposal.is_vote_closable(&vote, 5), Some(false));
+        assert_eq!(proposal.is_closable(11), Some(false)); // succeeded, but not queued
+        assert_eq!(proposal.is_vote_closable(&vote, 11), Some(true));
+        proposal.queued_at = 11;
+        assert_eq!(proposal.is_closable(11), Some(true));
+
+        // rewards may be claimed forever without a claim period
+        proposal.voting_reward.reward_per_proposal = 100;
+        assert_eq!(proposal.reward_claim_ends_at(), None);
+        assert_eq!(proposal.is_closable(1_000), Some(false));
+        assert_eq!(proposal.is_vote_closable(&vote, 1_000), Some(false));
+        let claimed_vote = Vote {
+            claimed: true,
+            ..Default::default()
+        };
+        assert_eq!(proposal.is_vote_closable(&claimed_vote, 11), Some(true));
+
+        proposal.reward_claim_period = 20;
+        assert_eq!(proposal.reward_claim_ends_at(), Some(30));
+        assert_eq!(proposal.is_closable(30), Some(false));
+        assert_eq!(proposal.is_vote_closable(&vote, 31), Some(true));
+        // the unclaimed reward does not need to be swept first, it is swept on close
+        proposal.total_claimed_reward = 40;
+        assert_eq!(proposal.unswept_reward(), Some(60));
+        assert_eq!(proposal.is_closable(31), Some(true));
+        proposal.reward_swept_at = 31;
+        proposal.swept_reward = 60;
+        assert_eq!(proposal.unswept_reward(), Some(0));
+        assert_eq!(proposal.is_closable(31), Some(true));
+
+        // an unsettled deposit must be settled first
+        proposal.deposit_amount = 1;
+        assert_eq!(proposal.is_closable(31), Some(false));
+        proposal.deposit_settled_at = 12;
+        assert_eq!(proposal.is_closable(31), Some(true));
+    }
+
+    #[test]
+    fn test_defeated_without_quorum_is_closable() {
+        let proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![0, 0, 5],
+            quorum_votes: 10,
+            activated_at: 1,
+            voting_ends_at: 10,
+            voting_reward: VotingReward {
+                reward_per_proposal: 100,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // no reward can be claimed when quorum is not met, so all of it is swept
+        let state = proposal.state(11).unwrap();
+        assert_eq!(proposal.is_reward_window_over(&state, 11), Some(true));
+        assert_eq!(proposal.is_vote_closable(&Vote::default(), 11), Some(true));
+        assert_eq!(proposal.unswept_reward(), Some(100));
+        assert_eq!(proposal.is_closable(11), Some(true));
+    }
+
+    #[test]
+    fn test_proposal_with_rewards_is_closable_once_refunded() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![0, 0, 5],
+            quorum_votes: 10,
+            activated_at: 1,
+            voting_ends_at: 10,
+            reward_count: 2,
+            ..Default::default()
+        };
+        let state = proposal.state(11).unwrap();
+        assert_eq!(proposal.is_reward_window_over(&state, 11), Some(true));
+        assert_eq!(proposal.is_closable(11), Some(false));
+        proposal.refunded_reward_count = 1;
+        assert!(proposal.has_unrefunded_rewards());
+        assert_eq!(proposal.is_closable(11), Some(false));
+        proposal.refunded_reward_count = 2;
+        assert!(!proposal.has_unrefunded_rewards());
+        assert_eq!(proposal.is_closable(11), Some(true));
+    }
+
+    #[test]
+    fn test_proposal_rent_payer() {
+        let proposer = Pubkey::new_unique();
+        let mut proposal = Proposal {
+            proposer,
+            ..Default::default()
+        };
+        // proposals created before the payer was recorded return their rent to the proposer
+        assert_eq!(proposal.rent_payer(), proposer);
+        let payer = Pubkey::new_unique();
+        proposal.payer = payer;
+        assert_eq!(proposal.rent_payer(), payer);
+    }
+
+    #[test]
+    fn test_proposal_reward_share() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![10, 30, 60],
+            quorum_votes: 10,
+            activated_at: 1,
+            voting_ends_at: 10,
+            reward_count: 2,
+            ..Default::default()
+        };
+        let mut vote = Vote {
+            voting_power: 30,
+            ..Default::default()
+        };
+        assert_eq!(proposal.get_reward_share(&vote, 1_000), Some(300));
+        assert_eq!(proposal.get_voting_reward(&vote), Some(0));
+
+        // rewards of the proposal keep the vote open until claimed
+        assert_eq!(proposal.is_vote_closable(&vote, 11), Some(false));
+        vote.claimed = true;
+        vote.claimed_rewards = 0b01;
+        assert!(vote.has_claimed_reward(0));
+        assert!(!vote.has_claimed_reward(1));
+        assert_eq!(proposal.is_vote_closable(&vote, 11), Some(false));
+        vote.claimed_rewards = 0b11;
+        assert_eq!(proposal.is_vote_closable(&vote, 11), Some(true));
+
+        proposal.reward_count = 0;
+        vote.claimed = false;
+        assert_eq!(proposal.is_vote_closable(&vote, 11), Some(true));
+    }
+
+    #[test]
+    fn test_proposal_policy() {
+        let upgradeable_loader = Pubkey::new_unique();
+        let token_program = Pubkey::new_unique();
+        let ix = |program_id: Pubkey, data: Vec<u8>| ProposalInstruction {
+            program_id,
+            keys: vec![],
+            data,
+        };
+        let elevated = GovernanceParameters {
+            timelock_delay_seconds: 86_400,
+            approval_threshold_bps: 6_600,
+            ..Default::default()
+        };
+        let mut policy = ProposalPolicy {
+            allow_unlisted: false,
+            lists: vec![
+                InstructionList {
+                    allowed: false,
+                    params: None,
+                    // SetAuthority
+                    filters: vec![InstructionFilter {
+                        program_id: token_program,
+                        discriminator: vec![6],
+                    }],
+                },
+                InstructionList {
+                    allowed: true,
+                    params: None,
+                    filters: vec![InstructionFilter {
+                        program_id: token_program,
+                        discriminator: vec![],
+                    }],
+                },
+                InstructionList {
+                    allowed: true,
+                    params: Some(elevated),
+                    filters: vec![InstructionFilter {
+                        program_id: upgradeable_loader,
+                        discriminator: vec![],
+                    }],
+                },
+            ],
+            ..Default::default()
+        };
+
+        let transfer = ix(token_program, vec![3, 1, 0]);
+        assert_eq!(
+            policy
+                .check_instructions(std::slice::from_ref(&transfer))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            policy
+                .check_instructions(&[transfer.clone(), ix(upgradeable_loader, vec![3])])
+                .unwrap(),
+            Some(elevated)
+        );
+        assert!(policy
+            .check_instructions(&[transfer.clone(), ix(token_program, vec![6, 0])])
+            .is_err());
+        let unlisted = ix(Pubkey::new_unique(), vec![]);
+        assert!(policy
+            .check_instructions(std::slice::from_ref(&unlisted))
+            .is_err());
+        policy.allow_unlisted = true;
+        assert_eq!(policy.check_instructions(&[unlisted]).unwrap(), None);
+
+        let params = GovernanceParameters {
+            voting_period: 100,
+            timelock_delay_seconds: 10,
+            quorum_bps: 1_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            params.strictest(&elevated),
+            GovernanceParameters {
+                voting_period: 100,
+                timelock_delay_seconds: 86_400,
+                approval_threshold_bps: 6_600,
+                quorum_bps: 1_000,
+                ..Default::default()
+            }
+        );
+
+        let mut proposal = Proposal::default();
+        let mut proposal_params = ProposalParams::default();
+        proposal.set_params(&mut proposal_params, &params);
+        assert_eq!(proposal.params(&proposal_params), params);
+    }
+
+    #[test]
+    fn test_proposal_class() {
+        let token_program = Pubkey::new_unique();
+        let proposal_class = ProposalClass {
+            name: "x".repeat(MAX_PROPOSAL_CLASS_NAME_LEN),
+            filters: vec![InstructionFilter {
+                program_id: token_program,
+                discriminator: vec![3],
+            }],
+            ..Default::default()
+        };
+        assert!(
+            proposal_class.try_to_vec().unwrap().len()
+                <= ProposalClass::space(&proposal_class.filters)
+        );
+
+        let ix = |program_id, data| ProposalInstruction {
+            program_id,
+            data,
+            ..Default::default()
+        };
+        assert!(proposal_class.check_instructions(&[]).is_ok());
+        assert!(proposal_class
+            .check_instructions(&[ix(token_program, vec![3, 1])])
+            .is_ok());
+        assert!(proposal_class
+            .check_instructions(&[ix(token_program, vec![3]), ix(token_program, vec![6])])
+            .is_err());
+        assert!(ProposalClass::default()
+            .check_instructions(&[ix(token_program, vec![3])])
+            .is_err());
+    }
+
+    #[test]
+    fn test_instructions_chunks() {
+        let ix = ProposalInstruction {
+            data: vec![0u8; 1_000],
+            ..Default::default()
+        };
+        let mut proposal = Proposal {
+            instructions: vec![ix.clone(); 20],
+            ..Default::default()
+        };
+        let mut proposal_params = ProposalParams::default();
+        assert!(!proposal_params.is_sealed());
+        proposal_params.seal_instructions(&proposal, 1).unwrap();
+        assert!(proposal_params.is_sealed());
+        let instructions_hash = proposal_params.instructions_hash;
+        proposal.instructions.push(ix);
+        proposal_params.seal_instructions(&proposal, 2).unwrap();
+        assert_ne!(proposal_params.instructions_hash, instructions_hash);
+
+        let instructions = proposal.to_smart_wallet_instructions(None);
+        let chunk_len = crate::instructions_chunk_len(&instructions);
+        assert_eq!(
+            chunk_len,
+            MAX_INSTRUCTIONS_CHUNK_SPACE / instructions[0].space()
+        );
+        assert_eq!(crate::instructions_chunk_len(&instructions[20..]), 1);
+        assert_eq!(crate::instructions_chunk_len(&[]), 0);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        assert_eq!(sqrt(0), 0);
+        assert_eq!(sqrt(1), 1);
+        assert_eq!(sqrt(15), 3);
+        assert_eq!(sqrt(16), 4);
+        assert_eq!(sqrt(u64::MAX), u32::MAX as u64);
+    }
+
+    #[test]
+    fn test_reward_policies() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            max_option: 2,
+            option_votes: vec![0, 0, 0],
+            voting_reward: VotingReward {
+                reward_per_proposal: 1_000,
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let abstain = Vote {
+            side: ABSTAIN_VOTE_INDEX as u8,
+            voting_power: 100,
+            ..Default::default()
+        };
+        let whale = Vote {
+            side: 2,
+            voting_power: 8_100,
+            ..Default::default()
+        };
+        let minnow = Vote {
+            side: 1,
+            voting_power: 900,
+            ..Default::default()
+        };
+        for vote in [&abstain, &whale, &minnow] {
+            proposal.add_ballot(vote).unwrap();
+        }
+        assert_eq!(proposal.voter_count, 3);
+        assert_eq!(proposal.sqrt_voting_power, 10 + 90 + 30);
+
+        proposal.reward_policy = RewardPolicy::ProRata.into();
+        assert_eq!(proposal.get_voting_reward(&abstain), Some(10));
+        assert_eq!(proposal.get_voting_reward(&whale), Some(890));
+
+        proposal.reward_policy = RewardPolicy::ExcludeAbstain.into();
+        assert_eq!(proposal.get_voting_reward(&abstain), Some(0));
+        assert_eq!(proposal.get_voting_reward(&whale), Some(900));
+        assert_eq!(proposal.get_voting_reward(&minnow), Some(100));
+
+        proposal.reward_policy = RewardPolicy::Flat.into();
+        assert_eq!(proposal.get_voting_reward(&abstain), Some(333));
+        assert_eq!(proposal.get_voting_reward(&whale), Some(333));
+
+        proposal.reward_policy = RewardPolicy::SquareRoot.into();
+        assert_eq!(proposal.get_voting_reward(&whale), Some(692));
+        assert_eq!(proposal.get_voting_reward(&minnow), Some(230));
+
+        // removing a vote takes it out of every policy's totals
+        proposal.subtract_ballot(&minnow).unwrap();
+        assert_eq!(proposal.voter_count, 2);
+        assert_eq!(proposal.sqrt_voting_power, 100);
+
+        proposal.reward_policy = 4;
+        assert_eq!(proposal.get_voting_reward(&whale), None);
+    }
+
+    #[test]
+    fn test_governor_space() {
+        let mut governor = Governor {
+            voting_reward: VotingReward {
+                reward_mint: Pubkey::new_unique(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let params = GovernanceParameters {
+            voting_delay: 1,
+            voting_period: 2,
+            quorum_votes: 3,
+            timelock_delay_seconds: 4,
+            approval_threshold_bps: 6_600,
+            quorum_bps: 400,
+        };
+        governor.set_governance_params(&params);
+        assert_eq!(governor.governance_params(), params);
+
+        let data = governor.try_to_vec().unwrap();
+        assert_eq!(data.len(), Governor::LEN);
+        // the account keeps its original size and the fields their offsets
+        assert_eq!(Governor::LEN, 721);
+        assert_eq!(&data[137..169], governor.voting_reward.reward_mint.as_ref());
+    }
+
+    #[test]
+    fn test_proposal_space() {
+        let instructions = vec![ProposalInstruction {
+            data: vec![1, 2, 3],
+            ..Default::default()
+        }];
+        let proposal = Proposal {
+            max_option: 2,
+            option_votes: vec![0; 3],
+            instructions: instructions.clone(),
+            vote_lock_period: u32::MAX,
+            ..Default::default()
+        };
+        let data = proposal.try_to_vec().unwrap();
+        assert_eq!(data.len(), Proposal::space(3, instructions));
+        // the fields added since fit into the original buffers
+        let baseline_len = 32 * 3 + 8 * 8 + 3 + 16 * 10 + std::mem::size_of::<VotingReward>();
+        assert_eq!(data.len(), baseline_len + 4 + 3 * 8 + 4 + 43);
+
+        let proposal_params = ProposalParams::default();
+        assert_eq!(
+            proposal_params.try_to_vec().unwrap().len(),
+            ProposalParams::LEN
+        );
+    }
+
+    #[test]
+    fn test_vote_lock() {
+        let mut proposal = Proposal {
+            voting_ends_at: 1_000,
+            ..Default::default()
+        };
+        assert!(!proposal.is_vote_locked(999));
+
+        proposal.vote_lock_period = 100;
+        assert!(!proposal.is_vote_locked(899));
+        assert!(proposal.is_vote_locked(900));
+
+        proposal.vote_lock_period = 0;
+        proposal.votes_final = true;
+        assert!(proposal.is_vote_locked(0));
+
+        let vote = Vote::default();
+        assert_eq!(vote.try_to_vec().unwrap().len(), Vote::LEN);
+    }
+
+    #[test]
+    fn test_late_quorum_extension() {
+        let mut proposal = Proposal {
+            proposal_type: ProposalType::YesNo.into(),
+            option_votes: vec![0; 3],
+            activated_at: 1_000,
+            voting_ends_at: 2_000,
+            ..Default::default()
+        };
+        assert_eq!(proposal.leading_side(), AGAINST_VOTE_INDEX as u8);
+        proposal.option_votes[FOR_VOTE_INDEX] = 1;
+        assert_eq!(proposal.leading_side(), FOR_VOTE_INDEX as u8);
+
+        // disabled
+        assert_eq!(proposal.extend_voting(1_950), Some(false));
+
+        proposal.late_quorum_extension = 100;
+        proposal.remaining_voting_extension = 120;
+        // not late enough
+        assert_eq!(proposal.extend_voting(1_900), Some(false));
+        assert_eq!(proposal.extend_voting(1_950), Some(true));
+        assert_eq!(proposal.voting_ends_at, 2_050);
+        assert_eq!(proposal.remaining_voting_extension, 70);
+        // capped
+        assert_eq!(proposal.extend_voting(2_040), Some(true));
+        assert_eq!(proposal.voting_ends_at, 2_120);
+        assert_eq!(proposal.extend_voting(2_110), Some(false));
+        assert_eq!(proposal.voting_ends_at, 2_120);
+        assert_eq!(proposal.remaining_voting_extension, 0);
+    }
+
+    #[test]
+    fn test_parse_ed25519_instruction() {
+        let signer = Pubkey::new_unique();
+        let proposal = Pubkey::new_unique();
+        let message = signed_vote_message(&proposal, 2, 7);
+        assert_eq!(message.len(), SIGNED_VOTE_DOMAIN.len() + 41);
+
+        // one signature with its public key, signature and message following the offsets
+        let public_key_offset = 2 + 14;
+        let signature_offset = public_key_offset + 32;
+        let message_offset = signature_offset + 64;
+        let mut data = vec![1, 0];
+        for value in [
+            signature_offset,
+            u16::MAX,
+            public_key_offset,
+            u16::MAX,
+            message_offset,
+            message.len() as u16,
+            u16::MAX,
+        ] {
+            data.extend_from_slice(&value.to_le_bytes());
+        }
+        data.extend_from_slice(signer.as_ref());
+        data.extend_from_slice(&[0; 64]);
+        data.extend_from_slice(&message);
+
+        let signatures = parse_ed25519_instruction(&data).unwrap();
+        assert_eq!(
+            signatures,
+            vec![VerifiedSignature {
+                pubkey: signer,
+                message: message.clone()
+            }]
+        );
+        assert!(has_signed_vote(&signatures, &signer, &proposal, 2, 7));
+        assert!(!has_signed_vote(&signatures, &signer, &proposal, 1, 7));
+        assert!(!has_signed_vote(&signatures, &signer, &proposal, 2, 8));
+        assert!(!has_signed_vote(&signatures, &proposal, &proposal, 2, 7));
+
+        // data verified in other instructions is not trusted
+        let mut other_instruction = data.clone();
+        other_instruction[14..16].copy_from_slice(&0u16.to_le_bytes());
+        assert!(parse_ed25519_instruction(&other_instruction).is_err());
+
+        // out of bounds
+        data.truncate(data.len() - 1);
+        assert!(parse_ed25519_instruction(&data).is_err());
+    }
 }
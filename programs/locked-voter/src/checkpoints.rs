@@ -0,0 +1,136 @@
+//! Voting power checkpoints.
+//!
+//! Checkpoints are kept in a ring: once full, a new checkpoint overwrites the oldest one.
+//! Several changes in the same second share a checkpoint.
+//! Escrow checkpoints are only recorded on actions signed by the escrow owner, so that
+//! nobody else can overwrite the checkpoints a vote needs.
+
+use crate::*;
+use num_traits::ToPrimitive;
+
+/// A state recorded at a point in time.
+trait Checkpoint: Copy {
+    fn timestamp(&self) -> i64;
+}
+
+impl Checkpoint for EscrowCheckpoint {
+    fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+/// Records a checkpoint in a ring of `max_checkpoints`.
+fn record<T: Checkpoint>(
+    checkpoints: &mut Vec<T>,
+    checkpoint_count: &mut u64,
+    max_checkpoints: usize,
+    checkpoint: T,
+) -> Option<()> {
+    if *checkpoint_count > 0 {
+        let last = (checkpoint_count.checked_sub(1)? % max_checkpoints as u64) as usize;
+        if checkpoints.get(last)?.timestamp() == checkpoint.timestamp() {
+            checkpoints[last] = checkpoint;
+            return Some(());
+        }
+    }
+
+    let next = (*checkpoint_count % max_checkpoints as u64) as usize;
+    if next < checkpoints.len() {
+        checkpoints[next] = checkpoint;
+    } else {
+        checkpoints.push(checkpoint);
+    }
+    *checkpoint_count = checkpoint_count.checked_add(1)?;
+    Some(())
+}
+
+/// The last checkpoint at or before `timestamp`.
+/// Returns `Some(None)` if there was none, and [None] if it may have been overwritten.
+fn checkpoint_at<T: Checkpoint>(
+    checkpoints: &[T],
+    checkpoint_count: u64,
+    timestamp: i64,
+) -> Option<Option<T>> {
+    let checkpoint = checkpoints
+        .iter()
+        .filter(|checkpoint| checkpoint.timestamp() <= timestamp)
+        .max_by_key(|checkpoint| checkpoint.timestamp());
+    if checkpoint.is_none() && checkpoint_count > checkpoints.len() as u64 {
+        return None;
+    }
+    Some(checkpoint.copied())
+}
+
+impl EscrowCheckpoints {
+    /// Records the current state of the [Escrow].
+    pub fn record(&mut self, escrow: &Escrow, now: i64) -> Result<()> {
+        let checkpoint = EscrowCheckpoint {
+            timestamp: now,
+            amount: escrow.amount,
+            escrow_started_at: escrow.escrow_started_at,
+            escrow_ends_at: escrow.escrow_ends_at,
+            is_max_lock: escrow.is_max_lock,
+            partial_vote_delegate_weights: escrow.partial_vote_delegate_weights,
+        };
+        unwrap_opt!(
+            record(
+                &mut self.checkpoints,
+                &mut self.checkpoint_count,
+                MAX_ESCROW_CHECKPOINTS,
+                checkpoint,
+            ),
+            "cannot record checkpoint"
+        );
+        Ok(())
+    }
+
+    /// Records the current state of the [Escrow] as its state since the beginning.
+    /// The escrow cannot change before it has checkpoints, so this also holds for escrows
+    /// created before checkpoints existed.
+    pub fn seed(&mut self, escrow: &Escrow) -> Result<()> {
+        self.record(escrow, 0)
+    }
+
+    /// The [Escrow] as it was at the given time, or [None] if it is no longer known.
+    /// `Some(None)` means the escrow had no checkpoint yet.
+    fn escrow_at(&self, timestamp: i64) -> Option<Option<Escrow>> {
+        let checkpoint = checkpoint_at(&self.checkpoints, self.checkpoint_count, timestamp)?;
+        Some(checkpoint.map(|checkpoint| Escrow {
+            amount: checkpoint.amount,
+            escrow_started_at: checkpoint.escrow_started_at,
+            escrow_ends_at: checkpoint.escrow_ends_at,
+            is_max_lock: checkpoint.is_max_lock,
+            partial_vote_delegate_weights: checkpoint.partial_vote_delegate_weights,
+            ..Default::default()
+        }))
+    }
+
+    /// The voting power the [Escrow] had at the given time, or [None] if it is no longer known.
+    /// An escrow without checkpoints at that time had no voting power.
+    pub fn voting_power_at_time(&self, locker: &Locker, timestamp: i64) -> Option<u64> {
+        match self.escrow_at(timestamp)? {
+            Some(escrow) => locker.calculate_voter_power(&escrow, timestamp),
+            None => Some(0),
+        }
+    }
+
+    /// The share of the voting power of the [Escrow] at the given time that a vote delegate
+    /// votes with: the partial vote delegate at `partial_vote_delegate`, or [Escrow::vote_delegate].
+    pub fn delegated_voting_power_at_time(
+        &self,
+        locker: &Locker,
+        partial_vote_delegate: Option<usize>,
+        timestamp: i64,
+    ) -> Option<u64> {
+        let escrow = match self.escrow_at(timestamp)? {
+            Some(escrow) => escrow,
+            None => return Some(0),
+        };
+        let voting_power = locker.calculate_voter_power(&escrow, timestamp)?;
+        let share_bps = escrow.vote_share_bps(partial_vote_delegate)?;
+        u128::from(voting_power)
+            .checked_mul(share_bps.into())?
+            .checked_div(govern::MAX_BPS.into())?
+            .to_u64()
+    }
+}
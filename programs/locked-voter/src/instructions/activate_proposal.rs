@@ -5,11 +5,17 @@ use crate::*;
 pub struct ActivateProposal<'info> {
     /// The [Locker].
     pub locker: Account<'info, Locker>,
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
     /// The [Governor].
     pub governor: Box<Account<'info, Governor>>,
     /// The [Proposal].
     #[account(mut)]
     pub proposal: Box<Account<'info, Proposal>>,
+    /// The [govern::ProposalParams] of the [Proposal].
+    /// CHECK: checked by the [govern] program.
+    pub proposal_params: UncheckedAccount<'info>,
     /// The [govern] program.
     pub govern_program: Program<'info, govern::program::Govern>,
     /// The smart wallet on the [Governor].
@@ -27,6 +33,9 @@ impl<'info> ActivateProposal<'info> {
                 self.to_activate_proposal_accounts(),
             )
             .with_signer(seeds),
+            unwrap_int!(self
+                .locker
+                .total_voting_power_at_time(&self.slope_changes, Clock::get()?.unix_timestamp)),
         )?;
 
         Ok(())
@@ -37,6 +46,7 @@ impl<'info> ActivateProposal<'info> {
         govern::cpi::accounts::ActivateProposal {
             governor: self.governor.to_account_info(),
             proposal: self.proposal.to_account_info(),
+            proposal_params: self.proposal_params.to_account_info(),
             locker: self.locker.to_account_info(),
         }
     }
@@ -48,6 +58,10 @@ impl<'info> Validate<'info> for ActivateProposal<'info> {
         assert_keys_eq!(self.governor, self.locker.governor);
         assert_keys_eq!(self.proposal.governor, self.governor);
         assert_keys_eq!(self.smart_wallet, self.governor.smart_wallet);
+        // the total would leave out the escrows not synced yet
+        if self.proposal.quorum_bps > 0 {
+            invariant!(self.locker.is_voting_power_synced(), VotingPowerNotSynced);
+        }
         Ok(())
     }
 }
@@ -0,0 +1,142 @@
+use crate::*;
+use govern::ProposalState;
+
+/// Accounts for [voter::add_delegated_vote].
+#[derive(Accounts)]
+pub struct AddDelegatedVote<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [Delegate] of the [Escrow].
+    #[account(has_one = locker)]
+    pub delegate: Box<Account<'info, Delegate>>,
+    /// The [Escrow] whose voting power is added.
+    #[account(has_one = locker)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+    /// CHECK: The [Vote] of the owner of the [Escrow], which must not have been cast
+    /// with the voting power of the [Escrow]. It may not exist.
+    #[account(
+        seeds = [
+            b"Vote".as_ref(),
+            proposal.key().as_ref(),
+            escrow.owner.as_ref()
+        ],
+        bump,
+        seeds::program = govern::ID
+    )]
+    pub escrow_vote: UncheckedAccount<'info>,
+    /// The [DelegatedVote] recording the voting power added.
+    #[account(
+        init,
+        seeds = [
+            b"DelegatedVote".as_ref(),
+            proposal.key().as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + DelegatedVote::LEN
+    )]
+    pub delegated_vote: Box<Account<'info, DelegatedVote>>,
+
+    /// The [Proposal] being voted on.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Vote] of the [Delegate].
+    #[account(mut)]
+    pub vote: Box<Account<'info, Vote>>,
+
+    /// Payer of the [DelegatedVote].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [govern] program.
+    pub govern_program: Program<'info, govern::program::Govern>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AddDelegatedVote<'info> {
+    pub fn add_delegated_vote(&mut self, bump: u8) -> Result<()> {
+        // partial vote delegates vote with their shares themselves
+        let voting_power = unwrap_opt!(
+            self.escrow_checkpoints.delegated_voting_power_at_time(
+                &self.locker,
+                None,
+                self.proposal.activated_at
+            ),
+            VotingPowerCheckpointNotFound
+        );
+
+        let delegated_vote = &mut self.delegated_vote;
+        delegated_vote.proposal = self.proposal.key();
+        delegated_vote.escrow = self.escrow.key();
+        delegated_vote.delegate = self.delegate.key();
+        delegated_vote.bump = bump;
+        delegated_vote.voting_power = voting_power;
+
+        emit!(AddDelegatedVoteEvent {
+            proposal: delegated_vote.proposal,
+            delegate: delegated_vote.delegate,
+            escrow: delegated_vote.escrow,
+            voting_power,
+        });
+
+        // zero votes should short circuit.
+        if voting_power == 0 {
+            return Ok(());
+        }
+
+        let delegate_voting_power = unwrap_int!(self.vote.voting_power.checked_add(voting_power));
+        set_cast_vote_power(
+            self.govern_program.to_account_info(),
+            govern::cpi::accounts::SetVotePower {
+                governor: self.governor.to_account_info(),
+                proposal: self.proposal.to_account_info(),
+                vote: self.vote.to_account_info(),
+                locker: self.locker.to_account_info(),
+            },
+            &self.locker,
+            delegate_voting_power,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for AddDelegatedVote<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.vote_delegate, self.delegate);
+        assert_keys_eq!(self.locker.governor, self.governor);
+        assert_keys_eq!(self.proposal.governor, self.governor);
+        assert_keys_eq!(self.vote.proposal, self.proposal);
+        assert_keys_eq!(self.vote.voter, self.delegate);
+        invariant!(self.vote.cast_at != 0, DelegateHasNotVoted);
+        if !self.escrow_vote.data_is_empty() {
+            let escrow_vote = Account::<Vote>::try_from(self.escrow_vote.as_ref())?;
+            invariant!(escrow_vote.voting_power == 0, EscrowHasVoted);
+        }
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Active,
+            "proposal must be active"
+        );
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::add_delegated_vote].
+pub struct AddDelegatedVoteEvent {
+    /// The [Proposal].
+    #[index]
+    pub proposal: Pubkey,
+    /// The [Delegate].
+    #[index]
+    pub delegate: Pubkey,
+    /// The [Escrow].
+    pub escrow: Pubkey,
+    /// The voting power added.
+    pub voting_power: u64,
+}
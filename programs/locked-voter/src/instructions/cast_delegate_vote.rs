@@ -0,0 +1,91 @@
+use crate::*;
+use govern::ProposalState;
+
+/// Accounts for [voter::cast_delegate_vote].
+#[derive(Accounts)]
+pub struct CastDelegateVote<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [Delegate] that is voting.
+    #[account(has_one = locker, has_one = authority)]
+    pub delegate: Box<Account<'info, Delegate>>,
+    /// The authority of the [Delegate].
+    pub authority: Signer<'info>,
+
+    /// The [Proposal] being voted on.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Vote] of the [Delegate].
+    #[account(mut)]
+    pub vote: Box<Account<'info, Vote>>,
+
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+    /// The [govern] program.
+    pub govern_program: Program<'info, govern::program::Govern>,
+}
+
+impl<'info> CastDelegateVote<'info> {
+    /// Sets the side of the [Vote] of the [Delegate], keeping the voting power added so far.
+    pub fn cast_delegate_vote(&mut self, side: u8) -> Result<()> {
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        govern::cpi::set_vote(
+            self.set_vote_context().with_signer(seeds),
+            side,
+            self.vote.voting_power,
+        )
+    }
+
+    /// Sets the choices of the [Vote] of the [Delegate], keeping the voting power added so far.
+    pub fn cast_delegate_vote_choices(&mut self, choices: Vec<u8>) -> Result<()> {
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        govern::cpi::set_vote_choices(
+            self.set_vote_context().with_signer(seeds),
+            choices,
+            self.vote.voting_power,
+        )
+    }
+
+    fn set_vote_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, govern::cpi::accounts::SetVote<'info>> {
+        CpiContext::new(
+            self.govern_program.to_account_info(),
+            govern::cpi::accounts::SetVote {
+                governor: self.governor.to_account_info(),
+                proposal: self.proposal.to_account_info(),
+                vote: self.vote.to_account_info(),
+                locker: self.locker.to_account_info(),
+            },
+        )
+    }
+}
+
+/// Sets the voting power of a cast [Vote], keeping its side or choices.
+/// This is allowed during the vote lock, as the ballot does not change.
+pub(crate) fn set_cast_vote_power<'info>(
+    govern_program: AccountInfo<'info>,
+    set_vote_power: govern::cpi::accounts::SetVotePower<'info>,
+    locker: &Locker,
+    voting_power: u64,
+) -> Result<()> {
+    let seeds: &[&[&[u8]]] = locker_seeds!(locker);
+    govern::cpi::set_vote_power(
+        CpiContext::new_with_signer(govern_program, set_vote_power, seeds),
+        voting_power,
+    )
+}
+
+impl<'info> Validate<'info> for CastDelegateVote<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.locker.governor, self.governor);
+        assert_keys_eq!(self.proposal.governor, self.governor);
+        assert_keys_eq!(self.vote.proposal, self.proposal);
+        assert_keys_eq!(self.vote.voter, self.delegate);
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Active,
+            "proposal must be active"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,154 @@
+use crate::*;
+use anchor_lang::solana_program::sysvar;
+use govern::ProposalState;
+
+/// A vote signed off-chain by the vote delegate of an [Escrow].
+/// See [govern::signed_vote_message].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignedVote {
+    /// The vote side.
+    pub side: u8,
+    /// Must be greater than the [Escrow::vote_nonce] of the last signed vote settled for the escrow.
+    pub nonce: u64,
+}
+
+/// Accounts for [voter::cast_signed_votes].
+#[derive(Accounts)]
+pub struct CastSignedVotes<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// The [Proposal] being voted on.
+    #[account(mut)]
+    pub proposal: Box<Account<'info, Proposal>>,
+    /// The [Governor].
+    pub governor: Box<Account<'info, Governor>>,
+
+    /// The relayer settling the votes, who pays for new [Vote]s.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: The instructions sysvar, holding the ed25519 program instructions which checked the signatures.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions_sysvar: UncheckedAccount<'info>,
+
+    /// The [govern] program.
+    pub govern_program: Program<'info, govern::program::Govern>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CastSignedVotes<'info> {
+    pub fn cast_signed_votes(
+        &mut self,
+        remaining_accounts: &[AccountInfo<'info>],
+        votes: Vec<SignedVote>,
+    ) -> Result<()> {
+        let quadruples = remaining_accounts.chunks_exact(4);
+        invariant!(
+            quadruples.len() == votes.len() && quadruples.remainder().is_empty(),
+            "Remaining accounts must be [escrow, escrow_checkpoints, vote, delegated_vote] for each vote"
+        );
+
+        let signatures = govern::load_verified_signatures(&self.instructions_sysvar)?;
+        let proposal = self.proposal.key();
+        for (signed_vote, accounts) in votes.into_iter().zip(quadruples) {
+            let mut escrow = Account::<Escrow>::try_from(&accounts[0])?;
+            let escrow_checkpoints = Account::<EscrowCheckpoints>::try_from(&accounts[1])?;
+            let vote_info = &accounts[2];
+            let delegated_vote_info = &accounts[3];
+            assert_keys_eq!(escrow.locker, self.locker);
+            assert_keys_eq!(escrow_checkpoints.escrow, escrow);
+            invariant!(signed_vote.nonce > escrow.vote_nonce, InvalidVoteNonce);
+            invariant!(
+                govern::has_signed_vote(
+                    &signatures,
+                    &escrow.vote_delegate,
+                    &proposal,
+                    signed_vote.side,
+                    signed_vote.nonce
+                ),
+                VoteNotSigned
+            );
+            escrow.vote_nonce = signed_vote.nonce;
+            escrow.exit(&crate::ID)?;
+
+            let (vote, _bump) = Pubkey::find_program_address(
+                &[b"Vote".as_ref(), proposal.as_ref(), escrow.owner.as_ref()],
+                &govern::ID,
+            );
+            assert_keys_eq!(vote, vote_info.key());
+            let (delegated_vote, _bump) = Pubkey::find_program_address(
+                &[
+                    b"DelegatedVote".as_ref(),
+                    proposal.as_ref(),
+                    escrow.key().as_ref(),
+                ],
+                &crate::ID,
+            );
+            assert_keys_eq!(delegated_vote, delegated_vote_info.key());
+            assert_not_delegated(delegated_vote_info)?;
+            if vote_info.data_is_empty() {
+                self.new_vote(vote_info, escrow.owner)?;
+            }
+
+            let voting_power = unwrap_opt!(
+                escrow_checkpoints.delegated_voting_power_at_time(
+                    &self.locker,
+                    None,
+                    self.proposal.activated_at
+                ),
+                VotingPowerCheckpointNotFound
+            );
+            // zero votes should short circuit.
+            if voting_power == 0 {
+                continue;
+            }
+
+            let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+            govern::cpi::set_vote(
+                CpiContext::new_with_signer(
+                    self.govern_program.to_account_info(),
+                    govern::cpi::accounts::SetVote {
+                        governor: self.governor.to_account_info(),
+                        proposal: self.proposal.to_account_info(),
+                        vote: vote_info.clone(),
+                        locker: self.locker.to_account_info(),
+                    },
+                    seeds,
+                ),
+                signed_vote.side,
+                voting_power,
+            )?;
+            // a late vote may have extended voting
+            self.proposal.reload()?;
+        }
+        Ok(())
+    }
+
+    fn new_vote(&self, vote_info: &AccountInfo<'info>, voter: Pubkey) -> Result<()> {
+        govern::cpi::new_vote(
+            CpiContext::new(
+                self.govern_program.to_account_info(),
+                govern::cpi::accounts::NewVote {
+                    proposal: self.proposal.to_account_info(),
+                    vote: vote_info.clone(),
+                    payer: self.payer.to_account_info(),
+                    system_program: self.system_program.to_account_info(),
+                },
+            ),
+            voter,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for CastSignedVotes<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.locker.governor, self.governor);
+        assert_keys_eq!(self.proposal.governor, self.governor);
+        invariant!(
+            self.proposal.get_state()? == ProposalState::Active,
+            "proposal must be active"
+        );
+        Ok(())
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use anchor_lang::system_program;
 use govern::ProposalState;
 
 /// Accounts for [voter::cast_vote].
@@ -8,7 +9,12 @@ pub struct CastVote<'info> {
     pub locker: Box<Account<'info, Locker>>,
     /// The [Escrow] that is voting.
     pub escrow: Box<Account<'info, Escrow>>,
-    /// Vote delegate of the [Escrow].
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+    /// Vote delegate of the [Escrow], one of its partial vote delegates, or its owner.
+    /// The owner pays for the [DelegatedVote] marking their vote.
+    #[account(mut)]
     pub vote_delegate: Signer<'info>,
 
     /// The [Proposal] being voted on.
@@ -17,16 +23,36 @@ pub struct CastVote<'info> {
     /// The [Vote].
     #[account(mut)]
     pub vote: Box<Account<'info, Vote>>,
+    /// CHECK: The [DelegatedVote] of the [Escrow] on the [Proposal]. [Escrow::vote_delegate]
+    /// cannot vote once it exists; the owner voting over their delegate creates or updates it.
+    #[account(
+        mut,
+        seeds = [
+            b"DelegatedVote".as_ref(),
+            proposal.key().as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump
+    )]
+    pub delegated_vote: UncheckedAccount<'info>,
 
     /// The [Governor].
     pub governor: Box<Account<'info, Governor>>,
     /// The [govern] program.
     pub govern_program: Program<'info, govern::program::Govern>,
+    /// System program.
+    pub system_program: Program<'info, System>,
 }
 
 impl<'info> CastVote<'info> {
-    pub fn cast_vote(&mut self, side: u8) -> Result<()> {
-        let voting_power = self.future_voting_power()?;
+    pub fn cast_vote(
+        &mut self,
+        delegated_vote_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+        side: u8,
+    ) -> Result<()> {
+        self.override_delegate_vote(delegated_vote_bump, remaining_accounts)?;
+        let voting_power = self.voting_power_at_activation()?;
 
         // zero votes should short circuit.
         if voting_power == 0 {
@@ -34,7 +60,43 @@ impl<'info> CastVote<'info> {
         }
 
         let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
-        let cpi_ctx = CpiContext::new(
+        govern::cpi::set_vote(
+            self.set_vote_context().with_signer(seeds),
+            side,
+            voting_power,
+        )?;
+        Ok(())
+    }
+
+    /// Casts a vote with choices in order of preference,
+    /// on an approval or ranked-choice proposal.
+    pub fn cast_vote_choices(
+        &mut self,
+        delegated_vote_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+        choices: Vec<u8>,
+    ) -> Result<()> {
+        self.override_delegate_vote(delegated_vote_bump, remaining_accounts)?;
+        let voting_power = self.voting_power_at_activation()?;
+
+        // zero votes should short circuit.
+        if voting_power == 0 {
+            return Ok(());
+        }
+
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        govern::cpi::set_vote_choices(
+            self.set_vote_context().with_signer(seeds),
+            choices,
+            voting_power,
+        )?;
+        Ok(())
+    }
+
+    fn set_vote_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, govern::cpi::accounts::SetVote<'info>> {
+        CpiContext::new(
             self.govern_program.to_account_info(),
             govern::cpi::accounts::SetVote {
                 governor: self.governor.to_account_info(),
@@ -43,33 +105,160 @@ impl<'info> CastVote<'info> {
                 locker: self.locker.to_account_info(),
             },
         )
-        .with_signer(seeds);
+    }
 
-        govern::cpi::set_vote(cpi_ctx, side, voting_power)?;
-        Ok(())
+    /// Gives the vote of the owner priority over the one of their delegate: the voting power
+    /// added to the vote of a [Delegate] is removed, and the delegate cannot vote anymore.
+    /// The [Vote] of the [Delegate] is the first remaining account if the escrow was added to it.
+    fn override_delegate_vote(
+        &self,
+        delegated_vote_bump: u8,
+        remaining_accounts: &[AccountInfo<'info>],
+    ) -> Result<()> {
+        if !self
+            .escrow
+            .is_owner_vote(&self.vote_delegate.key(), &self.vote.voter)
+        {
+            return Ok(());
+        }
+
+        if self.delegated_vote.data_is_empty() {
+            // nothing was added to the vote of a delegate, the current one only must not vote
+            if self.escrow.vote_delegate == self.escrow.owner {
+                return Ok(());
+            }
+            let proposal = self.proposal.key();
+            let escrow = self.escrow.key();
+            let seeds: &[&[&[u8]]] = &[&[
+                b"DelegatedVote".as_ref(),
+                proposal.as_ref(),
+                escrow.as_ref(),
+                &[delegated_vote_bump],
+            ]];
+            // like `init`, this works even if the account was funded beforehand
+            let space = 8 + DelegatedVote::LEN;
+            let lamports = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(self.delegated_vote.lamports());
+            if lamports > 0 {
+                system_program::transfer(
+                    CpiContext::new(
+                        self.system_program.to_account_info(),
+                        system_program::Transfer {
+                            from: self.vote_delegate.to_account_info(),
+                            to: self.delegated_vote.to_account_info(),
+                        },
+                    ),
+                    lamports,
+                )?;
+            }
+            system_program::allocate(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Allocate {
+                        account_to_allocate: self.delegated_vote.to_account_info(),
+                    },
+                    seeds,
+                ),
+                space as u64,
+            )?;
+            system_program::assign(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Assign {
+                        account_to_assign: self.delegated_vote.to_account_info(),
+                    },
+                    seeds,
+                ),
+                &crate::ID,
+            )?;
+            let delegated_vote = DelegatedVote {
+                proposal,
+                escrow,
+                delegate: self.escrow.vote_delegate,
+                bump: delegated_vote_bump,
+                voting_power: 0,
+                owner_voted: true,
+            };
+            let mut data = self.delegated_vote.try_borrow_mut_data()?;
+            delegated_vote.try_serialize(&mut &mut data[..])?;
+            return Ok(());
+        }
+
+        let mut delegated_vote = Account::<DelegatedVote>::try_from(self.delegated_vote.as_ref())?;
+        let voting_power = delegated_vote.record_owner_vote();
+        if voting_power > 0 {
+            let delegate_vote_info = unwrap_opt!(
+                remaining_accounts.first(),
+                "The vote of the delegate must be the first remaining account"
+            );
+            let delegate_vote = Account::<Vote>::try_from(delegate_vote_info)?;
+            assert_keys_eq!(delegate_vote.proposal, self.proposal);
+            assert_keys_eq!(delegate_vote.voter, delegated_vote.delegate);
+            set_cast_vote_power(
+                self.govern_program.to_account_info(),
+                govern::cpi::accounts::SetVotePower {
+                    governor: self.governor.to_account_info(),
+                    proposal: self.proposal.to_account_info(),
+                    vote: delegate_vote_info.clone(),
+                    locker: self.locker.to_account_info(),
+                },
+                &self.locker,
+                unwrap_int!(delegate_vote.voting_power.checked_sub(voting_power)),
+            )?;
+        }
+        delegated_vote.exit(&crate::ID)
     }
 
-    /// The voting power of the escrow at the time the proposal's voting ends.
-    /// Because user can unstake (toggle max lock) right after vote for a proposal, but the voting power is still the same,
-    /// so at the end of proposal user can still earn max voting power, while they can just wait for (max_stake_duration - voting_period) to withdraw the full token
-    /// in the fact that they should wait for max_stake_duration to withdraw the full token
-    /// To mitigate the issue, when deploying the DAO, admin should pick max_stake_duration >> voting_period
-    fn future_voting_power(&self) -> Result<u64> {
-        Ok(unwrap_int!(self.escrow.voting_power_at_time(
-            &self.locker,
-            self.proposal.voting_ends_at
-        )))
+    /// The partial vote delegate casting the [Vote], or [None] for [Escrow::vote_delegate]
+    /// or the owner, who vote as the owner of the escrow.
+    fn partial_vote_delegate(&self) -> Option<Option<usize>> {
+        if self.vote.voter == self.escrow.owner {
+            return Some(None);
+        }
+        self.escrow
+            .partial_vote_delegate_index(
+                &self.escrow.key(),
+                &self.vote_delegate.key(),
+                &self.vote.voter,
+            )
+            .map(Some)
+    }
+
+    /// The share of the voting power of the escrow the delegate votes with, when the proposal
+    /// was activated, so that tokens locked or lock changes made during the vote do not count.
+    fn voting_power_at_activation(&self) -> Result<u64> {
+        let partial_vote_delegate =
+            unwrap_opt!(self.partial_vote_delegate(), NotPartialVoteDelegate);
+        Ok(unwrap_opt!(
+            self.escrow_checkpoints.delegated_voting_power_at_time(
+                &self.locker,
+                partial_vote_delegate,
+                self.proposal.activated_at
+            ),
+            VotingPowerCheckpointNotFound
+        ))
     }
 }
 
 impl<'info> Validate<'info> for CastVote<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.escrow.locker, self.locker);
-        assert_keys_eq!(self.escrow.vote_delegate, self.vote_delegate);
         assert_keys_eq!(self.locker.governor, self.governor);
         assert_keys_eq!(self.proposal.governor, self.governor);
         assert_keys_eq!(self.vote.proposal, self.proposal);
-        assert_keys_eq!(self.vote.voter, self.escrow.owner);
+        if self.vote.voter == self.escrow.owner {
+            // the owner can always vote
+            if self.vote_delegate.key() != self.escrow.owner {
+                assert_keys_eq!(self.escrow.vote_delegate, self.vote_delegate);
+                assert_not_delegated(&self.delegated_vote)?;
+            }
+        } else {
+            invariant!(
+                self.partial_vote_delegate().is_some(),
+                NotPartialVoteDelegate
+            );
+        }
         invariant!(
             self.proposal.get_state()? == ProposalState::Active,
             "proposal must be active"
@@ -77,3 +266,14 @@ impl<'info> Validate<'info> for CastVote<'info> {
         Ok(())
     }
 }
+
+/// Checks that the voting power of an [Escrow] on a [Proposal] can still be voted with by
+/// [Escrow::vote_delegate], given its [DelegatedVote].
+pub(crate) fn assert_not_delegated(delegated_vote_info: &AccountInfo) -> Result<()> {
+    if delegated_vote_info.data_is_empty() {
+        return Ok(());
+    }
+    let delegated_vote = Account::<DelegatedVote>::try_from(delegated_vote_info)?;
+    invariant!(!delegated_vote.owner_voted, OwnerHasVoted);
+    Err(error!(crate::ErrorCode::EscrowVoteDelegated))
+}
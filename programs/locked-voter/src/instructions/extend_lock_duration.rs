@@ -5,12 +5,21 @@ use num_traits::ToPrimitive;
 #[derive(Accounts)]
 pub struct ExtendLockDuration<'info> {
     /// [Locker].
+    #[account(mut)]
     pub locker: Box<Account<'info, Locker>>,
 
     /// [Escrow].
     #[account(mut, has_one = locker)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// Authority of the [Escrow] and
     pub escrow_owner: Signer<'info>,
 }
@@ -44,9 +53,16 @@ impl<'info> ExtendLockDuration<'info> {
 
         // update the escrow and locker
 
-        let locker = &self.locker;
+        let locker = &mut self.locker;
         let escrow = &mut self.escrow;
         escrow.record_extend_lock_duration_event(next_escrow_started_at, next_escrow_ends_at)?;
+        unwrap_int!(locker.track_escrow_voting_power(
+            &mut self.slope_changes,
+            escrow,
+            next_escrow_started_at
+        ));
+        self.escrow_checkpoints
+            .record(escrow, next_escrow_started_at)?;
 
         emit!(ExtendLockDurationEvent {
             locker: locker.key(),
@@ -12,6 +12,14 @@ pub struct IncreaseLockedAmount<'info> {
     #[account(mut, has_one = locker)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// Token account held by the [Escrow].
     #[account(
         mut,
@@ -51,6 +59,12 @@ impl<'info> IncreaseLockedAmount<'info> {
         let locker = &mut self.locker;
         let escrow = &mut self.escrow;
         escrow.record_increase_locked_amount_event(locker, amount)?;
+        let now = Clock::get()?.unix_timestamp;
+        unwrap_int!(locker.track_escrow_voting_power(&mut self.slope_changes, escrow, now));
+        // deposits by others are checkpointed on the next action of the owner
+        if self.payer.key() == escrow.owner {
+            self.escrow_checkpoints.record(escrow, now)?;
+        }
 
         emit!(IncreaseLockedAmountEvent {
             locker: locker.key(),
@@ -11,6 +11,14 @@ pub struct MergePartialUnstaking<'info> {
     #[account(mut, has_one = locker, has_one = owner)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// The [PartialUnstaking] that is being merged.
     #[account(mut, has_one = escrow, close = owner)]
     pub partial_unstake: Box<Account<'info, PartialUnstaking>>,
@@ -28,6 +36,11 @@ impl<'info> MergePartialUnstaking<'info> {
         let escrow = &mut self.escrow;
 
         unwrap_int!(escrow.merge_partial_unstaking_amount(partial_unstake.amount));
+        let now = Clock::get()?.unix_timestamp;
+        unwrap_int!(self
+            .locker
+            .track_escrow_voting_power(&mut self.slope_changes, escrow, now));
+        self.escrow_checkpoints.record(escrow, now)?;
 
         emit!(MergePartialUnstakingEvent {
             partial_unstake: partial_unstake_pk,
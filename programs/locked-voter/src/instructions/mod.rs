@@ -1,29 +1,53 @@
 //! Instruction processors.
 
 pub mod activate_proposal;
+pub mod add_delegated_vote;
+pub mod cast_delegate_vote;
+pub mod cast_signed_votes;
 pub mod cast_vote;
+pub mod create_proposal;
 pub mod extend_lock_duration;
 pub mod increase_locked_amount;
 pub mod merge_partial_unstaking;
+pub mod new_delegate;
 pub mod new_escrow;
+pub mod new_escrow_checkpoints;
 pub mod new_locker;
+pub mod new_locker_slope_changes;
 pub mod open_partial_unstaking;
+pub mod set_delegate_profile;
 pub mod set_locker_params;
+pub mod set_partial_vote_delegate;
 pub mod set_vote_delegate;
+pub mod sync_escrows_voting_power;
 pub mod toggle_max_lock;
 pub mod withdraw;
+pub mod withdraw_delegate_rewards;
 pub mod withdraw_partial_unstaking;
+pub mod withdraw_partial_vote_rewards;
 
 pub use activate_proposal::*;
+pub use add_delegated_vote::*;
+pub use cast_delegate_vote::*;
+pub use cast_signed_votes::*;
 pub use cast_vote::*;
+pub use create_proposal::*;
 pub use extend_lock_duration::*;
 pub use increase_locked_amount::*;
 pub use merge_partial_unstaking::*;
+pub use new_delegate::*;
 pub use new_escrow::*;
+pub use new_escrow_checkpoints::*;
 pub use new_locker::*;
+pub use new_locker_slope_changes::*;
 pub use open_partial_unstaking::*;
+pub use set_delegate_profile::*;
 pub use set_locker_params::*;
+pub use set_partial_vote_delegate::*;
 pub use set_vote_delegate::*;
+pub use sync_escrows_voting_power::*;
 pub use toggle_max_lock::*;
 pub use withdraw::*;
+pub use withdraw_delegate_rewards::*;
 pub use withdraw_partial_unstaking::*;
+pub use withdraw_partial_vote_rewards::*;
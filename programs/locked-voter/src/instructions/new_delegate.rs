@@ -0,0 +1,76 @@
+use crate::*;
+
+/// Accounts for [voter::new_delegate].
+#[derive(Accounts)]
+pub struct NewDelegate<'info> {
+    /// [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// [Delegate].
+    #[account(
+        init,
+        seeds = [
+            b"Delegate".as_ref(),
+            locker.key().as_ref(),
+            authority.key().as_ref()
+        ],
+        bump,
+        payer = authority,
+        space = 8 + Delegate::LEN
+    )]
+    pub delegate: Box<Account<'info, Delegate>>,
+
+    /// Authority of the [Delegate], who pays for its creation.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewDelegate<'info> {
+    /// Creates a new [Delegate].
+    pub fn new_delegate(&mut self, bump: u8, name: String, url: String) -> Result<()> {
+        invariant!(name.len() <= MAX_DELEGATE_NAME_LEN, DelegateNameTooLong);
+        invariant!(url.len() <= MAX_DELEGATE_URL_LEN, DelegateUrlTooLong);
+
+        let delegate = &mut self.delegate;
+        delegate.locker = self.locker.key();
+        delegate.authority = self.authority.key();
+        delegate.bump = bump;
+        delegate.name = name;
+        delegate.url = url;
+
+        emit!(NewDelegateEvent {
+            locker: delegate.locker,
+            delegate: delegate.key(),
+            authority: delegate.authority,
+            name: delegate.name.clone(),
+            url: delegate.url.clone(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewDelegate<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::new_delegate].
+pub struct NewDelegateEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// The [Delegate].
+    pub delegate: Pubkey,
+    /// The authority of the [Delegate].
+    pub authority: Pubkey,
+    /// The name of the [Delegate].
+    pub name: String,
+    /// The URL of the profile of the [Delegate].
+    pub url: String,
+}
@@ -50,6 +50,7 @@ impl<'info> NewEscrow<'info> {
         escrow.escrow_ends_at = 0;
         escrow.vote_delegate = self.escrow_owner.key();
         escrow.is_max_lock = false;
+        escrow.is_tracked = true;
 
         let locker = &mut self.locker;
         locker.total_escrow = unwrap_int!(locker.total_escrow.checked_add(1));
@@ -0,0 +1,45 @@
+use crate::*;
+
+/// Accounts for [voter::new_escrow_checkpoints].
+#[derive(Accounts)]
+pub struct NewEscrowCheckpoints<'info> {
+    /// [Escrow].
+    pub escrow: Box<Account<'info, Escrow>>,
+
+    /// [EscrowCheckpoints].
+    #[account(
+        init,
+        seeds = [
+            b"EscrowCheckpoints".as_ref(),
+            escrow.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + EscrowCheckpoints::LEN
+    )]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewEscrowCheckpoints<'info> {
+    /// Creates the [EscrowCheckpoints] of an [Escrow], seeded with its current state.
+    pub fn new_escrow_checkpoints(&mut self, bump: u8) -> Result<()> {
+        let escrow_checkpoints = &mut self.escrow_checkpoints;
+        escrow_checkpoints.escrow = self.escrow.key();
+        escrow_checkpoints.bump = bump;
+        escrow_checkpoints.seed(&self.escrow)?;
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewEscrowCheckpoints<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
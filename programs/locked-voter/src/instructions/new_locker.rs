@@ -36,6 +36,11 @@ pub struct NewLocker<'info> {
 impl<'info> NewLocker<'info> {
     /// Creates a new [Locker].
     pub fn new_locker(&mut self, bump: u8, params: LockerParams) -> Result<()> {
+        invariant!(
+            params.max_stake_duration <= MAX_STAKE_DURATION,
+            MaxStakeDurationTooLong
+        );
+
         let locker = &mut self.locker;
         locker.token_mint = self.token_mint.key();
         locker.governor = self.governor.key();
@@ -12,6 +12,14 @@ pub struct OpenPartialUnstaking<'info> {
     #[account(mut, has_one = locker, has_one = owner)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// [Escrow].
     #[account(
         init,
@@ -53,6 +61,12 @@ impl<'info> OpenPartialUnstaking<'info> {
             unwrap_int!(current_time.checked_add(remaining_duration as i64));
 
         unwrap_int!(escrow.accumulate_partial_unstaking_amount(amount));
+        unwrap_int!(self.locker.track_escrow_voting_power(
+            &mut self.slope_changes,
+            escrow,
+            current_time
+        ));
+        self.escrow_checkpoints.record(escrow, current_time)?;
 
         emit!(OpenPartialStakingEvent {
             partial_unstake: partial_unstake_pk,
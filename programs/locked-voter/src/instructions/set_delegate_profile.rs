@@ -0,0 +1,48 @@
+use crate::*;
+
+/// Accounts for [voter::set_delegate_profile].
+#[derive(Accounts)]
+pub struct SetDelegateProfile<'info> {
+    /// The [Delegate].
+    #[account(mut, has_one = authority)]
+    pub delegate: Box<Account<'info, Delegate>>,
+    /// The authority of the [Delegate].
+    pub authority: Signer<'info>,
+}
+
+impl<'info> SetDelegateProfile<'info> {
+    pub fn set_delegate_profile(&mut self, name: String, url: String) -> Result<()> {
+        invariant!(name.len() <= MAX_DELEGATE_NAME_LEN, DelegateNameTooLong);
+        invariant!(url.len() <= MAX_DELEGATE_URL_LEN, DelegateUrlTooLong);
+
+        let delegate = &mut self.delegate;
+        delegate.name = name;
+        delegate.url = url;
+
+        emit!(SetDelegateProfileEvent {
+            delegate: delegate.key(),
+            name: delegate.name.clone(),
+            url: delegate.url.clone(),
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetDelegateProfile<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::set_delegate_profile].
+pub struct SetDelegateProfileEvent {
+    /// The [Delegate].
+    #[index]
+    pub delegate: Pubkey,
+    /// The new name of the [Delegate].
+    pub name: String,
+    /// The new URL of the profile of the [Delegate].
+    pub url: String,
+}
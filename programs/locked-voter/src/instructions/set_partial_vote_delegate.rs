@@ -0,0 +1,76 @@
+use crate::*;
+
+/// Accounts for [voter::set_partial_vote_delegate].
+#[derive(Accounts)]
+pub struct SetPartialVoteDelegate<'info> {
+    /// The [Escrow].
+    #[account(mut)]
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+    /// The owner of the [Escrow].
+    pub escrow_owner: Signer<'info>,
+}
+
+impl<'info> SetPartialVoteDelegate<'info> {
+    pub fn set_partial_vote_delegate(
+        &mut self,
+        index: u8,
+        delegate: Pubkey,
+        weight_bps: u16,
+    ) -> Result<()> {
+        let index = usize::from(index);
+        invariant!(
+            index < MAX_PARTIAL_VOTE_DELEGATES,
+            InvalidPartialVoteDelegateIndex
+        );
+
+        let escrow = &mut self.escrow;
+        let old_delegate = escrow.partial_vote_delegates[index];
+        escrow.partial_vote_delegates[index] = delegate;
+        escrow.partial_vote_delegate_weights[index] = weight_bps;
+        invariant!(
+            escrow.vote_share_bps(None).is_some(),
+            PartialVoteDelegateWeightsTooHigh
+        );
+
+        // shares count from the next proposal activation, like lock changes
+        let now = Clock::get()?.unix_timestamp;
+        self.escrow_checkpoints.record(escrow, now)?;
+
+        emit!(SetPartialVoteDelegateEvent {
+            escrow_owner: escrow.owner,
+            index: index as u8,
+            old_delegate,
+            new_delegate: delegate,
+            weight_bps,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetPartialVoteDelegate<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.owner, self.escrow_owner);
+
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::set_partial_vote_delegate].
+pub struct SetPartialVoteDelegateEvent {
+    /// The owner of the Escrow.
+    #[index]
+    pub escrow_owner: Pubkey,
+    /// The index of the partial vote delegate.
+    pub index: u8,
+    /// The old partial vote delegate.
+    pub old_delegate: Pubkey,
+    /// The new partial vote delegate.
+    pub new_delegate: Pubkey,
+    /// The share in bps of the escrow voting power of the new partial vote delegate.
+    pub weight_bps: u16,
+}
@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Accounts for [voter::sync_escrows_voting_power].
+#[derive(Accounts)]
+pub struct SyncEscrowsVotingPower<'info> {
+    /// [Locker].
+    #[account(mut)]
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+}
+
+impl<'info> SyncEscrowsVotingPower<'info> {
+    /// Counts the [Escrow]s in the total voting power of the [Locker].
+    pub fn sync_escrows_voting_power(&mut self, escrows: &[AccountInfo<'info>]) -> Result<()> {
+        invariant!(!escrows.is_empty(), "Remaining accounts must be escrows");
+
+        let now = Clock::get()?.unix_timestamp;
+        for escrow_info in escrows {
+            invariant!(escrow_info.is_writable, "Escrow must be writable");
+            let mut escrow = Account::<Escrow>::try_from(escrow_info)?;
+            assert_keys_eq!(escrow.locker, self.locker);
+            unwrap_int!(self.locker.track_escrow_voting_power(
+                &mut self.slope_changes,
+                &mut escrow,
+                now
+            ));
+            escrow.exit(&crate::ID)?;
+        }
+
+        emit!(SyncEscrowsVotingPowerEvent {
+            locker: self.locker.key(),
+            escrow_count: escrows.len() as u64,
+            tracked_locked_supply: self.locker.tracked_locked_supply,
+            locked_supply: self.locker.locked_supply,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SyncEscrowsVotingPower<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::sync_escrows_voting_power].
+pub struct SyncEscrowsVotingPowerEvent {
+    /// The [Locker].
+    #[index]
+    pub locker: Pubkey,
+    /// Number of [Escrow]s synced.
+    pub escrow_count: u64,
+    /// [Locker::tracked_locked_supply] after the sync.
+    pub tracked_locked_supply: u64,
+    /// [Locker::locked_supply].
+    pub locked_supply: u64,
+}
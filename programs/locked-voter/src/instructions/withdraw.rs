@@ -12,6 +12,14 @@ pub struct Withdraw<'info> {
     #[account(mut, has_one = locker, close = payer)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [EscrowCheckpoints] of the [Escrow].
+    #[account(mut, has_one = escrow)]
+    pub escrow_checkpoints: Box<Account<'info, EscrowCheckpoints>>,
+
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// Authority of the [Escrow].
     pub escrow_owner: Signer<'info>,
     /// Tokens locked up in the [Escrow].
@@ -52,7 +60,15 @@ impl<'info> Withdraw<'info> {
 
         // update the locker
         let locker = &mut self.locker;
-        locker.locked_supply = unwrap_int!(locker.locked_supply.checked_sub(self.escrow.amount));
+        unwrap_int!(locker.record_withdrawal(&self.escrow, self.escrow.amount));
+        // the escrow is closed, leaving no voting power
+        let now = Clock::get()?.unix_timestamp;
+        let mut exited = Escrow {
+            amount: 0,
+            ..**self.escrow
+        };
+        unwrap_int!(locker.track_escrow_voting_power(&mut self.slope_changes, &mut exited, now));
+        self.escrow_checkpoints.record(&exited, now)?;
 
         emit!(ExitEscrowEvent {
             escrow_owner: self.escrow.owner,
@@ -0,0 +1,96 @@
+use crate::*;
+use anchor_spl::token;
+
+/// Accounts for [voter::withdraw_delegate_rewards].
+#[derive(Accounts)]
+pub struct WithdrawDelegateRewards<'info> {
+    /// The [Delegate], the voter of its [Vote]s, which receives their rewards
+    /// and the rent of their accounts once closed.
+    #[account(mut, has_one = authority)]
+    pub delegate: Box<Account<'info, Delegate>>,
+    /// The authority of the [Delegate], who receives what it holds.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    /// Token account of the [Delegate] holding rewards, if any.
+    #[account(mut)]
+    pub delegate_tokens: Option<Account<'info, TokenAccount>>,
+    /// Destination of the rewards.
+    #[account(mut)]
+    pub destination_tokens: Option<Account<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+}
+
+impl<'info> WithdrawDelegateRewards<'info> {
+    pub fn withdraw_delegate_rewards(&mut self) -> Result<()> {
+        let mut amount = 0;
+        if let (Some(delegate_tokens), Some(destination_tokens)) =
+            (&self.delegate_tokens, &self.destination_tokens)
+        {
+            amount = delegate_tokens.amount;
+            if amount > 0 {
+                let seeds: &[&[&[u8]]] = delegate_seeds!(self.delegate);
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: delegate_tokens.to_account_info(),
+                            to: destination_tokens.to_account_info(),
+                            authority: self.delegate.to_account_info(),
+                        },
+                        seeds,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        // the delegate keeps its rent
+        let delegate_info = self.delegate.to_account_info();
+        let rent = Rent::get()?.minimum_balance(delegate_info.data_len());
+        let lamports = delegate_info.lamports().saturating_sub(rent);
+        if lamports > 0 {
+            let authority_info = self.authority.to_account_info();
+            **delegate_info.try_borrow_mut_lamports()? = rent;
+            **authority_info.try_borrow_mut_lamports()? =
+                unwrap_int!(authority_info.lamports().checked_add(lamports));
+        }
+
+        emit!(WithdrawDelegateRewardsEvent {
+            delegate: self.delegate.key(),
+            token_mint: self.delegate_tokens.as_ref().map(|tokens| tokens.mint),
+            amount,
+            lamports,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for WithdrawDelegateRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.delegate_tokens.is_some() == self.destination_tokens.is_some(),
+            "delegate and destination tokens must be given together"
+        );
+        if let Some(delegate_tokens) = &self.delegate_tokens {
+            assert_keys_eq!(delegate_tokens.owner, self.delegate);
+        }
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::withdraw_delegate_rewards].
+pub struct WithdrawDelegateRewardsEvent {
+    /// The [Delegate].
+    #[index]
+    pub delegate: Pubkey,
+    /// Mint of the tokens withdrawn, if any.
+    pub token_mint: Option<Pubkey>,
+    /// Amount of tokens withdrawn.
+    pub amount: u64,
+    /// Lamports withdrawn.
+    pub lamports: u64,
+}
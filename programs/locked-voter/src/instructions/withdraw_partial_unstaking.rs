@@ -57,9 +57,7 @@ impl<'info> WithdrawPartialUnstaking<'info> {
 
         // update the locker
         let locker = &mut self.locker;
-        locker.locked_supply = unwrap_int!(locker
-            .locked_supply
-            .checked_sub(self.partial_unstake.amount));
+        unwrap_int!(locker.record_withdrawal(&self.escrow, self.partial_unstake.amount));
 
         unwrap_int!(self
             .escrow
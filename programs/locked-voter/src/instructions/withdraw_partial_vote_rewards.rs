@@ -0,0 +1,125 @@
+use crate::*;
+use anchor_lang::system_program;
+use anchor_spl::token;
+
+/// Accounts for [voter::withdraw_partial_vote_rewards].
+#[derive(Accounts)]
+#[instruction(index: u8)]
+pub struct WithdrawPartialVoteRewards<'info> {
+    /// The [Escrow].
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The owner of the [Escrow], who receives what the voter holds.
+    #[account(mut)]
+    pub escrow_owner: Signer<'info>,
+    /// The voter of the [Vote]s cast by the partial vote delegate at `index`, which receives
+    /// their rewards and the rent of their accounts once closed.
+    #[account(
+        mut,
+        seeds = [
+            b"PartialVoteDelegate".as_ref(),
+            escrow.key().as_ref(),
+            [index].as_ref()
+        ],
+        bump
+    )]
+    pub partial_vote_voter: SystemAccount<'info>,
+    /// Token account of the voter holding rewards, if any.
+    #[account(mut)]
+    pub voter_tokens: Option<Account<'info, TokenAccount>>,
+    /// Destination of the rewards.
+    #[account(mut)]
+    pub destination_tokens: Option<Account<'info, TokenAccount>>,
+
+    /// Token program.
+    pub token_program: Program<'info, Token>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawPartialVoteRewards<'info> {
+    pub fn withdraw_partial_vote_rewards(&mut self, bump: u8, index: u8) -> Result<()> {
+        let escrow = self.escrow.key();
+        let seeds: &[&[&[u8]]] = &[&[
+            b"PartialVoteDelegate".as_ref(),
+            escrow.as_ref(),
+            &[index],
+            &[bump],
+        ]];
+
+        let mut amount = 0;
+        if let (Some(voter_tokens), Some(destination_tokens)) =
+            (&self.voter_tokens, &self.destination_tokens)
+        {
+            amount = voter_tokens.amount;
+            if amount > 0 {
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        self.token_program.to_account_info(),
+                        token::Transfer {
+                            from: voter_tokens.to_account_info(),
+                            to: destination_tokens.to_account_info(),
+                            authority: self.partial_vote_voter.to_account_info(),
+                        },
+                        seeds,
+                    ),
+                    amount,
+                )?;
+            }
+        }
+
+        let lamports = self.partial_vote_voter.lamports();
+        if lamports > 0 {
+            system_program::transfer(
+                CpiContext::new_with_signer(
+                    self.system_program.to_account_info(),
+                    system_program::Transfer {
+                        from: self.partial_vote_voter.to_account_info(),
+                        to: self.escrow_owner.to_account_info(),
+                    },
+                    seeds,
+                ),
+                lamports,
+            )?;
+        }
+
+        emit!(WithdrawPartialVoteRewardsEvent {
+            escrow,
+            voter: self.partial_vote_voter.key(),
+            token_mint: self.voter_tokens.as_ref().map(|tokens| tokens.mint),
+            amount,
+            lamports,
+        });
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for WithdrawPartialVoteRewards<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.owner, self.escrow_owner);
+        invariant!(
+            self.voter_tokens.is_some() == self.destination_tokens.is_some(),
+            "voter and destination tokens must be given together"
+        );
+        if let Some(voter_tokens) = &self.voter_tokens {
+            assert_keys_eq!(voter_tokens.owner, self.partial_vote_voter);
+        }
+        Ok(())
+    }
+}
+
+#[event]
+/// Event called in [voter::withdraw_partial_vote_rewards].
+pub struct WithdrawPartialVoteRewardsEvent {
+    /// The [Escrow].
+    #[index]
+    pub escrow: Pubkey,
+    /// The voter of the partial vote delegate.
+    pub voter: Pubkey,
+    /// Mint of the tokens withdrawn, if any.
+    pub token_mint: Option<Pubkey>,
+    /// Amount of tokens withdrawn.
+    pub amount: u64,
+    /// Lamports withdrawn.
+    pub lamports: u64,
+}
@@ -10,6 +10,7 @@ use anchor_spl::token::{Mint, Token, TokenAccount};
 use govern::{Governor, Proposal, Vote};
 use vipers::prelude::*;
 
+pub mod checkpoints;
 mod instructions;
 pub mod locker;
 mod state;
@@ -41,6 +42,32 @@ pub mod locked_voter {
         ctx.accounts.new_escrow(unwrap_bump!(ctx, "escrow"))
     }
 
+    /// Creates the [EscrowCheckpoints] recording the voting power history of an [Escrow].
+    /// Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_escrow_checkpoints(ctx: Context<NewEscrowCheckpoints>) -> Result<()> {
+        ctx.accounts
+            .new_escrow_checkpoints(unwrap_bump!(ctx, "escrow_checkpoints"))
+    }
+
+    /// Creates the [LockerSlopeChanges] tracking the total voting power of a [Locker].
+    /// Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_locker_slope_changes(ctx: Context<NewLockerSlopeChanges>) -> Result<()> {
+        ctx.accounts
+            .new_locker_slope_changes(unwrap_bump!(ctx, "slope_changes"))
+    }
+
+    /// Counts the [Escrow]s given as remaining accounts in the total voting power of their [Locker],
+    /// for escrows created before it was tracked. Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn sync_escrows_voting_power<'info>(
+        ctx: Context<'_, '_, '_, 'info, SyncEscrowsVotingPower<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .sync_escrows_voting_power(ctx.remaining_accounts)
+    }
+
     /// increase locked amount [Escrow].
     #[access_control(ctx.accounts.validate())]
     pub fn increase_locked_amount<'info>(
@@ -74,16 +101,66 @@ pub mod locked_voter {
         ctx.accounts.withdraw()
     }
 
-    /// Activates a proposal in token launch phase
+    /// Creates a proposal on behalf of an [Escrow] owner with at least
+    /// [LockerParams::proposal_activation_min_votes] of voting power, so that any
+    /// token holder can propose.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_type: u8,
+        max_option: u8,
+        instructions: Vec<govern::ProposalInstruction>,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_proposal(proposal_type, max_option, instructions)
+    }
+
+    /// Activates a proposal in token launch phase.
+    /// A proposal with a [govern::GovernanceParameters::quorum_bps] needs every [Escrow] synced first.
     #[access_control(ctx.accounts.validate())]
     pub fn activate_proposal(ctx: Context<ActivateProposal>) -> Result<()> {
         ctx.accounts.activate_proposal()
     }
 
-    /// Casts a vote.
+    /// Casts a vote, with the voting power the [Escrow] had when the proposal was activated.
+    /// The owner of the [Escrow] can always vote, over their delegate.
     #[access_control(ctx.accounts.validate())]
-    pub fn cast_vote(ctx: Context<CastVote>, side: u8) -> Result<()> {
-        ctx.accounts.cast_vote(side)
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVote<'info>>,
+        side: u8,
+    ) -> Result<()> {
+        ctx.accounts.cast_vote(
+            unwrap_bump!(ctx, "delegated_vote"),
+            ctx.remaining_accounts,
+            side,
+        )
+    }
+
+    /// Casts a vote on an approval or ranked-choice proposal.
+    /// Choices are given in order of preference; an empty list is an abstain vote.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_vote_choices<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastVote<'info>>,
+        choices: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.cast_vote_choices(
+            unwrap_bump!(ctx, "delegated_vote"),
+            ctx.remaining_accounts,
+            choices,
+        )
+    }
+
+    /// Casts votes signed off-chain by the vote delegates of escrows, in a batch.
+    /// The signatures must be checked by ed25519 program instructions in the same transaction.
+    /// Remaining accounts are an [Escrow], its [EscrowCheckpoints], its [Vote] and its
+    /// [DelegatedVote] for each vote; missing [Vote]s are created.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_signed_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastSignedVotes<'info>>,
+        votes: Vec<SignedVote>,
+    ) -> Result<()> {
+        ctx.accounts
+            .cast_signed_votes(ctx.remaining_accounts, votes)
     }
 
     /// Delegate escrow vote.
@@ -92,6 +169,79 @@ pub mod locked_voter {
         ctx.accounts.set_vote_delegate(new_delegate)
     }
 
+    /// Delegates a share of the escrow voting power, in bps, to a partial vote delegate.
+    /// [Escrow::vote_delegate] votes with the rest.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_partial_vote_delegate(
+        ctx: Context<SetPartialVoteDelegate>,
+        index: u8,
+        delegate: Pubkey,
+        weight_bps: u16,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_partial_vote_delegate(index, delegate, weight_bps)
+    }
+
+    /// Withdraws the rewards and lamports held by the voter of the partial vote delegate
+    /// at `index` to the owner of the [Escrow].
+    /// Rewards are claimed to the voter with [govern::claim_reward_for_voter].
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_partial_vote_rewards(
+        ctx: Context<WithdrawPartialVoteRewards>,
+        index: u8,
+    ) -> Result<()> {
+        ctx.accounts
+            .withdraw_partial_vote_rewards(unwrap_bump!(ctx, "partial_vote_voter"), index)
+    }
+
+    /// Creates a [Delegate] with a profile, which votes once per proposal
+    /// for the escrows delegating to it.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_delegate(ctx: Context<NewDelegate>, name: String, url: String) -> Result<()> {
+        ctx.accounts
+            .new_delegate(unwrap_bump!(ctx, "delegate"), name, url)
+    }
+
+    /// Sets the profile of a [Delegate].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_delegate_profile(
+        ctx: Context<SetDelegateProfile>,
+        name: String,
+        url: String,
+    ) -> Result<()> {
+        ctx.accounts.set_delegate_profile(name, url)
+    }
+
+    /// Withdraws the rewards and lamports held by a [Delegate] to its authority.
+    /// Rewards are claimed to the delegate with [govern::claim_reward_for_voter].
+    #[access_control(ctx.accounts.validate())]
+    pub fn withdraw_delegate_rewards(ctx: Context<WithdrawDelegateRewards>) -> Result<()> {
+        ctx.accounts.withdraw_delegate_rewards()
+    }
+
+    /// Casts the vote of a [Delegate] with the voting power added so far.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_delegate_vote(ctx: Context<CastDelegateVote>, side: u8) -> Result<()> {
+        ctx.accounts.cast_delegate_vote(side)
+    }
+
+    /// Casts the vote of a [Delegate] on an approval or ranked-choice proposal.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_delegate_vote_choices(
+        ctx: Context<CastDelegateVote>,
+        choices: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.cast_delegate_vote_choices(choices)
+    }
+
+    /// Adds the voting power of an escrow to the cast vote of its [Delegate], once per proposal.
+    /// Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn add_delegated_vote(ctx: Context<AddDelegatedVote>) -> Result<()> {
+        ctx.accounts
+            .add_delegated_vote(unwrap_bump!(ctx, "delegated_vote"))
+    }
+
     /// Set locker params.
     #[access_control(ctx.accounts.validate())]
     pub fn set_locker_params(ctx: Context<SetLockerParams>, params: LockerParams) -> Result<()> {
@@ -154,4 +304,32 @@ pub enum ErrorCode {
     PartialUnstakingAmountIsNotZero,
     #[msg("Partial unstaking has not ended")]
     PartialUnstakingIsNotEnded,
+    #[msg("Signed vote nonce must be greater than the last one")]
+    InvalidVoteNonce,
+    #[msg("Vote is not signed by the vote delegate")]
+    VoteNotSigned,
+    #[msg("Voting power checkpoint was overwritten")]
+    VotingPowerCheckpointNotFound,
+    #[msg("Max stake duration is too long to track the total voting power")]
+    MaxStakeDurationTooLong,
+    #[msg("Invalid partial vote delegate index")]
+    InvalidPartialVoteDelegateIndex,
+    #[msg("Partial vote delegate weights must not sum to more than the full voting power")]
+    PartialVoteDelegateWeightsTooHigh,
+    #[msg("Signer is not a partial vote delegate voting as this voter")]
+    NotPartialVoteDelegate,
+    #[msg("Delegate name is too long")]
+    DelegateNameTooLong,
+    #[msg("Delegate URL is too long")]
+    DelegateUrlTooLong,
+    #[msg("Delegate must vote before voting power is added to its vote")]
+    DelegateHasNotVoted,
+    #[msg("Escrow has already voted on the proposal")]
+    EscrowHasVoted,
+    #[msg("Escrow voting power was added to the vote of its delegate")]
+    EscrowVoteDelegated,
+    #[msg("Escrow owner voted on the proposal over their delegate")]
+    OwnerHasVoted,
+    #[msg("Escrows must be synced before the total voting power can set a quorum")]
+    VotingPowerNotSynced,
 }
@@ -20,3 +20,16 @@ macro_rules! escrow_seeds {
         ]]
     };
 }
+
+/// Generates the signer seeds for a [crate::Delegate].
+#[macro_export]
+macro_rules! delegate_seeds {
+    ($delegate: expr) => {
+        &[&[
+            b"Delegate" as &[u8],
+            &$delegate.locker.as_ref(),
+            &$delegate.authority.as_ref(),
+            &[$delegate.bump],
+        ]]
+    };
+}
@@ -21,8 +21,20 @@ pub struct Locker {
     pub governor: Pubkey,
     /// Mutable parameters of how a [Locker] should behave.
     pub params: LockerParams,
+    /// Sum over the decaying locks of their amount times the seconds until their end,
+    /// at [Locker::voting_power_updated_at].
+    pub voting_power_bias: u128,
+    /// Sum of the amounts of the decaying locks, by which [Locker::voting_power_bias] decreases each second.
+    pub voting_power_slope: u64,
+    /// Total number of tokens in max locked [Escrow]s, whose voting power does not decay.
+    pub max_locked_supply: u64,
+    /// When [Locker::voting_power_bias] and [Locker::voting_power_slope] were last updated.
+    pub voting_power_updated_at: i64,
+    /// Total number of tokens locked in the [Escrow]s counted in the total voting power.
+    /// Escrows created before it was tracked are only counted once synced.
+    pub tracked_locked_supply: u64,
     /// buffer for further use
-    pub buffers: [u128; 32],
+    pub buffers: [u128; 29],
 }
 
 impl Locker {
@@ -32,7 +44,12 @@ impl Locker {
         + 8
         + 8
         + std::mem::size_of::<LockerParams>()
-        + 16 * 32;
+        + 16
+        + 8
+        + 8
+        + 8
+        + 8
+        + 16 * 29;
 }
 
 /// Contains parameters for the [Locker].
@@ -46,6 +63,7 @@ pub struct LockerParams {
     /// Maximum staking duration.
     pub max_stake_duration: u64,
     /// Minimum number of votes required to activate a proposal.
+    /// This is also the voting power an [Escrow] needs to create a proposal.
     pub proposal_activation_min_votes: u64,
 }
 
@@ -77,16 +95,76 @@ pub struct Escrow {
     pub is_max_lock: bool,
     /// total amount of partial unstaking amount
     pub partial_unstaking_amount: u64,
-    /// padding for further use
-    pub padding: u64,
+    /// Nonce of the last signed vote settled by [crate::locked_voter::cast_signed_votes].
+    pub vote_nonce: u64,
+    /// Amount of tokens the [Escrow] counts for in the total voting power of the [Locker].
+    pub tracked_amount: u64,
+    /// End of the lock the [Escrow] counts for in the total voting power of the [Locker],
+    /// rounded up to a [SLOPE_CHANGE_EPOCH]. Zero if it does not decay.
+    pub tracked_ends_at: i64,
+    /// Whether the [Escrow] counts as max locked in the total voting power of the [Locker].
+    pub tracked_is_max_lock: bool,
+    /// Weight in bps of the voting power of the [Escrow] that each of [Escrow::partial_vote_delegates] votes with.
+    pub partial_vote_delegate_weights: [u16; 3],
+    /// Whether the [Escrow] is counted in the total voting power of the [Locker].
+    pub is_tracked: bool,
+    /// padding
+    pub tracked_padding: [u8; 8],
+    /// Accounts authorized to vote with a share of the voting power of this [Escrow].
+    /// [Escrow::vote_delegate] votes with the rest.
+    pub partial_vote_delegates: [Pubkey; 3],
     /// buffer for further use
-    pub buffers: [u128; 9],
+    pub buffers: [u128; 1],
 }
 
 impl Escrow {
     /// LEN of escrow
     pub const LEN: usize = std::mem::size_of::<Pubkey>() * 4 + 1 + 8 + 8 + 8 + 1 + 16 * 10;
 
+    /// The voter of the [Vote]s cast by the partial vote delegate at an index of an [Escrow].
+    pub fn partial_vote_voter(escrow: &Pubkey, index: usize) -> Pubkey {
+        let (voter, _bump) = Pubkey::find_program_address(
+            &[
+                b"PartialVoteDelegate".as_ref(),
+                escrow.as_ref(),
+                &[index as u8],
+            ],
+            &crate::ID,
+        );
+        voter
+    }
+
+    /// Whether `signer` voting as `voter` is the owner of the [Escrow] voting themselves,
+    /// which takes priority over a vote of their delegate, whoever it is now.
+    pub fn is_owner_vote(&self, signer: &Pubkey, voter: &Pubkey) -> bool {
+        *signer == self.owner && *voter == self.owner
+    }
+
+    /// The index of the partial vote delegate `delegate` voting as `voter`, if any.
+    pub fn partial_vote_delegate_index(
+        &self,
+        escrow: &Pubkey,
+        delegate: &Pubkey,
+        voter: &Pubkey,
+    ) -> Option<usize> {
+        (0..MAX_PARTIAL_VOTE_DELEGATES).find(|index| {
+            self.partial_vote_delegates[*index] == *delegate
+                && Escrow::partial_vote_voter(escrow, *index) == *voter
+        })
+    }
+
+    /// The share in bps of the voting power of the [Escrow] that a vote delegate votes with:
+    /// the weight of the partial vote delegate at an index, or the rest for [Escrow::vote_delegate].
+    pub fn vote_share_bps(&self, partial_vote_delegate: Option<usize>) -> Option<u16> {
+        match partial_vote_delegate {
+            Some(index) => self.partial_vote_delegate_weights.get(index).copied(),
+            None => self
+                .partial_vote_delegate_weights
+                .iter()
+                .try_fold(govern::MAX_BPS, |rest, weight| rest.checked_sub(*weight)),
+        }
+    }
+
     /// Gets the amount of voting power the [Escrow] will have at the given time.
     pub fn voting_power_at_time(&self, locker: &Locker, timestamp: i64) -> Option<u64> {
         locker.calculate_voter_power(self, timestamp)
@@ -108,6 +186,10 @@ impl Escrow {
     ) -> Result<()> {
         self.amount = unwrap_int!(self.amount.checked_add(lock_amount));
         locker.locked_supply = unwrap_int!(locker.locked_supply.checked_add(lock_amount));
+        if self.is_tracked {
+            locker.tracked_locked_supply =
+                unwrap_int!(locker.tracked_locked_supply.checked_add(lock_amount));
+        }
         Ok(())
     }
 
@@ -158,6 +240,80 @@ impl Escrow {
     }
 }
 
+/// Number of [Escrow::partial_vote_delegates].
+pub const MAX_PARTIAL_VOTE_DELEGATES: usize = 3;
+
+/// Number of [EscrowCheckpoint]s kept by [EscrowCheckpoints].
+pub const MAX_ESCROW_CHECKPOINTS: usize = 32;
+
+/// What the voting power of an [Escrow] depends on, from when it changed.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EscrowCheckpoint {
+    /// When the [Escrow] changed.
+    pub timestamp: i64,
+    /// [Escrow::amount].
+    pub amount: u64,
+    /// [Escrow::escrow_started_at].
+    pub escrow_started_at: i64,
+    /// [Escrow::escrow_ends_at].
+    pub escrow_ends_at: i64,
+    /// [Escrow::is_max_lock].
+    pub is_max_lock: bool,
+    /// [Escrow::partial_vote_delegate_weights].
+    pub partial_vote_delegate_weights: [u16; 3],
+}
+
+impl EscrowCheckpoint {
+    /// LEN of EscrowCheckpoint
+    pub const LEN: usize = 8 * 4 + 1 + 2 * MAX_PARTIAL_VOTE_DELEGATES;
+}
+
+/// Voting power history of an [Escrow], so that votes use the power the escrow had
+/// when the proposal was activated.
+#[account]
+#[derive(Debug, Default)]
+pub struct EscrowCheckpoints {
+    /// The [Escrow].
+    pub escrow: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Number of checkpoints ever recorded. Only the last [MAX_ESCROW_CHECKPOINTS] are kept.
+    pub checkpoint_count: u64,
+    /// The checkpoints, in a ring of [MAX_ESCROW_CHECKPOINTS].
+    pub checkpoints: Vec<EscrowCheckpoint>,
+}
+
+impl EscrowCheckpoints {
+    /// LEN of EscrowCheckpoints
+    pub const LEN: usize =
+        std::mem::size_of::<Pubkey>() + 1 + 8 + 4 + EscrowCheckpoint::LEN * MAX_ESCROW_CHECKPOINTS;
+}
+
+/// Length of the epochs by which [LockerSlopeChanges] groups the ends of locks.
+pub const SLOPE_CHANGE_EPOCH: i64 = 7 * 24 * 3600;
+/// Number of epochs [LockerSlopeChanges] schedules slope changes for.
+pub const MAX_SLOPE_CHANGES: usize = 256;
+/// Maximum [LockerParams::max_stake_duration], so that every lock ends within [MAX_SLOPE_CHANGES] epochs.
+pub const MAX_STAKE_DURATION: u64 = (MAX_SLOPE_CHANGES as u64 - 1) * SLOPE_CHANGE_EPOCH as u64;
+
+/// The decreases of the total voting power slope of a [Locker], scheduled at the end of
+/// the epochs in which locks end.
+#[account]
+#[derive(Debug, Default)]
+pub struct LockerSlopeChanges {
+    /// The [Locker].
+    pub locker: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Amount of tokens whose lock ends at the end of each epoch, in a ring of [MAX_SLOPE_CHANGES] epochs.
+    pub slope_changes: Vec<u64>,
+}
+
+impl LockerSlopeChanges {
+    /// LEN of LockerSlopeChanges
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() + 1 + 4 + 8 * MAX_SLOPE_CHANGES;
+}
+
 /// Account to store infor for partial unstaking
 #[account]
 #[derive(Debug, Default)]
@@ -179,6 +335,74 @@ impl PartialUnstaking {
     pub const LEN: usize = std::mem::size_of::<Pubkey>() + 8 + 8 + 16 * 6;
 }
 
+/// Max length of [Delegate::name].
+pub const MAX_DELEGATE_NAME_LEN: usize = 32;
+/// Max length of [Delegate::url].
+pub const MAX_DELEGATE_URL_LEN: usize = 128;
+
+/// A vote delegate of a [Locker] with an on-chain profile.
+/// It votes once per [Proposal] with the voting power of all [Escrow]s whose
+/// [Escrow::vote_delegate] is this account.
+#[account]
+#[derive(Debug, Default)]
+pub struct Delegate {
+    /// The [Locker].
+    pub locker: Pubkey,
+    /// The account casting the votes of the [Delegate].
+    pub authority: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// buffer for further use
+    pub buffers: [u128; 4],
+    /// Name of the [Delegate].
+    pub name: String,
+    /// URL of the profile of the [Delegate].
+    pub url: String,
+}
+
+impl Delegate {
+    /// LEN of Delegate
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 2
+        + 1
+        + 16 * 4
+        + 4
+        + MAX_DELEGATE_NAME_LEN
+        + 4
+        + MAX_DELEGATE_URL_LEN;
+}
+
+/// The voting power of an [Escrow] added to the [Vote] of a [Delegate] on a [Proposal].
+/// An [Escrow] is added at most once per [Proposal], and not after its owner voted on it.
+#[account]
+#[derive(Copy, Debug, Default)]
+pub struct DelegatedVote {
+    /// The [Proposal].
+    pub proposal: Pubkey,
+    /// The [Escrow].
+    pub escrow: Pubkey,
+    /// The [Delegate] the voting power was added to.
+    pub delegate: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// The voting power added, or 0 once the owner of the [Escrow] voted instead.
+    pub voting_power: u64,
+    /// Whether the owner of the [Escrow] voted on the [Proposal] over its vote delegate.
+    pub owner_voted: bool,
+}
+
+impl DelegatedVote {
+    /// LEN of DelegatedVote
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() * 3 + 1 + 8 + 1;
+
+    /// Records that the owner of the [Escrow] voted, returning the voting power to remove
+    /// from the vote of [DelegatedVote::delegate]. This does not depend on the current vote
+    /// delegate of the escrow, which may have changed since the voting power was added.
+    pub fn record_owner_vote(&mut self) -> u64 {
+        self.owner_voted = true;
+        std::mem::take(&mut self.voting_power)
+    }
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -378,4 +602,380 @@ mod tests {
         assert_eq!(locker.locked_supply, 0);
         assert_escrow(&locker, bob, current_ts, 0.0);
     }
+
+    #[test]
+    fn test_voting_power_checkpoints() {
+        let locker = &mut Locker {
+            params: LockerParams {
+                max_stake_duration: MAX_TIME as u64,
+                max_stake_vote_multiplier: DEFAULT_STAKE_MULTIPLIER,
+                ..LockerParams::default()
+            },
+            ..Locker::default()
+        };
+        let alice = &mut Escrow::default();
+        let checkpoints = &mut EscrowCheckpoints::default();
+
+        let t0 = CANONICAL_START_TIME;
+        checkpoints.record(alice, t0).unwrap();
+        alice
+            .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        alice.is_max_lock = true;
+        checkpoints.record(alice, t0 + HOUR).unwrap();
+
+        // tokens locked afterwards do not count
+        alice
+            .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        checkpoints.record(alice, t0 + DAY).unwrap();
+
+        assert_eq!(checkpoints.voting_power_at_time(locker, t0 - 1), Some(0));
+        assert_eq!(checkpoints.voting_power_at_time(locker, t0), Some(0));
+        assert_eq!(
+            checkpoints.voting_power_at_time(locker, t0 + HOUR),
+            Some(DEFAULT_LOCK_AMOUNT)
+        );
+        assert_eq!(
+            checkpoints.voting_power_at_time(locker, t0 + DAY - 1),
+            Some(DEFAULT_LOCK_AMOUNT)
+        );
+        assert_eq!(
+            checkpoints.voting_power_at_time(locker, t0 + DAY),
+            Some(2 * DEFAULT_LOCK_AMOUNT)
+        );
+
+        // changes in the same second share a checkpoint
+        alice.is_max_lock = false;
+        checkpoints.record(alice, t0 + DAY).unwrap();
+        assert_eq!(checkpoints.checkpoint_count, 3);
+        assert_eq!(checkpoints.voting_power_at_time(locker, t0 + DAY), Some(0));
+
+        // once the ring is full, the oldest checkpoints are overwritten
+        for i in 1..=MAX_ESCROW_CHECKPOINTS as i64 {
+            checkpoints.record(alice, t0 + DAY + i).unwrap();
+        }
+        assert_eq!(checkpoints.checkpoints.len(), MAX_ESCROW_CHECKPOINTS);
+        assert_eq!(checkpoints.voting_power_at_time(locker, t0 + HOUR), None);
+        assert_eq!(
+            checkpoints.voting_power_at_time(locker, t0 + 2 * DAY),
+            Some(0)
+        );
+        assert!(
+            checkpoints.try_to_vec().unwrap().len() <= EscrowCheckpoints::LEN,
+            "checkpoints must fit"
+        );
+
+        // seeded checkpoints hold for any earlier time
+        alice.is_max_lock = true;
+        let seeded = &mut EscrowCheckpoints::default();
+        seeded.seed(alice).unwrap();
+        assert_eq!(
+            seeded.voting_power_at_time(locker, t0),
+            Some(2 * DEFAULT_LOCK_AMOUNT)
+        );
+    }
+
+    #[test]
+    fn test_total_voting_power() {
+        let locker = &mut Locker {
+            params: LockerParams {
+                max_stake_duration: MAX_TIME as u64,
+                max_stake_vote_multiplier: 10,
+                ..LockerParams::default()
+            },
+            ..Locker::default()
+        };
+        let slope_changes = &mut LockerSlopeChanges {
+            slope_changes: vec![0; MAX_SLOPE_CHANGES],
+            ..LockerSlopeChanges::default()
+        };
+        let alice = &mut Escrow::default();
+        let bob = &mut Escrow::default();
+        let carol = &mut Escrow::default();
+
+        // voting power of a lock over a week, at the most
+        let week_of_decay = 10 * DEFAULT_LOCK_AMOUNT / (MAX_TIME / WEEK) as u64;
+        // the total is at least the sum of the escrows, by less than a week of decay each
+        let assert_total =
+            |locker: &Locker, slope_changes: &LockerSlopeChanges, escrows: &[&Escrow], ts: i64| {
+                let total = locker
+                    .total_voting_power_at_time(slope_changes, ts)
+                    .unwrap();
+                let sum: u64 = escrows
+                    .iter()
+                    .map(|escrow| escrow.voting_power_at_time(locker, ts).unwrap())
+                    .sum();
+                let max_error = escrows.len() as u64 * week_of_decay;
+                assert!(
+                    total >= sum && total <= sum + max_error,
+                    "total: {}, sum: {}, ts: {}",
+                    total,
+                    sum,
+                    ts
+                );
+            };
+
+        let mut current_ts = CANONICAL_START_TIME;
+        assert_eq!(
+            locker.total_voting_power_at_time(slope_changes, current_ts),
+            Some(0)
+        );
+
+        // Alice locks for a week, Bob for a year and Carol max locks
+        for (escrow, duration) in [(&mut *alice, WEEK), (&mut *bob, DAYS_PER_YEAR * DAY)] {
+            escrow
+                .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+                .unwrap();
+            escrow
+                .record_extend_lock_duration_event(current_ts, current_ts + duration)
+                .unwrap();
+            locker
+                .track_escrow_voting_power(slope_changes, escrow, current_ts)
+                .unwrap();
+        }
+        carol
+            .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        carol.is_max_lock = true;
+        locker
+            .track_escrow_voting_power(slope_changes, carol, current_ts)
+            .unwrap();
+
+        for _ in 0..60 {
+            current_ts += DAY;
+            assert_total(locker, slope_changes, &[alice, bob, carol], current_ts);
+        }
+
+        // Bob doubles his lock and Carol stops max locking
+        bob.record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        locker
+            .track_escrow_voting_power(slope_changes, bob, current_ts)
+            .unwrap();
+        carol.is_max_lock = false;
+        carol
+            .record_extend_lock_duration_event(current_ts, current_ts + MAX_TIME)
+            .unwrap();
+        locker
+            .track_escrow_voting_power(slope_changes, carol, current_ts)
+            .unwrap();
+        let max_error = 3 * week_of_decay;
+        for _ in 0..400 {
+            current_ts += DAY;
+            let total = locker
+                .total_voting_power_at_time(slope_changes, current_ts)
+                .unwrap();
+            let sum = alice.voting_power_at_time(locker, current_ts).unwrap()
+                + bob.voting_power_at_time(locker, current_ts).unwrap()
+                + carol.voting_power_at_time(locker, current_ts).unwrap();
+            assert!(total >= sum && total <= sum + max_error);
+        }
+        // the powers of Alice and Bob are gone
+        let carol_power =
+            (carol.tracked_ends_at - current_ts) as u128 * 10 * DEFAULT_LOCK_AMOUNT as u128
+                / MAX_TIME as u128;
+        assert_eq!(
+            locker.total_voting_power_at_time(slope_changes, current_ts),
+            Some(carol_power as u64)
+        );
+
+        // Carol exits after her lock ended, long after the last update
+        current_ts += MAX_TIME + 10 * WEEK;
+        assert_eq!(
+            locker.total_voting_power_at_time(slope_changes, current_ts),
+            Some(0)
+        );
+        carol.amount = 0;
+        locker
+            .track_escrow_voting_power(slope_changes, carol, current_ts)
+            .unwrap();
+        assert_eq!(locker.voting_power_bias, 0);
+        assert_eq!(locker.voting_power_slope, 0);
+        assert!(slope_changes
+            .slope_changes
+            .iter()
+            .all(|change| *change == 0));
+        assert!(
+            slope_changes.try_to_vec().unwrap().len() <= LockerSlopeChanges::LEN,
+            "slope changes must fit"
+        );
+
+        // the total is not known before the last update
+        assert_eq!(
+            locker.total_voting_power_at_time(slope_changes, current_ts - 1),
+            None
+        );
+    }
+
+    #[test]
+    fn test_tracked_locked_supply() {
+        let locker = &mut Locker {
+            params: LockerParams {
+                max_stake_duration: MAX_TIME as u64,
+                max_stake_vote_multiplier: 10,
+                ..LockerParams::default()
+            },
+            ..Locker::default()
+        };
+        let slope_changes = &mut LockerSlopeChanges {
+            slope_changes: vec![0; MAX_SLOPE_CHANGES],
+            ..LockerSlopeChanges::default()
+        };
+        let current_ts = CANONICAL_START_TIME;
+
+        // Alice locked before the total voting power was tracked
+        let alice = &mut Escrow::default();
+        alice
+            .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        alice.partial_unstaking_amount = DEFAULT_LOCK_AMOUNT;
+        locker.locked_supply += DEFAULT_LOCK_AMOUNT;
+        alice
+            .record_extend_lock_duration_event(current_ts, current_ts + DAYS_PER_YEAR * DAY)
+            .unwrap();
+        let bob = &mut Escrow {
+            is_tracked: true,
+            ..Escrow::default()
+        };
+        bob.record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        bob.is_max_lock = true;
+        locker
+            .track_escrow_voting_power(slope_changes, bob, current_ts)
+            .unwrap();
+        assert_eq!(locker.tracked_locked_supply, DEFAULT_LOCK_AMOUNT);
+        assert!(!locker.is_voting_power_synced());
+
+        // syncing Alice counts her partial unstaking too, once
+        locker
+            .track_escrow_voting_power(slope_changes, alice, current_ts)
+            .unwrap();
+        locker
+            .track_escrow_voting_power(slope_changes, alice, current_ts)
+            .unwrap();
+        assert_eq!(locker.tracked_locked_supply, 3 * DEFAULT_LOCK_AMOUNT);
+        assert!(locker.is_voting_power_synced());
+
+        // withdrawals keep it synced
+        locker
+            .record_withdrawal(alice, alice.partial_unstaking_amount)
+            .unwrap();
+        alice.partial_unstaking_amount = 0;
+        locker.record_withdrawal(bob, bob.amount).unwrap();
+        assert_eq!(locker.tracked_locked_supply, DEFAULT_LOCK_AMOUNT);
+        assert!(locker.is_voting_power_synced());
+    }
+
+    #[test]
+    fn test_partial_vote_delegation() {
+        let locker = &mut Locker {
+            params: LockerParams {
+                max_stake_duration: MAX_TIME as u64,
+                max_stake_vote_multiplier: DEFAULT_STAKE_MULTIPLIER,
+                ..LockerParams::default()
+            },
+            ..Locker::default()
+        };
+        let alice = &mut Escrow::default();
+        let checkpoints = &mut EscrowCheckpoints::default();
+        alice
+            .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+            .unwrap();
+        alice.is_max_lock = true;
+
+        let t0 = CANONICAL_START_TIME;
+        alice.partial_vote_delegate_weights = [2_500, 0, 1_000];
+        checkpoints.record(alice, t0).unwrap();
+        assert_eq!(alice.vote_share_bps(None), Some(6_500));
+        assert_eq!(alice.vote_share_bps(Some(0)), Some(2_500));
+        assert_eq!(alice.vote_share_bps(Some(MAX_PARTIAL_VOTE_DELEGATES)), None);
+
+        // the shares never add up to more than the full voting power
+        let shares: u64 = [None, Some(0), Some(1), Some(2)]
+            .into_iter()
+            .map(|index| {
+                checkpoints
+                    .delegated_voting_power_at_time(locker, index, t0)
+                    .unwrap()
+            })
+            .sum();
+        assert_eq!(shares, DEFAULT_LOCK_AMOUNT);
+        assert_eq!(
+            checkpoints.delegated_voting_power_at_time(locker, Some(0), t0),
+            Some(DEFAULT_LOCK_AMOUNT / 4)
+        );
+
+        // weights summing to more than the full voting power leave no valid share
+        alice.partial_vote_delegate_weights = [6_000, 5_000, 0];
+        assert_eq!(alice.vote_share_bps(None), None);
+
+        // weights changed afterwards do not count
+        alice.partial_vote_delegate_weights = [10_000, 0, 0];
+        checkpoints.record(alice, t0 + DAY).unwrap();
+        assert_eq!(
+            checkpoints.delegated_voting_power_at_time(locker, Some(0), t0 + DAY - 1),
+            Some(DEFAULT_LOCK_AMOUNT / 4)
+        );
+        assert_eq!(
+            checkpoints.delegated_voting_power_at_time(locker, None, t0 + DAY),
+            Some(0)
+        );
+
+        // each partial vote delegate votes as its own voter
+        let escrow = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        alice.partial_vote_delegates[0] = delegate;
+        let voter = Escrow::partial_vote_voter(&escrow, 0);
+        assert_ne!(voter, Escrow::partial_vote_voter(&escrow, 1));
+        assert_eq!(
+            alice.partial_vote_delegate_index(&escrow, &delegate, &voter),
+            Some(0)
+        );
+        assert_eq!(
+            alice.partial_vote_delegate_index(&escrow, &Pubkey::new_unique(), &voter),
+            None
+        );
+    }
+
+    #[test]
+    fn test_owner_vote_after_delegate_reset() {
+        let owner = Pubkey::new_unique();
+        let delegate = Pubkey::new_unique();
+        let alice = &mut Escrow {
+            owner,
+            vote_delegate: delegate,
+            ..Escrow::default()
+        };
+        let delegated_vote = &mut DelegatedVote {
+            delegate,
+            voting_power: DEFAULT_LOCK_AMOUNT,
+            ..DelegatedVote::default()
+        };
+
+        // the owner takes the delegation back after their voting power was added
+        alice.vote_delegate = owner;
+        assert!(alice.is_owner_vote(&owner, &owner));
+        assert!(!alice.is_owner_vote(&delegate, &owner));
+        assert_eq!(delegated_vote.record_owner_vote(), DEFAULT_LOCK_AMOUNT);
+        assert_eq!(delegated_vote.voting_power, 0);
+        assert!(delegated_vote.owner_voted);
+
+        // voting again removes nothing more
+        assert_eq!(delegated_vote.record_owner_vote(), 0);
+    }
+
+    #[test]
+    fn test_delegate_space() {
+        let delegate = Delegate {
+            name: "x".repeat(MAX_DELEGATE_NAME_LEN),
+            url: "x".repeat(MAX_DELEGATE_URL_LEN),
+            ..Default::default()
+        };
+        assert!(delegate.try_to_vec().unwrap().len() <= Delegate::LEN);
+        assert_eq!(
+            DelegatedVote::default().try_to_vec().unwrap().len(),
+            DelegatedVote::LEN
+        );
+    }
 }
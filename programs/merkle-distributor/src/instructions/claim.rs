@@ -65,6 +65,10 @@ pub struct Claim<'info> {
     /// CHECK: escrow_tokens
     #[account(mut)]
     pub escrow_tokens: UncheckedAccount<'info>,
+
+    /// CHECK: slope changes of the locker
+    #[account(mut)]
+    pub slope_changes: UncheckedAccount<'info>,
 }
 
 /// Claims tokens from the [MerkleDistributor].
@@ -121,6 +125,7 @@ pub fn handle_claim(
         ctx.accounts.voter_program.to_account_info(),
         voter::cpi::accounts::IncreaseLockedAmount {
             locker: ctx.accounts.locker.to_account_info(),
+            slope_changes: ctx.accounts.slope_changes.to_account_info(),
             escrow: ctx.accounts.escrow.to_account_info(),
             escrow_tokens: ctx.accounts.escrow_tokens.to_account_info(),
             payer: ctx.accounts.distributor.to_account_info(),
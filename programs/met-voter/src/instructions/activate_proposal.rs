@@ -5,11 +5,17 @@ use crate::*;
 pub struct ActivateProposal<'info> {
     /// The [Locker].
     pub locker: Box<Account<'info, Locker>>,
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
     /// The [Governor].
     pub governor: Box<Account<'info, Governor>>,
     /// The [Proposal].
     #[account(mut)]
     pub proposal: Box<Account<'info, Proposal>>,
+    /// The [govern::ProposalParams] of the [Proposal].
+    /// CHECK: checked by the [govern] program.
+    pub proposal_params: UncheckedAccount<'info>,
     /// The user's [Escrow].
     pub escrow: Box<Account<'info, Escrow>>,
     /// The [Escrow]'s owner.
@@ -29,6 +35,9 @@ impl<'info> ActivateProposal<'info> {
                 self.to_activate_proposal_accounts(),
             )
             .with_signer(seeds),
+            unwrap_int!(self
+                .locker
+                .total_voting_power_at_time(&self.slope_changes, Clock::get()?.unix_timestamp)),
         )?;
 
         Ok(())
@@ -39,6 +48,7 @@ impl<'info> ActivateProposal<'info> {
         govern::cpi::accounts::ActivateProposal {
             governor: self.governor.to_account_info(),
             proposal: self.proposal.to_account_info(),
+            proposal_params: self.proposal_params.to_account_info(),
             locker: self.locker.to_account_info(),
         }
     }
@@ -68,6 +78,10 @@ impl<'info> Validate<'info> for ActivateProposal<'info> {
             phase == Phase::TokenLaunchPhase,
             "must be token launch phase"
         );
+        // the total would leave out the escrows not synced yet
+        if self.proposal.quorum_bps > 0 {
+            invariant!(self.locker.is_voting_power_synced(), VotingPowerNotSynced);
+        }
 
         Ok(())
     }
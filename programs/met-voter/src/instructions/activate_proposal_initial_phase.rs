@@ -10,6 +10,9 @@ pub struct ActivateProposalInitialPhase<'info> {
     /// The [Proposal].
     #[account(mut)]
     pub proposal: Box<Account<'info, Proposal>>,
+    /// The [govern::ProposalParams] of the [Proposal].
+    /// CHECK: checked by the [govern] program.
+    pub proposal_params: UncheckedAccount<'info>,
     /// The [govern] program.
     pub govern_program: Program<'info, govern::program::Govern>,
     /// The smart wallet on the [Governor].
@@ -27,6 +30,7 @@ impl<'info> ActivateProposalInitialPhase<'info> {
                 self.to_activate_proposal_accounts(),
             )
             .with_signer(seeds),
+            unwrap_int!(self.locker.max_voting_power()),
         )?;
 
         Ok(())
@@ -37,6 +41,7 @@ impl<'info> ActivateProposalInitialPhase<'info> {
         govern::cpi::accounts::ActivateProposal {
             governor: self.governor.to_account_info(),
             proposal: self.proposal.to_account_info(),
+            proposal_params: self.proposal_params.to_account_info(),
             locker: self.locker.to_account_info(),
         }
     }
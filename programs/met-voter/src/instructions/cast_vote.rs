@@ -34,7 +34,37 @@ impl<'info> CastVote<'info> {
         }
 
         let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
-        let cpi_ctx = CpiContext::new(
+        govern::cpi::set_vote(
+            self.set_vote_context().with_signer(seeds),
+            side,
+            voting_power,
+        )?;
+        Ok(())
+    }
+
+    /// Casts a vote with choices in order of preference,
+    /// on an approval or ranked-choice proposal.
+    pub fn cast_vote_choices(&mut self, choices: Vec<u8>) -> Result<()> {
+        let voting_power = self.future_voting_power()?;
+
+        // zero votes should short circuit.
+        if voting_power == 0 {
+            return Ok(());
+        }
+
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        govern::cpi::set_vote_choices(
+            self.set_vote_context().with_signer(seeds),
+            choices,
+            voting_power,
+        )?;
+        Ok(())
+    }
+
+    fn set_vote_context(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, govern::cpi::accounts::SetVote<'info>> {
+        CpiContext::new(
             self.govern_program.to_account_info(),
             govern::cpi::accounts::SetVote {
                 governor: self.governor.to_account_info(),
@@ -43,10 +73,6 @@ impl<'info> CastVote<'info> {
                 locker: self.locker.to_account_info(),
             },
         )
-        .with_signer(seeds);
-
-        govern::cpi::set_vote(cpi_ctx, side, voting_power)?;
-        Ok(())
     }
 
     /// The voting power of the escrow at the time the proposal's voting ends.
@@ -0,0 +1,121 @@
+use crate::*;
+use govern::ProposalInstruction;
+
+/// Accounts for [voter::create_proposal].
+#[derive(Accounts)]
+pub struct CreateProposal<'info> {
+    /// The [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+    /// The [Escrow] of the proposer.
+    pub escrow: Box<Account<'info, Escrow>>,
+    /// The [Escrow::owner], proposing.
+    pub proposer: Signer<'info>,
+
+    /// The [Governor].
+    /// CHECK: checked by the [govern] program.
+    #[account(mut)]
+    pub governor: UncheckedAccount<'info>,
+    /// The [Proposal] to create.
+    /// CHECK: initialized by the [govern] program.
+    #[account(mut)]
+    pub proposal: UncheckedAccount<'info>,
+    /// The [govern::ProposalParams] of the [Proposal].
+    /// CHECK: initialized by the [govern] program.
+    #[account(mut)]
+    pub proposal_params: UncheckedAccount<'info>,
+    /// The smart wallet on the [Governor].
+    /// CHECK: checked by the [govern] program.
+    pub smart_wallet: UncheckedAccount<'info>,
+    /// Payer of the proposal.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// System program.
+    pub system_program: Program<'info, System>,
+
+    /// The proposal deposit mint, if a deposit is required.
+    /// CHECK: checked by the [govern] program.
+    pub deposit_mint: Option<UncheckedAccount<'info>>,
+    /// Escrow of the proposal deposit, if a deposit is required.
+    /// CHECK: initialized by the [govern] program.
+    #[account(mut)]
+    pub deposit_vault: Option<UncheckedAccount<'info>>,
+    /// Token account of the proposer paying the deposit, if a deposit is required.
+    /// CHECK: checked by the [govern] program.
+    #[account(mut)]
+    pub proposer_token_account: Option<UncheckedAccount<'info>>,
+    /// Token program, if a deposit is required.
+    pub token_program: Option<Program<'info, Token>>,
+    /// The proposal policy of the governor, if it has one.
+    /// CHECK: checked by the [govern] program.
+    pub proposal_policy: Option<UncheckedAccount<'info>>,
+    /// The proposal class picked by the proposer, if any.
+    /// CHECK: checked by the [govern] program.
+    pub proposal_class: Option<UncheckedAccount<'info>>,
+
+    /// Event authority of the [govern] program.
+    /// CHECK: checked by the [govern] program.
+    pub event_authority: UncheckedAccount<'info>,
+    /// The [govern] program.
+    pub govern_program: Program<'info, govern::program::Govern>,
+}
+
+impl<'info> CreateProposal<'info> {
+    /// Creates a proposal, signed by the locker once the proposer's voting power is checked.
+    pub fn create_proposal(
+        &self,
+        proposal_type: u8,
+        max_option: u8,
+        instructions: Vec<ProposalInstruction>,
+    ) -> Result<()> {
+        let seeds: &[&[&[u8]]] = locker_seeds!(self.locker);
+        govern::cpi::create_proposal(
+            CpiContext::new(
+                self.govern_program.to_account_info(),
+                self.to_create_proposal_accounts(),
+            )
+            .with_signer(seeds),
+            proposal_type,
+            max_option,
+            instructions,
+        )
+    }
+
+    /// Conversion.
+    fn to_create_proposal_accounts(&self) -> govern::cpi::accounts::CreateProposal<'info> {
+        govern::cpi::accounts::CreateProposal {
+            governor: self.governor.to_account_info(),
+            proposal: self.proposal.to_account_info(),
+            proposal_params: self.proposal_params.to_account_info(),
+            smart_wallet: self.smart_wallet.to_account_info(),
+            proposer: self.proposer.to_account_info(),
+            payer: self.payer.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            locker: Some(self.locker.to_account_info()),
+            deposit_mint: self.deposit_mint.as_ref().map(|a| a.to_account_info()),
+            deposit_vault: self.deposit_vault.as_ref().map(|a| a.to_account_info()),
+            proposer_token_account: self
+                .proposer_token_account
+                .as_ref()
+                .map(|a| a.to_account_info()),
+            token_program: self.token_program.as_ref().map(|a| a.to_account_info()),
+            proposal_policy: self.proposal_policy.as_ref().map(|a| a.to_account_info()),
+            proposal_class: self.proposal_class.as_ref().map(|a| a.to_account_info()),
+            event_authority: self.event_authority.to_account_info(),
+            program: self.govern_program.to_account_info(),
+        }
+    }
+}
+
+impl<'info> Validate<'info> for CreateProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.escrow.locker, self.locker);
+        assert_keys_eq!(self.escrow.owner, self.proposer);
+        assert_keys_eq!(self.locker.governor, self.governor);
+        invariant!(
+            self.escrow.voting_power(&self.locker)?
+                >= self.locker.params.proposal_activation_min_votes,
+            "insufficient voting power to create a proposal"
+        );
+        Ok(())
+    }
+}
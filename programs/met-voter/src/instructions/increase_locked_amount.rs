@@ -13,6 +13,10 @@ pub struct IncreaseLockedAmount<'info> {
     #[account(mut, has_one = locker)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// Token account held by the [Escrow].
     #[account(
         mut,
@@ -52,6 +56,11 @@ impl<'info> IncreaseLockedAmount<'info> {
         let locker = &mut self.locker;
         let escrow = &mut self.escrow;
         escrow.record_increase_locked_amount_event(locker, amount)?;
+        unwrap_int!(locker.track_escrow_voting_power(
+            &mut self.slope_changes,
+            escrow,
+            Clock::get()?.unix_timestamp
+        ));
 
         emit!(IncreaseLockedAmountEvent {
             locker: locker.key(),
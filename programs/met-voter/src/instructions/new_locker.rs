@@ -43,6 +43,10 @@ impl<'info> NewLocker<'info> {
             // buffer 1 day, so incase smart_wallet update wrongly, we can update it again
             invariant!(expiration >= now + 86400, ExpirationIsLessThanCurrentTime);
         }
+        invariant!(
+            params.max_stake_duration <= MAX_STAKE_DURATION,
+            MaxStakeDurationTooLong
+        );
         let locker = &mut self.locker;
         locker.token_mint = self.token_mint.key();
         locker.governor = self.governor.key();
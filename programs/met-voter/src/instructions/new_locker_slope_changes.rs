@@ -0,0 +1,45 @@
+use crate::*;
+
+/// Accounts for [voter::new_locker_slope_changes].
+#[derive(Accounts)]
+pub struct NewLockerSlopeChanges<'info> {
+    /// [Locker].
+    pub locker: Box<Account<'info, Locker>>,
+
+    /// [LockerSlopeChanges].
+    #[account(
+        init,
+        seeds = [
+            b"LockerSlopeChanges".as_ref(),
+            locker.key().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + LockerSlopeChanges::LEN
+    )]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
+    /// Payer of the initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// System program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> NewLockerSlopeChanges<'info> {
+    /// Creates the [LockerSlopeChanges] of a [Locker], with no lock scheduled to end.
+    pub fn new_locker_slope_changes(&mut self, bump: u8) -> Result<()> {
+        let slope_changes = &mut self.slope_changes;
+        slope_changes.locker = self.locker.key();
+        slope_changes.bump = bump;
+        slope_changes.slope_changes = vec![0; MAX_SLOPE_CHANGES];
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for NewLockerSlopeChanges<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
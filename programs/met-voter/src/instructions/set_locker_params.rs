@@ -16,6 +16,11 @@ pub struct SetLockerParams<'info> {
 
 impl<'info> SetLockerParams<'info> {
     pub fn set_locker_params(&mut self, params: LockerParams) -> Result<()> {
+        invariant!(
+            params.max_stake_duration <= MAX_STAKE_DURATION,
+            MaxStakeDurationTooLong
+        );
+
         let prev_params = self.locker.params;
         self.locker.params = params;
 
@@ -4,12 +4,17 @@ use crate::*;
 #[derive(Accounts)]
 pub struct ToggleMaxLock<'info> {
     /// [Locker].
+    #[account(mut)]
     pub locker: Box<Account<'info, Locker>>,
 
     /// [Escrow].
     #[account(mut, has_one = locker)]
     pub escrow: Box<Account<'info, Escrow>>,
 
+    /// The [LockerSlopeChanges] of the [Locker].
+    #[account(mut, has_one = locker)]
+    pub slope_changes: Box<Account<'info, LockerSlopeChanges>>,
+
     /// Authority of the [Escrow] and
     pub escrow_owner: Signer<'info>,
 }
@@ -33,6 +38,11 @@ impl<'info> ToggleMaxLock<'info> {
         }
 
         escrow.is_max_lock = is_max_lock;
+        unwrap_int!(self.locker.track_escrow_voting_power(
+            &mut self.slope_changes,
+            escrow,
+            Clock::get()?.unix_timestamp
+        ));
         Ok(())
     }
 }
@@ -45,6 +45,24 @@ pub mod met_voter {
         ctx.accounts.change_locker_expiration(expiration)
     }
 
+    /// Creates the [LockerSlopeChanges] tracking the total voting power of a [Locker].
+    /// Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn new_locker_slope_changes(ctx: Context<NewLockerSlopeChanges>) -> Result<()> {
+        ctx.accounts
+            .new_locker_slope_changes(unwrap_bump!(ctx, "slope_changes"))
+    }
+
+    /// Counts the [Escrow]s given as remaining accounts in the total voting power of their [Locker],
+    /// for escrows created before it was tracked. Anyone can call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn sync_escrows_voting_power<'info>(
+        ctx: Context<'_, '_, '_, 'info, SyncEscrowsVotingPower<'info>>,
+    ) -> Result<()> {
+        ctx.accounts
+            .sync_escrows_voting_power(ctx.remaining_accounts)
+    }
+
     /// Creates a new [Escrow] for an account.
     ///
     /// A Vote Escrow, or [Escrow] for short, is an agreement between an account (known as the `authority`) and the DAO to
@@ -88,7 +106,22 @@ pub mod met_voter {
         ctx.accounts.withdraw()
     }
 
-    /// Activates a proposal in token launch phase
+    /// Creates a proposal on behalf of an [Escrow] owner with at least
+    /// [LockerParams::proposal_activation_min_votes] of voting power, so that any
+    /// token holder can propose.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        proposal_type: u8,
+        max_option: u8,
+        instructions: Vec<govern::ProposalInstruction>,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_proposal(proposal_type, max_option, instructions)
+    }
+
+    /// Activates a proposal in token launch phase.
+    /// A proposal with a [govern::GovernanceParameters::quorum_bps] needs every [Escrow] synced first.
     #[access_control(ctx.accounts.validate())]
     pub fn activate_proposal(ctx: Context<ActivateProposal>) -> Result<()> {
         ctx.accounts.activate_proposal()
@@ -108,6 +141,25 @@ pub mod met_voter {
         ctx.accounts.cast_vote(side)
     }
 
+    /// Casts a vote on an approval or ranked-choice proposal.
+    /// Choices are given in order of preference; an empty list is an abstain vote.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_vote_choices(ctx: Context<CastVote>, choices: Vec<u8>) -> Result<()> {
+        ctx.accounts.cast_vote_choices(choices)
+    }
+
+    /// Casts votes signed off-chain by the vote delegates of escrows, in a batch.
+    /// The signatures must be checked by ed25519 program instructions in the same transaction.
+    /// Remaining accounts are an [Escrow] and its [Vote] for each vote; missing [Vote]s are created.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cast_signed_votes<'info>(
+        ctx: Context<'_, '_, '_, 'info, CastSignedVotes<'info>>,
+        votes: Vec<SignedVote>,
+    ) -> Result<()> {
+        ctx.accounts
+            .cast_signed_votes(ctx.remaining_accounts, votes)
+    }
+
     /// Delegate escrow vote.
     #[access_control(ctx.accounts.validate())]
     pub fn set_vote_delegate(ctx: Context<SetVoteDelegate>, new_delegate: Pubkey) -> Result<()> {
@@ -142,4 +194,12 @@ pub enum ErrorCode {
     ExpirationIsNotZero,
     #[msg("Amount is zero")]
     AmountIsZero,
+    #[msg("Signed vote nonce must be greater than the last one")]
+    InvalidVoteNonce,
+    #[msg("Vote is not signed by the vote delegate")]
+    VoteNotSigned,
+    #[msg("Max stake duration is too long to track the total voting power")]
+    MaxStakeDurationTooLong,
+    #[msg("Escrows must be synced before the total voting power can set a quorum")]
+    VotingPowerNotSynced,
 }
@@ -19,6 +19,33 @@ fn get_unix_timestamp() -> Result<i64> {
     Ok(Clock::get()?.unix_timestamp)
 }
 
+/// Rounds the end of a lock up to the end of its [SLOPE_CHANGE_EPOCH].
+fn epoch_end(timestamp: i64) -> Option<i64> {
+    timestamp
+        .checked_add(SLOPE_CHANGE_EPOCH.checked_sub(1)?)?
+        .checked_div(SLOPE_CHANGE_EPOCH)?
+        .checked_mul(SLOPE_CHANGE_EPOCH)
+}
+
+/// The index of the slope change at the end of an epoch in [LockerSlopeChanges::slope_changes].
+fn slope_change_index(epoch_end: i64) -> Option<usize> {
+    epoch_end
+        .checked_div(SLOPE_CHANGE_EPOCH)?
+        .checked_rem(MAX_SLOPE_CHANGES.to_i64()?)?
+        .to_usize()
+}
+
+/// The ends of the epochs after `from` up to `to`, at most [MAX_SLOPE_CHANGES] of them.
+fn epoch_ends_between(from: i64, to: i64) -> Option<Vec<i64>> {
+    let mut epoch_ends = vec![];
+    let mut epoch_end = epoch_end(from.checked_add(1)?)?;
+    while epoch_end <= to && epoch_ends.len() < MAX_SLOPE_CHANGES {
+        epoch_ends.push(epoch_end);
+        epoch_end = epoch_end.checked_add(SLOPE_CHANGE_EPOCH)?;
+    }
+    Some(epoch_ends)
+}
+
 impl Locker {
     pub fn get_current_phase(&self) -> Result<Phase> {
         let now = get_unix_timestamp()?;
@@ -27,6 +54,162 @@ impl Locker {
         }
         Ok(Phase::TokenLaunchPhase)
     }
+    /// Whether every [Escrow] with locked tokens is counted in the total voting power.
+    pub fn is_voting_power_synced(&self) -> bool {
+        self.tracked_locked_supply == self.locked_supply
+    }
+
+    /// Removes the tokens withdrawn from an [Escrow] from the locked supply.
+    pub fn record_withdrawal(&mut self, escrow: &Escrow, amount: u64) -> Option<()> {
+        self.locked_supply = self.locked_supply.checked_sub(amount)?;
+        if escrow.is_tracked {
+            self.tracked_locked_supply = self.tracked_locked_supply.checked_sub(amount)?;
+        }
+        Some(())
+    }
+
+    /// The voting power of the [Locker] if all locked tokens were max locked.
+    /// This is the total voting power during the [Phase::InitialPhase], when every [Escrow]
+    /// votes with all of its tokens, and the base of [govern::GovernanceParameters::quorum_bps] then.
+    pub fn max_voting_power(&self) -> Option<u64> {
+        self.locked_supply
+            .checked_mul(self.params.max_stake_vote_multiplier.into())
+    }
+
+    /// The total voting power of the [Escrow]s at the given time during the [Phase::TokenLaunchPhase],
+    /// which must not be before [Locker::voting_power_updated_at].
+    /// This is the base of [govern::GovernanceParameters::quorum_bps] then.
+    ///
+    /// Lock ends are rounded up to a [SLOPE_CHANGE_EPOCH], so the total is never below the
+    /// sum of the voting power of the [Escrow]s.
+    pub fn total_voting_power_at_time(
+        &self,
+        slope_changes: &LockerSlopeChanges,
+        timestamp: i64,
+    ) -> Option<u64> {
+        let (bias, _) = self.voting_power_point_at(slope_changes, timestamp)?;
+        let multiplier = u128::from(self.params.max_stake_vote_multiplier);
+        let decaying_power = bias
+            .checked_mul(multiplier)?
+            .checked_div(self.params.max_stake_duration.into())?;
+        let max_lock_power = u128::from(self.max_locked_supply).checked_mul(multiplier)?;
+        decaying_power.checked_add(max_lock_power)?.to_u64()
+    }
+
+    /// The bias and slope of the decaying locks at the given time, applying the slope changes
+    /// since [Locker::voting_power_updated_at].
+    fn voting_power_point_at(
+        &self,
+        slope_changes: &LockerSlopeChanges,
+        timestamp: i64,
+    ) -> Option<(u128, u64)> {
+        // nothing was ever tracked
+        if self.voting_power_updated_at == 0 {
+            return Some((0, 0));
+        }
+        if timestamp < self.voting_power_updated_at {
+            return None;
+        }
+
+        let mut bias = self.voting_power_bias;
+        let mut slope = self.voting_power_slope;
+        let mut updated_at = self.voting_power_updated_at;
+        // every lock ends within MAX_SLOPE_CHANGES epochs, so the slope is zero after them
+        for epoch_end in epoch_ends_between(updated_at, timestamp)? {
+            let elapsed = epoch_end.checked_sub(updated_at)?.to_u128()?;
+            bias = bias.checked_sub(u128::from(slope).checked_mul(elapsed)?)?;
+            slope = slope.checked_sub(
+                *slope_changes
+                    .slope_changes
+                    .get(slope_change_index(epoch_end)?)?,
+            )?;
+            updated_at = epoch_end;
+        }
+        let elapsed = timestamp.checked_sub(updated_at)?.to_u128()?;
+        bias = bias.checked_sub(u128::from(slope).checked_mul(elapsed)?)?;
+        Some((bias, slope))
+    }
+
+    /// Brings the bias and slope of the decaying locks up to date, clearing the applied slope changes.
+    fn update_voting_power(
+        &mut self,
+        slope_changes: &mut LockerSlopeChanges,
+        now: i64,
+    ) -> Option<()> {
+        let (bias, slope) = self.voting_power_point_at(slope_changes, now)?;
+        if self.voting_power_updated_at != 0 {
+            for epoch_end in epoch_ends_between(self.voting_power_updated_at, now)? {
+                *slope_changes
+                    .slope_changes
+                    .get_mut(slope_change_index(epoch_end)?)? = 0;
+            }
+        }
+        self.voting_power_bias = bias;
+        self.voting_power_slope = slope;
+        self.voting_power_updated_at = now;
+        Some(())
+    }
+
+    /// Updates the total voting power of the [Locker] for the current state of an [Escrow],
+    /// replacing what the [Escrow] counted for before.
+    pub fn track_escrow_voting_power(
+        &mut self,
+        slope_changes: &mut LockerSlopeChanges,
+        escrow: &mut Escrow,
+        now: i64,
+    ) -> Option<()> {
+        self.update_voting_power(slope_changes, now)?;
+
+        // escrows created before the total was tracked are counted from their first update
+        if !escrow.is_tracked {
+            self.tracked_locked_supply = self.tracked_locked_supply.checked_add(escrow.amount)?;
+            escrow.is_tracked = true;
+        }
+
+        // remove what the escrow counted for, unless its lock already ended
+        if escrow.tracked_is_max_lock {
+            self.max_locked_supply = self.max_locked_supply.checked_sub(escrow.tracked_amount)?;
+        } else if escrow.tracked_ends_at > now {
+            let slope_change = slope_changes
+                .slope_changes
+                .get_mut(slope_change_index(escrow.tracked_ends_at)?)?;
+            *slope_change = slope_change.checked_sub(escrow.tracked_amount)?;
+            self.voting_power_slope = self.voting_power_slope.checked_sub(escrow.tracked_amount)?;
+            let remaining = escrow.tracked_ends_at.checked_sub(now)?.to_u128()?;
+            self.voting_power_bias = self
+                .voting_power_bias
+                .checked_sub(u128::from(escrow.tracked_amount).checked_mul(remaining)?)?;
+        }
+
+        // add what the escrow counts for now
+        escrow.tracked_amount = escrow.amount;
+        escrow.tracked_is_max_lock = escrow.is_max_lock;
+        escrow.tracked_ends_at = 0;
+        if escrow.is_max_lock {
+            self.max_locked_supply = self.max_locked_supply.checked_add(escrow.amount)?;
+        } else if escrow.escrow_started_at != 0 && escrow.escrow_ends_at > now {
+            let ends_at = epoch_end(escrow.escrow_ends_at)?;
+            // the slope change must not land on a slot of an epoch not yet applied
+            let epochs_ahead = ends_at
+                .checked_div(SLOPE_CHANGE_EPOCH)?
+                .checked_sub(now.checked_div(SLOPE_CHANGE_EPOCH)?)?;
+            if epochs_ahead > MAX_SLOPE_CHANGES.to_i64()? {
+                return None;
+            }
+            let slope_change = slope_changes
+                .slope_changes
+                .get_mut(slope_change_index(ends_at)?)?;
+            *slope_change = slope_change.checked_add(escrow.amount)?;
+            self.voting_power_slope = self.voting_power_slope.checked_add(escrow.amount)?;
+            let remaining = ends_at.checked_sub(now)?.to_u128()?;
+            self.voting_power_bias = self
+                .voting_power_bias
+                .checked_add(u128::from(escrow.amount).checked_mul(remaining)?)?;
+            escrow.tracked_ends_at = ends_at;
+        }
+        Some(())
+    }
+
     /// Calculates the amount of voting power an [Escrow] has.
     pub fn calculate_voter_power(&self, escrow: &Escrow, now: i64) -> Option<u64> {
         // invalid `now` argument, should never happen.
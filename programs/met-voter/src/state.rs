@@ -36,8 +36,20 @@ pub struct Locker {
     pub expiration: i64,
     /// Mutable parameters of how a [Locker] should behave.
     pub params: LockerParams,
+    /// Sum over the decaying locks of their amount times the seconds until their end,
+    /// at [Locker::voting_power_updated_at].
+    pub voting_power_bias: u128,
+    /// Sum of the amounts of the decaying locks, by which [Locker::voting_power_bias] decreases each second.
+    pub voting_power_slope: u64,
+    /// Total number of tokens in max locked [Escrow]s, whose voting power does not decay.
+    pub max_locked_supply: u64,
+    /// When [Locker::voting_power_bias] and [Locker::voting_power_slope] were last updated.
+    pub voting_power_updated_at: i64,
+    /// Total number of tokens locked in the [Escrow]s counted in the total voting power.
+    /// Escrows created before it was tracked are only counted once synced.
+    pub tracked_locked_supply: u64,
     /// buffer for further use
-    pub buffers: [u128; 32],
+    pub buffers: [u128; 29],
 }
 
 impl Locker {
@@ -48,7 +60,12 @@ impl Locker {
         + 8
         + 8
         + std::mem::size_of::<LockerParams>()
-        + 16 * 32;
+        + 16
+        + 8
+        + 8
+        + 8
+        + 8
+        + 16 * 29;
 }
 
 /// Contains parameters for the [Locker].
@@ -62,6 +79,7 @@ pub struct LockerParams {
     /// Maximum staking duration.
     pub max_stake_duration: u64,
     /// Minimum number of votes required to activate a proposal.
+    /// This is also the voting power an [Escrow] needs to create a proposal.
     pub proposal_activation_min_votes: u64,
 }
 
@@ -91,8 +109,23 @@ pub struct Escrow {
 
     /// Max lock
     pub is_max_lock: bool,
+    /// Nonce of the last signed vote settled by [crate::met_voter::cast_signed_votes].
+    pub vote_nonce: u64,
+    /// padding for further use
+    pub padding: u64,
+    /// Amount of tokens the [Escrow] counts for in the total voting power of the [Locker].
+    pub tracked_amount: u64,
+    /// End of the lock the [Escrow] counts for in the total voting power of the [Locker],
+    /// rounded up to a [SLOPE_CHANGE_EPOCH]. Zero if it does not decay.
+    pub tracked_ends_at: i64,
+    /// Whether the [Escrow] counts as max locked in the total voting power of the [Locker].
+    pub tracked_is_max_lock: bool,
+    /// Whether the [Escrow] is counted in the total voting power of the [Locker].
+    pub is_tracked: bool,
+    /// padding
+    pub tracked_padding: [u8; 14],
     /// buffer for further use
-    pub buffers: [u128; 10],
+    pub buffers: [u128; 7],
 }
 
 impl Escrow {
@@ -119,6 +152,10 @@ impl Escrow {
     ) -> Result<()> {
         self.amount = unwrap_int!(self.amount.checked_add(lock_amount));
         locker.locked_supply = unwrap_int!(locker.locked_supply.checked_add(lock_amount));
+        if self.is_tracked {
+            locker.tracked_locked_supply =
+                unwrap_int!(locker.tracked_locked_supply.checked_add(lock_amount));
+        }
         Ok(())
     }
 
@@ -150,6 +187,31 @@ impl Escrow {
     }
 }
 
+/// Length of the epochs by which [LockerSlopeChanges] groups the ends of locks.
+pub const SLOPE_CHANGE_EPOCH: i64 = 7 * 24 * 3600;
+/// Number of epochs [LockerSlopeChanges] schedules slope changes for.
+pub const MAX_SLOPE_CHANGES: usize = 256;
+/// Maximum [LockerParams::max_stake_duration], so that every lock ends within [MAX_SLOPE_CHANGES] epochs.
+pub const MAX_STAKE_DURATION: u64 = (MAX_SLOPE_CHANGES as u64 - 1) * SLOPE_CHANGE_EPOCH as u64;
+
+/// The decreases of the total voting power slope of a [Locker], scheduled at the end of
+/// the epochs in which locks end.
+#[account]
+#[derive(Debug, Default)]
+pub struct LockerSlopeChanges {
+    /// The [Locker].
+    pub locker: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// Amount of tokens whose lock ends at the end of each epoch, in a ring of [MAX_SLOPE_CHANGES] epochs.
+    pub slope_changes: Vec<u64>,
+}
+
+impl LockerSlopeChanges {
+    /// LEN of LockerSlopeChanges
+    pub const LEN: usize = std::mem::size_of::<Pubkey>() + 1 + 4 + 8 * MAX_SLOPE_CHANGES;
+}
+
 #[cfg(test)]
 #[allow(clippy::unwrap_used)]
 mod tests {
@@ -349,4 +411,73 @@ mod tests {
         assert_eq!(locker.locked_supply, 0);
         assert_escrow(&locker, bob, current_ts, 0.0);
     }
+
+    #[test]
+    fn test_total_voting_power() {
+        let locker = &mut Locker {
+            params: LockerParams {
+                max_stake_duration: MAX_TIME as u64,
+                max_stake_vote_multiplier: 10,
+                ..LockerParams::default()
+            },
+            ..Locker::default()
+        };
+        let slope_changes = &mut LockerSlopeChanges {
+            slope_changes: vec![0; MAX_SLOPE_CHANGES],
+            ..LockerSlopeChanges::default()
+        };
+        let alice = &mut Escrow::default();
+        let bob = &mut Escrow::default();
+        let carol = &mut Escrow::default();
+
+        let mut current_ts = CANONICAL_START_TIME;
+        // Alice locks for a year and Carol max locks; Bob's lock never started
+        for escrow in [&mut *alice, &mut *bob, &mut *carol] {
+            escrow
+                .record_increase_locked_amount_event(locker, DEFAULT_LOCK_AMOUNT)
+                .unwrap();
+        }
+        alice
+            .record_extend_lock_duration_event(current_ts, current_ts + DAYS_PER_YEAR * DAY)
+            .unwrap();
+        carol.is_max_lock = true;
+        for escrow in [&mut *alice, &mut *bob, &mut *carol] {
+            locker
+                .track_escrow_voting_power(slope_changes, escrow, current_ts)
+                .unwrap();
+        }
+        // only the max locked tokens count at the start
+        assert_eq!(locker.max_voting_power(), Some(30 * DEFAULT_LOCK_AMOUNT));
+
+        // voting power of a lock over a week, at the most
+        let week_of_decay = 10 * DEFAULT_LOCK_AMOUNT / (MAX_TIME / WEEK) as u64;
+        for _ in 0..400 {
+            current_ts += DAY;
+            let total = locker
+                .total_voting_power_at_time(slope_changes, current_ts)
+                .unwrap();
+            let sum = alice.voting_power_at_time(locker, current_ts).unwrap()
+                + bob.voting_power_at_time(locker, current_ts).unwrap()
+                + carol.voting_power_at_time(locker, current_ts).unwrap();
+            assert!(total >= sum && total <= sum + week_of_decay);
+        }
+        assert_eq!(
+            locker.total_voting_power_at_time(slope_changes, current_ts),
+            Some(10 * DEFAULT_LOCK_AMOUNT)
+        );
+
+        // Carol exits
+        carol.amount = 0;
+        locker
+            .track_escrow_voting_power(slope_changes, carol, current_ts)
+            .unwrap();
+        assert_eq!(
+            locker.total_voting_power_at_time(slope_changes, current_ts),
+            Some(0)
+        );
+        assert!(slope_changes
+            .slope_changes
+            .iter()
+            .all(|change| *change == 0));
+    }
 }
@@ -0,0 +1,67 @@
+use crate::*;
+
+/// Accounts for [smart_wallet::cancel_transaction].
+#[derive(Accounts)]
+pub struct CancelTransaction<'info> {
+    /// The [SmartWallet].
+    #[account(mut)]
+    pub smart_wallet: Account<'info, SmartWallet>,
+    /// The [Transaction].
+    #[account(mut, has_one = smart_wallet, has_one = proposer)]
+    pub transaction: Account<'info, Transaction>,
+    /// Must be proposer of the transaction
+    pub proposer: Signer<'info>,
+    /// The payer of the [Transaction], who receives its rent.
+    /// Transactions created before payers were recorded return their rent to the proposer.
+    /// CHECK: checked in [Validate] against the payer recorded in the [Transaction].
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+}
+
+impl<'info> CancelTransaction<'info> {
+    pub fn cancel_transaction(&mut self) -> Result<()> {
+        self.transaction.close(self.payer.to_account_info())?;
+        emit!(TransactionCancelEvent {
+            smart_wallet: self.smart_wallet.key(),
+            transaction: self.transaction.key(),
+            proposer: self.proposer.key(),
+            num_signers: self.transaction.num_signers() as u64,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CancelTransaction<'info> {
+    fn validate(&self) -> Result<()> {
+        // only the proposer cancels, whatever the approvals, but an executed transaction
+        // is kept as a record
+        invariant!(self.transaction.executed_at == -1, AlreadyExecuted);
+        let payer = self
+            .transaction
+            .recorded_payer(
+                &self.transaction.to_account_info().try_borrow_data()?,
+                self.smart_wallet.max_owners as usize,
+            )
+            .unwrap_or(self.transaction.proposer);
+        assert_keys_eq!(payer, self.payer);
+        Ok(())
+    }
+}
+
+/// Emitted when a [Transaction] is canceled.
+#[event]
+pub struct TransactionCancelEvent {
+    /// The [SmartWallet].
+    #[index]
+    pub smart_wallet: Pubkey,
+    /// The [Transaction].
+    #[index]
+    pub transaction: Pubkey,
+    /// The owner which proposed the transaction.
+    pub proposer: Pubkey,
+    /// Number of owners which had approved the transaction.
+    pub num_signers: u64,
+    /// The Unix timestamp when the event was emitted.
+    pub timestamp: i64,
+}
@@ -17,12 +17,14 @@ pub struct CreateTransaction<'info> {
         ],
         bump,
         payer = payer,
-        space = 8 + Transaction::space(instructions, smart_wallet.max_owners as usize),
+        space = 8
+            + Transaction::space(instructions, smart_wallet.max_owners as usize)
+            + Transaction::PAYER_SPACE,
     )]
     pub transaction: Account<'info, Transaction>,
     /// One of the owners. Checked in the handler via [SmartWallet::owner_index].
     pub proposer: Signer<'info>,
-    /// Payer to create the [Transaction].
+    /// Payer to create the [Transaction], who receives its rent if it is canceled.
     #[account(mut)]
     pub payer: Signer<'info>,
     /// The [System] program.
@@ -39,60 +41,146 @@ impl<'info> CreateTransaction<'info> {
         instructions: Vec<TXInstruction>,
         eta: i64,
     ) -> Result<()> {
-        let smart_wallet = &self.smart_wallet;
-        let owner_index = smart_wallet.owner_index(self.proposer.key())?;
+        init_transaction(
+            &mut self.smart_wallet,
+            &mut self.transaction,
+            self.proposer.key(),
+            self.payer.key(),
+            bump,
+            instructions,
+            eta,
+        )
+    }
+}
+
+/// Accounts for [smart_wallet::create_transaction_from_buffer].
+#[derive(Accounts)]
+pub struct CreateTransactionFromBuffer<'info> {
+    /// The [SmartWallet].
+    #[account(mut)]
+    pub smart_wallet: Account<'info, SmartWallet>,
+    /// The [TransactionBuffer] holding the instructions of the [Transaction].
+    #[account(mut, has_one = smart_wallet, has_one = proposer, close = payer)]
+    pub transaction_buffer: Account<'info, TransactionBuffer>,
+    /// The [Transaction].
+    #[account(
+        init,
+        seeds = [
+            b"Transaction".as_ref(),
+            smart_wallet.key().as_ref(),
+            smart_wallet.num_transactions.to_le_bytes().as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8
+            + Transaction::space(
+                transaction_buffer.instructions.clone(),
+                smart_wallet.max_owners as usize
+            )
+            + Transaction::PAYER_SPACE,
+    )]
+    pub transaction: Account<'info, Transaction>,
+    /// The [TransactionBuffer::proposer]. Checked in the handler via [SmartWallet::owner_index].
+    pub proposer: Signer<'info>,
+    /// Payer to create the [Transaction], who receives its rent if it is canceled.
+    /// Receives the rent of the [TransactionBuffer].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTransactionFromBuffer<'info> {
+    pub fn create_transaction_from_buffer(&mut self, bump: u8, eta: i64) -> Result<()> {
+        init_transaction(
+            &mut self.smart_wallet,
+            &mut self.transaction,
+            self.proposer.key(),
+            self.payer.key(),
+            bump,
+            self.transaction_buffer.instructions.clone(),
+            eta,
+        )
+    }
+}
+
+impl<'info> Validate<'info> for CreateTransactionFromBuffer<'info> {
+    fn validate(&self) -> Result<()> {
+        // owner_index check happens later
+        Ok(())
+    }
+}
 
-        let clock = Clock::get()?;
-        let current_ts = clock.unix_timestamp;
-        if smart_wallet.minimum_delay != 0 {
-            invariant!(
-                eta >= unwrap_int!(current_ts.checked_add(smart_wallet.minimum_delay as i64)),
-                InvalidETA
-            );
-        }
-        if eta != NO_ETA {
-            invariant!(eta >= 0, "ETA must be positive");
-            let delay = unwrap_int!(eta.checked_sub(current_ts));
-            invariant!(delay >= 0, "ETA must be in the future");
-            invariant!(delay <= MAX_DELAY_SECONDS, DelayTooHigh);
-        }
+/// Initializes a [Transaction] signed by its proposer at the next index of the [SmartWallet],
+/// recording its payer.
+fn init_transaction(
+    smart_wallet: &mut Account<SmartWallet>,
+    tx: &mut Account<Transaction>,
+    proposer: Pubkey,
+    payer: Pubkey,
+    bump: u8,
+    instructions: Vec<TXInstruction>,
+    eta: i64,
+) -> Result<()> {
+    let owner_index = smart_wallet.owner_index(proposer)?;
 
-        // generate the signers boolean list
-        let owners = &smart_wallet.owners;
-        let mut signers = Vec::new();
-        signers.resize(owners.len(), false);
-        signers[owner_index] = true;
+    let clock = Clock::get()?;
+    let current_ts = clock.unix_timestamp;
+    if smart_wallet.minimum_delay != 0 {
+        invariant!(
+            eta >= unwrap_int!(current_ts.checked_add(smart_wallet.minimum_delay as i64)),
+            InvalidETA
+        );
+    }
+    if eta != NO_ETA {
+        invariant!(eta >= 0, "ETA must be positive");
+        let delay = unwrap_int!(eta.checked_sub(current_ts));
+        invariant!(delay >= 0, "ETA must be in the future");
+        invariant!(delay <= MAX_DELAY_SECONDS, DelayTooHigh);
+    }
 
-        let index = smart_wallet.num_transactions;
-        let smart_wallet = &mut self.smart_wallet;
-        smart_wallet.num_transactions = unwrap_int!(smart_wallet.num_transactions.checked_add(1));
+    // generate the signers boolean list
+    let owners = &smart_wallet.owners;
+    let mut signers = Vec::new();
+    signers.resize(owners.len(), false);
+    signers[owner_index] = true;
 
-        // init the TX
-        let tx = &mut self.transaction;
-        tx.smart_wallet = smart_wallet.key();
-        tx.index = index;
-        tx.bump = bump;
+    let index = smart_wallet.num_transactions;
+    smart_wallet.num_transactions = unwrap_int!(smart_wallet.num_transactions.checked_add(1));
 
-        tx.proposer = self.proposer.key();
-        tx.instructions = instructions.clone();
-        tx.signers = signers;
-        tx.owner_set_seqno = smart_wallet.owner_set_seqno;
-        tx.eta = eta;
+    // init the TX
+    tx.smart_wallet = smart_wallet.key();
+    tx.index = index;
+    tx.bump = bump;
 
-        tx.executor = Pubkey::default();
-        tx.executed_at = -1;
-        tx.created_at = current_ts;
+    tx.proposer = proposer;
+    tx.instructions = instructions.clone();
+    tx.signers = signers;
+    tx.owner_set_seqno = smart_wallet.owner_set_seqno;
+    tx.eta = eta;
 
-        emit!(TransactionCreateEvent {
-            smart_wallet: self.smart_wallet.key(),
-            transaction: self.transaction.key(),
-            proposer: self.proposer.key(),
-            instructions,
-            eta,
-            timestamp: Clock::get()?.unix_timestamp
-        });
-        Ok(())
-    }
+    tx.executor = Pubkey::default();
+    tx.executed_at = -1;
+    tx.created_at = current_ts;
+    let tx_info = tx.to_account_info();
+    unwrap_opt!(
+        tx.record_payer(
+            &mut tx_info.try_borrow_mut_data()?,
+            smart_wallet.max_owners as usize,
+            payer
+        ),
+        "cannot record payer"
+    );
+
+    emit!(TransactionCreateEvent {
+        smart_wallet: smart_wallet.key(),
+        transaction: tx.key(),
+        proposer,
+        instructions,
+        eta,
+        timestamp: Clock::get()?.unix_timestamp
+    });
+    Ok(())
 }
 
 impl<'info> Validate<'info> for CreateTransaction<'info> {
@@ -0,0 +1,142 @@
+use crate::*;
+
+/// Accounts for [smart_wallet::create_transaction_buffer].
+#[derive(Accounts)]
+#[instruction(base: Pubkey, instructions: Vec<TXLookupInstruction>)]
+pub struct CreateTransactionBuffer<'info> {
+    /// The [SmartWallet].
+    pub smart_wallet: Account<'info, SmartWallet>,
+    /// The [TransactionBuffer].
+    #[account(
+        init,
+        seeds = [
+            b"TransactionBuffer".as_ref(),
+            smart_wallet.key().as_ref(),
+            proposer.key().as_ref(),
+            base.as_ref()
+        ],
+        bump,
+        payer = payer,
+        space = 8 + TransactionBuffer::space(&[])
+            + instructions.iter().map(|ix| ix.space()).sum::<usize>(),
+    )]
+    pub transaction_buffer: Account<'info, TransactionBuffer>,
+    /// One of the owners. Checked in [Validate] via [SmartWallet::owner_index].
+    pub proposer: Signer<'info>,
+    /// Payer to create the [TransactionBuffer].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTransactionBuffer<'info> {
+    pub fn create_transaction_buffer(
+        &mut self,
+        bump: u8,
+        base: Pubkey,
+        instructions: Vec<TXLookupInstruction>,
+        lookup_tables: &[AccountInfo],
+    ) -> Result<()> {
+        let instructions = resolve_instructions(&instructions, lookup_tables)?;
+        let buffer = &mut self.transaction_buffer;
+        buffer.smart_wallet = self.smart_wallet.key();
+        buffer.proposer = self.proposer.key();
+        buffer.base = base;
+        buffer.bump = bump;
+        buffer.instructions = instructions.clone();
+
+        emit!(TransactionBufferAppendEvent {
+            smart_wallet: self.smart_wallet.key(),
+            transaction_buffer: buffer.key(),
+            instructions,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateTransactionBuffer<'info> {
+    fn validate(&self) -> Result<()> {
+        self.smart_wallet.owner_index(self.proposer.key())?;
+        Ok(())
+    }
+}
+
+/// Accounts for [smart_wallet::append_transaction_buffer].
+#[derive(Accounts)]
+#[instruction(instructions: Vec<TXLookupInstruction>)]
+pub struct AppendTransactionBuffer<'info> {
+    /// The [SmartWallet].
+    pub smart_wallet: Account<'info, SmartWallet>,
+    /// The [TransactionBuffer].
+    #[account(
+        mut,
+        has_one = smart_wallet,
+        has_one = proposer,
+        realloc = transaction_buffer.to_account_info().data_len()
+            + instructions.iter().map(|ix| ix.space()).sum::<usize>(),
+        realloc::payer = payer,
+        realloc::zero = false
+    )]
+    pub transaction_buffer: Account<'info, TransactionBuffer>,
+    /// The [TransactionBuffer::proposer].
+    pub proposer: Signer<'info>,
+    /// Payer of the larger [TransactionBuffer].
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// The [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AppendTransactionBuffer<'info> {
+    pub fn append_transaction_buffer(
+        &mut self,
+        instructions: Vec<TXLookupInstruction>,
+        lookup_tables: &[AccountInfo],
+    ) -> Result<()> {
+        let instructions = resolve_instructions(&instructions, lookup_tables)?;
+        let buffer = &mut self.transaction_buffer;
+        buffer.instructions.extend(instructions.iter().cloned());
+
+        emit!(TransactionBufferAppendEvent {
+            smart_wallet: self.smart_wallet.key(),
+            transaction_buffer: buffer.key(),
+            instructions,
+            timestamp: Clock::get()?.unix_timestamp
+        });
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for AppendTransactionBuffer<'info> {
+    fn validate(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Resolves the accounts the `instructions` reference through the `lookup_tables`.
+fn resolve_instructions(
+    instructions: &[TXLookupInstruction],
+    lookup_tables: &[AccountInfo],
+) -> Result<Vec<TXInstruction>> {
+    instructions
+        .iter()
+        .map(|ix| ix.resolve(lookup_tables))
+        .collect()
+}
+
+/// Emitted when instructions are appended to a [TransactionBuffer].
+#[event]
+pub struct TransactionBufferAppendEvent {
+    /// The [SmartWallet].
+    #[index]
+    pub smart_wallet: Pubkey,
+    /// The [TransactionBuffer].
+    #[index]
+    pub transaction_buffer: Pubkey,
+    /// Instructions appended to the [TransactionBuffer], with their accounts resolved.
+    pub instructions: Vec<TXInstruction>,
+    /// The Unix timestamp when the event was emitted.
+    pub timestamp: i64,
+}
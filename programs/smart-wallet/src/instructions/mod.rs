@@ -1,17 +1,21 @@
 pub mod approve;
 pub mod auth;
+pub mod cancel_transaction;
 pub mod create_smart_wallet;
 pub mod create_subaccount_info;
 pub mod create_transaction;
+pub mod create_transaction_buffer;
 pub mod execute_transaction;
 pub mod owner_invoke_instruction;
 pub mod remove_transaction;
 
 pub use approve::*;
 pub use auth::*;
+pub use cancel_transaction::*;
 pub use create_smart_wallet::*;
 pub use create_subaccount_info::*;
 pub use create_transaction::*;
+pub use create_transaction_buffer::*;
 pub use execute_transaction::*;
 pub use owner_invoke_instruction::*;
 pub use remove_transaction::*;
@@ -44,6 +44,15 @@ pub const DEFAULT_GRACE_PERIOD: i64 = 14 * SECONDS_PER_DAY;
 /// Constant declaring that there is no ETA of the transaction.
 pub const NO_ETA: i64 = -1;
 
+/// The address lookup table program, owner of the tables [TXLookup]s reference.
+pub mod address_lookup_table_program {
+    use super::*;
+    declare_id!("AddressLookupTab1e1111111111111111111111111");
+}
+
+/// Size of the metadata preceding the addresses of an address lookup table.
+pub const LOOKUP_TABLE_META_SIZE: usize = 56;
+
 declare_id!("smaK3fwkA7ubbxEhsimp1iqPTzfS4MBsNL77QLABZP6");
 
 #[program]
@@ -103,6 +112,14 @@ pub mod smart_wallet {
         ctx.accounts.remove_transaction()
     }
 
+    /// Cancels a [Transaction] that has not been executed, even if owners approved it,
+    /// by closing its account and returning its rent to its payer. Only the proposer of the
+    /// [Transaction] may call this, e.g. a governor whose proposal was vetoed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_transaction(ctx: Context<CancelTransaction>) -> Result<()> {
+        ctx.accounts.cancel_transaction()
+    }
+
     /// Creates a new [Transaction] account with time delay.
     #[access_control(ctx.accounts.validate())]
     pub fn create_transaction_with_timelock(
@@ -118,6 +135,50 @@ pub mod smart_wallet {
         )
     }
 
+    /// Creates a new [TransactionBuffer] holding the first instructions of a [Transaction]
+    /// too large to be created in a single Solana transaction. The proposer must be one
+    /// of the owners of the smart_wallet; `base` distinguishes its buffers.
+    ///
+    /// Accounts referenced through address lookup tables are resolved from the tables
+    /// passed as remaining accounts.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_transaction_buffer(
+        ctx: Context<CreateTransactionBuffer>,
+        base: Pubkey,
+        instructions: Vec<TXLookupInstruction>,
+    ) -> Result<()> {
+        ctx.accounts.create_transaction_buffer(
+            unwrap_bump!(ctx, "transaction_buffer"),
+            base,
+            instructions,
+            ctx.remaining_accounts,
+        )
+    }
+
+    /// Appends instructions to a [TransactionBuffer], resolving the accounts referenced
+    /// through address lookup tables from the tables passed as remaining accounts.
+    /// Only the proposer of the [TransactionBuffer] may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn append_transaction_buffer(
+        ctx: Context<AppendTransactionBuffer>,
+        instructions: Vec<TXLookupInstruction>,
+    ) -> Result<()> {
+        ctx.accounts
+            .append_transaction_buffer(instructions, ctx.remaining_accounts)
+    }
+
+    /// Creates a new [Transaction] account with the instructions of a [TransactionBuffer],
+    /// automatically signed by the proposer of the buffer, and closes the buffer.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_transaction_from_buffer(
+        ctx: Context<CreateTransactionFromBuffer>,
+        _bump: u8, // weird bug from Anchor
+        eta: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_transaction_from_buffer(unwrap_bump!(ctx, "transaction"), eta)
+    }
+
     /// Approves a transaction on behalf of an owner of the smart_wallet.
     #[access_control(ctx.accounts.validate())]
     pub fn approve(ctx: Context<Approve>) -> Result<()> {
@@ -240,4 +301,10 @@ pub enum ErrorCode {
     SubaccountOwnerMismatch,
     #[msg("Number of signers is not zero.")]
     NumSignerIsNotZero,
+    #[msg("The address lookup table is missing or invalid.")]
+    InvalidLookupTable,
+    #[msg("The address is not in the lookup table.")]
+    InvalidLookupIndex,
+    #[msg("Invalid position of an account referenced through a lookup table.")]
+    InvalidLookupPosition,
 }
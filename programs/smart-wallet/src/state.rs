@@ -3,7 +3,9 @@
 
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program;
-use vipers::unwrap_or_err;
+use vipers::{invariant, unwrap_or_err};
+
+use crate::{address_lookup_table_program, LOOKUP_TABLE_META_SIZE};
 
 /// A [SmartWallet] is a multisig wallet with Timelock capabilities.
 #[account]
@@ -105,12 +107,62 @@ impl Transaction {
             + (instructions.iter().map(|ix| ix.space()).sum::<usize>())
     }
 
+    /// Space after the data of a [Transaction] recording its payer,
+    /// who receives its rent if it is canceled.
+    pub const PAYER_SPACE: usize = std::mem::size_of::<Pubkey>();
+
+    /// Records the payer after the data of the [Transaction] in its account `data`.
+    pub fn record_payer(&self, data: &mut [u8], max_owners: usize, payer: Pubkey) -> Option<()> {
+        let space = 8 + Transaction::space(self.instructions.clone(), max_owners);
+        data.get_mut(space..space.checked_add(Transaction::PAYER_SPACE)?)?
+            .copy_from_slice(payer.as_ref());
+        Some(())
+    }
+
+    /// The payer recorded in the account `data` of the [Transaction],
+    /// or [None] if the transaction was created before payers were recorded.
+    pub fn recorded_payer(&self, data: &[u8], max_owners: usize) -> Option<Pubkey> {
+        let space = 8 + Transaction::space(self.instructions.clone(), max_owners);
+        if data.len() != space.checked_add(Transaction::PAYER_SPACE)? {
+            return None;
+        }
+        Pubkey::try_from(data.get(space..)?).ok()
+    }
+
     /// Number of signers.
     pub fn num_signers(&self) -> usize {
         self.signers.iter().filter(|&did_sign| *did_sign).count()
     }
 }
 
+/// A [TransactionBuffer] accumulates the instructions of a [Transaction] too large
+/// to be created in a single Solana transaction, until
+/// [crate::smart_wallet::create_transaction_from_buffer] creates the [Transaction].
+#[account]
+#[derive(Debug, Default, PartialEq)]
+pub struct TransactionBuffer {
+    /// The [SmartWallet] of the buffered [Transaction].
+    pub smart_wallet: Pubkey,
+    /// The proposer of the buffered [Transaction].
+    pub proposer: Pubkey,
+    /// Base used to derive, distinguishing the buffers of a proposer.
+    pub base: Pubkey,
+    /// Bump seed.
+    pub bump: u8,
+    /// The instructions appended so far.
+    pub instructions: Vec<TXInstruction>,
+}
+
+impl TransactionBuffer {
+    /// Computes the space a [TransactionBuffer] uses.
+    pub fn space(instructions: &[TXInstruction]) -> usize {
+        std::mem::size_of::<Pubkey>() * 3
+            + 1
+            + 4 // Vec discriminator
+            + (instructions.iter().map(|ix| ix.space()).sum::<usize>())
+    }
+}
+
 /// Instruction.
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
 pub struct TXInstruction {
@@ -133,6 +185,136 @@ impl TXInstruction {
     }
 }
 
+/// A [TXInstruction] referencing some of its accounts through address lookup tables.
+///
+/// The accounts are resolved when the instruction is written into a [TransactionBuffer],
+/// so that owners approve the actual accounts of the [Transaction].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TXLookupInstruction {
+    /// Pubkey of the instruction processor that executes this instruction
+    pub program_id: Pubkey,
+    /// Metadata for the accounts passed directly to the instruction processor
+    pub keys: Vec<TXAccountMeta>,
+    /// Opaque data passed to the instruction processor
+    pub data: Vec<u8>,
+    /// Accounts referenced through address lookup tables, placed among
+    /// [TXLookupInstruction::keys] at their [TXLookupMeta::position].
+    pub lookups: Vec<TXLookup>,
+}
+
+impl TXLookupInstruction {
+    /// Space that the resolved [TXInstruction] takes up.
+    pub fn space(&self) -> usize {
+        let lookup_count: usize = self.lookups.iter().map(|lookup| lookup.keys.len()).sum();
+        std::mem::size_of::<Pubkey>()
+            + 4
+            + (self.keys.len() + lookup_count) * std::mem::size_of::<TXAccountMeta>()
+            + 4
+            + self.data.len()
+    }
+
+    /// Resolves the [TXLookupInstruction::lookups] from the lookup tables among the `accounts`.
+    pub fn resolve(&self, accounts: &[AccountInfo]) -> crate::Result<TXInstruction> {
+        let lookup_count: usize = self.lookups.iter().map(|lookup| lookup.keys.len()).sum();
+        let mut metas: Vec<Option<TXAccountMeta>> = vec![None; self.keys.len() + lookup_count];
+        for lookup in self.lookups.iter() {
+            let table = unwrap_or_err!(
+                accounts
+                    .iter()
+                    .find(|account| account.key() == lookup.table),
+                InvalidLookupTable
+            );
+            let addresses = lookup.resolve(table)?;
+            for (meta, pubkey) in lookup.keys.iter().zip(addresses) {
+                let slot =
+                    unwrap_or_err!(metas.get_mut(meta.position as usize), InvalidLookupPosition);
+                invariant!(slot.is_none(), InvalidLookupPosition);
+                *slot = Some(TXAccountMeta {
+                    pubkey,
+                    is_signer: meta.is_signer,
+                    is_writable: meta.is_writable,
+                });
+            }
+        }
+        let mut keys = self.keys.iter();
+        let keys = metas
+            .into_iter()
+            .map(|meta| meta.or_else(|| keys.next().copied()))
+            .collect::<Option<Vec<_>>>();
+        Ok(TXInstruction {
+            program_id: self.program_id,
+            keys: unwrap_or_err!(keys, InvalidLookupPosition),
+            data: self.data.clone(),
+        })
+    }
+}
+
+impl From<TXInstruction> for TXLookupInstruction {
+    fn from(
+        TXInstruction {
+            program_id,
+            keys,
+            data,
+        }: TXInstruction,
+    ) -> TXLookupInstruction {
+        TXLookupInstruction {
+            program_id,
+            keys,
+            data,
+            lookups: vec![],
+        }
+    }
+}
+
+/// Accounts of a [TXLookupInstruction] referenced through an address lookup table.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq)]
+pub struct TXLookup {
+    /// The address lookup table.
+    pub table: Pubkey,
+    /// The accounts referenced through the table.
+    pub keys: Vec<TXLookupMeta>,
+}
+
+impl TXLookup {
+    /// Space that a [TXLookup] takes up.
+    pub fn space(&self) -> usize {
+        std::mem::size_of::<Pubkey>() + 4 + self.keys.len() * std::mem::size_of::<TXLookupMeta>()
+    }
+
+    /// The addresses of the [TXLookup::keys], read from the lookup table account.
+    pub fn resolve(&self, table: &AccountInfo) -> crate::Result<Vec<Pubkey>> {
+        invariant!(
+            table.owner == &address_lookup_table_program::ID,
+            InvalidLookupTable
+        );
+        let data = table.try_borrow_data()?;
+        self.keys
+            .iter()
+            .map(|meta| {
+                let start = LOOKUP_TABLE_META_SIZE + meta.address_index as usize * 32;
+                let address = unwrap_or_err!(data.get(start..start + 32), InvalidLookupIndex);
+                Ok(Pubkey::new_from_array(unwrap_or_err!(
+                    address.try_into().ok(),
+                    InvalidLookupIndex
+                )))
+            })
+            .collect()
+    }
+}
+
+/// Account of a [TXLookupInstruction] referenced through an address lookup table.
+#[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Copy, Clone)]
+pub struct TXLookupMeta {
+    /// Position of the account among the accounts of the instruction.
+    pub position: u8,
+    /// Index of the address in the lookup table.
+    pub address_index: u8,
+    /// True if an Instruction requires a Transaction signature matching the address.
+    pub is_signer: bool,
+    /// True if the address can be loaded as a read-write account.
+    pub is_writable: bool,
+}
+
 /// Account metadata used to define [TXInstruction]s
 #[derive(AnchorSerialize, AnchorDeserialize, Debug, PartialEq, Copy, Clone)]
 pub struct TXAccountMeta {
@@ -224,7 +406,7 @@ pub struct StagedTXInstruction {
 
 #[cfg(test)]
 mod state_test {
-    use crate::SmartWallet;
+    use crate::*;
     use anchor_lang::{prelude::Pubkey, AnchorSerialize};
     use std::assert_eq;
 
@@ -280,4 +462,95 @@ mod state_test {
             }
         }
     }
+
+    #[test]
+    fn test_transaction_space() {
+        let instructions = vec![
+            TXInstruction {
+                data: vec![0u8; 16],
+                ..Default::default()
+            };
+            3
+        ];
+        let tx = Transaction {
+            instructions: instructions.clone(),
+            signers: vec![false; 5],
+            ..Default::default()
+        };
+        assert_eq!(
+            tx.try_to_vec().unwrap().len(),
+            Transaction::space(instructions.clone(), 5)
+        );
+
+        // the payer is recorded after the transaction
+        let space = 8 + Transaction::space(instructions.clone(), 5);
+        let payer = Pubkey::new_unique();
+        let mut data = vec![0u8; space + Transaction::PAYER_SPACE];
+        tx.record_payer(&mut data, 5, payer).unwrap();
+        assert_eq!(tx.recorded_payer(&data, 5), Some(payer));
+        assert_eq!(tx.recorded_payer(&data[..space], 5), None);
+        let buffer = TransactionBuffer {
+            instructions: instructions.clone(),
+            ..Default::default()
+        };
+        assert_eq!(
+            buffer.try_to_vec().unwrap().len(),
+            TransactionBuffer::space(&instructions)
+        );
+    }
+
+    #[test]
+    fn test_lookup_resolution() {
+        let addresses: Vec<Pubkey> = (0..3).map(|_| Pubkey::new_unique()).collect();
+        let mut data = vec![0u8; LOOKUP_TABLE_META_SIZE];
+        for address in addresses.iter() {
+            data.extend_from_slice(address.as_ref());
+        }
+        let table_key = Pubkey::new_unique();
+        let mut lamports = 0;
+        let table = AccountInfo::new(
+            &table_key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &address_lookup_table_program::ID,
+            false,
+            0,
+        );
+        let signer = Pubkey::new_unique();
+        let lookup_meta = |position: u8, address_index: u8| TXLookupMeta {
+            position,
+            address_index,
+            is_signer: false,
+            is_writable: true,
+        };
+        let mut tx_ix = TXLookupInstruction {
+            keys: vec![TXAccountMeta {
+                pubkey: signer,
+                is_signer: true,
+                is_writable: false,
+            }],
+            lookups: vec![TXLookup {
+                table: table_key,
+                keys: vec![lookup_meta(0, 2), lookup_meta(2, 0)],
+            }],
+            ..Default::default()
+        };
+
+        let ix = tx_ix.resolve(std::slice::from_ref(&table)).unwrap();
+        let keys: Vec<Pubkey> = ix.keys.iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(keys, vec![addresses[2], signer, addresses[0]]);
+        assert!(ix.keys[1].is_signer && ix.keys[0].is_writable);
+        assert_eq!(ix.space(), tx_ix.space());
+
+        // the table must be passed
+        assert!(tx_ix.resolve(&[]).is_err());
+        // positions must be within the accounts of the instruction
+        tx_ix.lookups[0].keys[1].position = 3;
+        assert!(tx_ix.resolve(std::slice::from_ref(&table)).is_err());
+        // addresses must be in the table
+        tx_ix.lookups[0].keys[1] = lookup_meta(2, 3);
+        assert!(tx_ix.resolve(&[table]).is_err());
+    }
 }
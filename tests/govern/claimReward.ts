@@ -16,6 +16,12 @@ import {
     createSmartWalletProgram,
     createLockedVoterProgram,
     deriveEscrow,
+    deriveDelegatedVote,
+    deriveEscrowCheckpoints,
+    createEscrowCheckpointsIx,
+    createLockerSlopeChangesIx,
+    deriveLockerSlopeChanges,
+    deriveProposalParams,
     deriveGovern,
     deriveLocker,
     deriveSmartWallet,
@@ -124,6 +130,9 @@ describe("Locked voter", () => {
                 payer: voterProgram.provider.publicKey,
                 systemProgram: web3.SystemProgram.programId,
             })
+            .postInstructions([
+                await createLockerSlopeChangesIx(locker, voterProgram),
+            ])
             .rpc();
     }
 
@@ -327,6 +336,7 @@ describe("Locked voter", () => {
                     payer: wallet.publicKey,
                     systemProgram: web3.SystemProgram.programId,
                 })
+                .postInstructions([await createEscrowCheckpointsIx(escrow, voterProgram)])
                 .rpc();
 
             const escrowATA = await getOrCreateATA(
@@ -347,6 +357,8 @@ describe("Locked voter", () => {
                 .increaseLockedAmount(lockAmount)
                 .accounts({
                     escrow,
+                    escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                    slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
                     escrowTokens: escrowATA,
                     locker,
                     payer: voterProgram.provider.publicKey,
@@ -359,6 +371,8 @@ describe("Locked voter", () => {
                 .extendLockDuration(maxStakeDuration)
                 .accounts({
                     escrow,
+                    escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                    slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
                     escrowOwner: wallet.publicKey,
                     locker,
                 })
@@ -398,6 +412,11 @@ describe("Locked voter", () => {
                     isWritable: false,
                     pubkey: locker,
                 },
+                {
+                    isSigner: false,
+                    isWritable: false,
+                    pubkey: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
+                },
                 {
                     isSigner: false,
                     isWritable: true,
@@ -408,6 +427,11 @@ describe("Locked voter", () => {
                     isWritable: true,
                     pubkey: proposal,
                 },
+                {
+                  isSigner: false,
+                  isWritable: false,
+                  pubkey: deriveProposalParams(proposal)[0],
+                },
                 {
                     isSigner: false,
                     isWritable: false,
@@ -475,6 +499,9 @@ describe("Locked voter", () => {
                 .castVote(VoteSide.For)
                 .accounts({
                     escrow,
+                    escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                    delegatedVote: deriveDelegatedVote(proposal, escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                    systemProgram: web3.SystemProgram.programId,
                     governor: govern,
                     governProgram: GOVERN_PROGRAM_ID,
                     locker,
@@ -28,6 +28,11 @@ export const DEFAULT_VOTE_DELAY = new BN(1);
 // Default duration of voting on a proposal, in seconds, ~ 3 days
 export const DEFAULT_VOTE_PERIOD = new BN(3).mul(ONE_DAY);
 
+// Default share of votes, in basis points, required for a proposal to pass. 0 is a simple majority
+export const DEFAULT_APPROVAL_THRESHOLD_BPS = 0;
+// Default quorum as a share of total voting power, in basis points. 0 uses the absolute quorum votes
+export const DEFAULT_QUORUM_BPS = 0;
+
 /**
  * Default parameters for a Governor.
  */
@@ -36,4 +41,6 @@ export const DEFAULT_GOVERNANCE_PARAMETERS = {
     quorumVotes: DEFAULT_QUORUM_VOTES,
     votingDelay: DEFAULT_VOTE_DELAY,
     votingPeriod: DEFAULT_VOTE_PERIOD,
+    approvalThresholdBps: DEFAULT_APPROVAL_THRESHOLD_BPS,
+    quorumBps: DEFAULT_QUORUM_BPS,
 };
\ No newline at end of file
@@ -3,7 +3,6 @@ import { IdlAccounts, Program } from "@coral-xyz/anchor";
 import {
   Keypair,
   SystemProgram,
-  TransactionInstruction,
 } from "@solana/web3.js";
 import { expect } from "chai";
 import { Govern } from "../../target/types/govern";
@@ -119,15 +118,27 @@ describe("Govern", () => {
         ],
         program.programId
       );
+      const [proposalParams] = await anchor.web3.PublicKey.findProgramAddress(
+        [Buffer.from("ProposalParams"), proposal.toBuffer()],
+        program.programId
+      );
       await program.methods
-        .createProposal(1, maxOption, DUMMY_INSTRUCTIONS)
+        .createProposal(1, maxOption, [])
         .accounts({
           governor: governor,
           proposal,
+          proposalParams,
           smartWallet,
           proposer: provider.wallet.publicKey,
           payer: provider.wallet.publicKey,
           systemProgram: SystemProgram.programId,
+          locker: null,
+          depositMint: null,
+          depositVault: null,
+          proposerTokenAccount: null,
+          tokenProgram: null,
+          proposalPolicy: null,
+          proposalClass: null,
         })
         .rpc();
 
@@ -159,18 +170,86 @@ describe("Govern", () => {
       expect(proposalData.governor.toString()).to.eq(governor.toString());
     });
 
-    it("Cancel a proposal", async () => {
-      await program.methods
-        .cancelProposal()
-        .accounts({
-          governor,
-          proposal: proposalKey,
-          proposer: provider.wallet.publicKey,
-        })
-        .rpc();
+    context("Option instructions", () => {
+      it("Cannot create proposal with shared instructions", async () => {
+        governorState = await program.account.governor.fetch(governor);
+        const [proposal] = await anchor.web3.PublicKey.findProgramAddress(
+          [
+            Buffer.from("Proposal"),
+            governor.toBuffer(),
+            governorState.proposalCount.toArrayLike(Buffer, "le", 8),
+          ],
+          program.programId
+        );
+        try {
+          const [proposalParams] = await anchor.web3.PublicKey.findProgramAddress(
+            [Buffer.from("ProposalParams"), proposal.toBuffer()],
+            program.programId
+          );
+          await program.methods
+            .createProposal(1, maxOption, DUMMY_INSTRUCTIONS)
+            .accounts({
+              governor: governor,
+              proposal,
+              proposalParams,
+              smartWallet,
+              proposer: provider.wallet.publicKey,
+              payer: provider.wallet.publicKey,
+              systemProgram: SystemProgram.programId,
+              locker: null,
+              depositMint: null,
+              depositVault: null,
+              proposerTokenAccount: null,
+              tokenProgram: null,
+              proposalPolicy: null,
+              proposalClass: null,
+            })
+            .rpc();
+          expect.fail("should not create proposal with shared instructions");
+        } catch (e) {
+          expect((e as Error).message).to.include("InvalidOptionInstructions");
+        }
+      });
 
-      const proposalData = await program.account.proposal.fetch(proposalKey);
-      expect(proposalData.canceledAt.toNumber()).to.greaterThan(0);
+      it("Can create option instructions", async () => {
+        const option = 1;
+        const [optionInstructions] =
+          await anchor.web3.PublicKey.findProgramAddress(
+            [
+              Buffer.from("OptionInstructions"),
+              proposalKey.toBuffer(),
+              Buffer.from([option]),
+            ],
+            program.programId
+          );
+
+        const [proposalParams] = await anchor.web3.PublicKey.findProgramAddress(
+          [Buffer.from("ProposalParams"), proposalKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .createOptionInstructions(option, DUMMY_INSTRUCTIONS)
+          .accounts({
+            governor,
+            proposal: proposalKey,
+            proposalParams,
+            proposalClass: null,
+            proposer: provider.wallet.publicKey,
+            optionInstructions,
+            payer: provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+            proposalPolicy: null,
+          })
+          .rpc();
+
+        const bundle = await program.account.optionInstructions.fetch(
+          optionInstructions
+        );
+        expect(bundle.option).to.equal(option);
+        expect(bundle.instructions.length).to.equal(DUMMY_INSTRUCTIONS.length);
+        const proposalData = await program.account.proposal.fetch(proposalKey);
+        expect(proposalData.optionInstructionSets).to.equal(1 << option);
+      });
     });
 
     context("Proposal meta", () => {
@@ -239,6 +318,46 @@ describe("Govern", () => {
         expect(metadata.proposal.toString()).to.equal(proposalKey.toString());
       });
     });
+
+    context("Cancel", () => {
+      it("Cancel a proposal", async () => {
+        const [optionProposalMetaKey] =
+          await anchor.web3.PublicKey.findProgramAddress(
+            [Buffer.from("OptionProposalMeta"), proposalKey.toBuffer()],
+            program.programId
+          );
+        const [proposalParams] = await anchor.web3.PublicKey.findProgramAddress(
+          [Buffer.from("ProposalParams"), proposalKey.toBuffer()],
+          program.programId
+        );
+        await program.methods
+          .cancelProposal()
+          .accounts({
+            governor,
+            proposal: proposalKey,
+            proposalParams,
+            proposer: provider.wallet.publicKey,
+            payer: provider.wallet.publicKey,
+            depositVault: null,
+            treasury: null,
+            tokenProgram: null,
+            proposalMeta: null,
+            proposalMetaPayer: null,
+            optionProposalMeta: optionProposalMetaKey,
+            optionProposalMetaPayer: null,
+          })
+          .rpc();
+        // the proposal and its metadata are closed and their rent returned
+        const proposalData = await program.account.proposal.fetchNullable(
+          proposalKey
+        );
+        expect(proposalData).to.be.null;
+        const metadata = await program.account.optionProposalMeta.fetchNullable(
+          optionProposalMetaKey
+        );
+        expect(metadata).to.be.null;
+      });
+    });
   });
 });
 
@@ -248,8 +367,9 @@ const DUMMY_INSTRUCTIONS = [
   Keypair.generate().publicKey,
 ].map(
   (pid) =>
-    new TransactionInstruction({
+    ({
       programId: pid,
       keys: [],
+      data: Buffer.alloc(0),
     })
 );
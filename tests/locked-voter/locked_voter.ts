@@ -16,6 +16,12 @@ import {
   createSmartWalletProgram,
   createLockedVoterProgram,
   deriveEscrow,
+  deriveDelegatedVote,
+  deriveEscrowCheckpoints,
+  createEscrowCheckpointsIx,
+  createLockerSlopeChangesIx,
+  deriveLockerSlopeChanges,
+  deriveProposalParams,
   deriveGovern,
   deriveLocker,
   deriveSmartWallet,
@@ -120,6 +126,9 @@ describe("Locked voter", () => {
         payer: voterProgram.provider.publicKey,
         systemProgram: web3.SystemProgram.programId,
       })
+      .postInstructions([
+        await createLockerSlopeChangesIx(locker, voterProgram),
+      ])
       .rpc();
   }
 
@@ -209,6 +218,7 @@ describe("Locked voter", () => {
           payer: wallet.publicKey,
           systemProgram: web3.SystemProgram.programId,
         })
+        .postInstructions([await createEscrowCheckpointsIx(escrow, voterProgram)])
         .rpc();
 
       const escrowATA = await getOrCreateATA(
@@ -244,6 +254,8 @@ describe("Locked voter", () => {
           .extendLockDuration(maxStakeDuration.add(new BN(1)))
           .accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             escrowOwner: userWallet.publicKey,
             locker,
           })
@@ -266,6 +278,8 @@ describe("Locked voter", () => {
           .extendLockDuration(minStakeDuration.sub(new BN(1)))
           .accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             escrowOwner: userWallet.publicKey,
             locker,
           })
@@ -290,6 +304,8 @@ describe("Locked voter", () => {
         .extendLockDuration(maxStakeDuration)
         .accounts({
           escrow,
+          escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+          slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
           escrowOwner: userWallet.publicKey,
           locker,
         })
@@ -326,6 +342,8 @@ describe("Locked voter", () => {
         .increaseLockedAmount(lockAmount)
         .accounts({
           escrow,
+          escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+          slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
           escrowTokens: escrowATA,
           locker,
           payer: voterProgram.provider.publicKey,
@@ -356,6 +374,8 @@ describe("Locked voter", () => {
           .extendLockDuration(minStakeDuration)
           .accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             escrowOwner: userWallet.publicKey,
             locker,
           })
@@ -383,6 +403,9 @@ describe("Locked voter", () => {
           .castVote(VoteSide.For)
           .accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            delegatedVote: deriveDelegatedVote(proposal, escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            systemProgram: web3.SystemProgram.programId,
             locker,
             governor: govern,
             governProgram: GOVERN_PROGRAM_ID,
@@ -429,6 +452,11 @@ describe("Locked voter", () => {
           isWritable: false,
           pubkey: locker,
         },
+        {
+          isSigner: false,
+          isWritable: false,
+          pubkey: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
+        },
         {
           isSigner: false,
           isWritable: true,
@@ -439,6 +467,11 @@ describe("Locked voter", () => {
           isWritable: true,
           pubkey: proposal,
         },
+        {
+          isSigner: false,
+          isWritable: false,
+          pubkey: deriveProposalParams(proposal)[0],
+        },
         {
           isSigner: false,
           isWritable: false,
@@ -532,6 +565,9 @@ describe("Locked voter", () => {
       .castVote(VoteSide.Against)
       .accounts({
         escrow, // Use delegated escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        delegatedVote: deriveDelegatedVote(proposal, escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        systemProgram: web3.SystemProgram.programId,
         governor: govern,
         governProgram: GOVERN_PROGRAM_ID,
         locker,
@@ -550,32 +586,52 @@ describe("Locked voter", () => {
     );
   });
 
-  it("cannot vote anymore if delegated to other user", async () => {
+  it("owner votes over their delegate", async () => {
     const keypair = userKeypairs[0];
     const wallet = new Wallet(keypair);
 
     const voterProgram = createLockedVoterProgram(wallet, LOCKED_VOTER_PROGRAM_ID);
+    const governProgram = createGovernProgram(wallet, GOVERN_PROGRAM_ID);
     const [escrow, _eBump] = deriveEscrow(locker, wallet.publicKey, LOCKED_VOTER_PROGRAM_ID);
     const [vote, _vBump] = deriveVote(wallet.publicKey, proposal);
+    const [delegatedVote, _dBump] = deriveDelegatedVote(proposal, escrow, LOCKED_VOTER_PROGRAM_ID);
 
-    invokeAndAssertError(
-      () => {
-        return voterProgram.methods
-          .castVote(VoteSide.Against)
-          .accounts({
-            escrow,
-            governor: govern,
-            governProgram: GOVERN_PROGRAM_ID,
-            locker,
-            proposal,
-            vote,
-            voteDelegate: voterProgram.provider.publicKey,
-          })
-          .rpc();
-      },
-      "self.escrow.vote_delegate != self.vote_delegate",
-      false
+    const beforeProposalState = await governProgram.account.proposal.fetch(
+      proposal
     );
+
+    await voterProgram.methods
+      .castVote(VoteSide.For)
+      .accounts({
+        escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        delegatedVote,
+        systemProgram: web3.SystemProgram.programId,
+        governor: govern,
+        governProgram: GOVERN_PROGRAM_ID,
+        locker,
+        proposal,
+        vote,
+        voteDelegate: voterProgram.provider.publicKey,
+      })
+      .rpc();
+
+    const voteState = await governProgram.account.vote.fetch(vote);
+    const afterProposalState = await governProgram.account.proposal.fetch(
+      proposal
+    );
+    const delegatedVoteState = await voterProgram.account.delegatedVote.fetch(
+      delegatedVote
+    );
+
+    // the vote of the delegate is replaced
+    expect(voteState.side.toString()).to.be.equal(VoteSide.For.toString());
+    expect(
+      beforeProposalState.optionVotes[1]
+        .sub(afterProposalState.optionVotes[1])
+        .toString()
+    ).to.be.equal(voteState.votingPower.toString());
+    expect(delegatedVoteState.ownerVoted).to.be.true;
   });
 
   it("user #2 cast for proposal", async () => {
@@ -597,6 +653,9 @@ describe("Locked voter", () => {
       .castVote(VoteSide.For)
       .accounts({
         escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        delegatedVote: deriveDelegatedVote(proposal, escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        systemProgram: web3.SystemProgram.programId,
         governor: govern,
         governProgram: GOVERN_PROGRAM_ID,
         locker,
@@ -638,6 +697,9 @@ describe("Locked voter", () => {
       .castVote(VoteSide.Abstain)
       .accounts({
         escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        delegatedVote: deriveDelegatedVote(proposal, escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        systemProgram: web3.SystemProgram.programId,
         governor: govern,
         governProgram: GOVERN_PROGRAM_ID,
         locker,
@@ -705,6 +767,8 @@ describe("Locked voter", () => {
         .accounts({
           destinationTokens: userATA,
           escrow,
+          escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+          slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
           escrowOwner: voterProgram.provider.publicKey,
           escrowTokens: escrowATA,
           locker,
@@ -723,6 +787,7 @@ describe("Locked voter", () => {
     const voterProgram = createLockedVoterProgram(userWallet, LOCKED_VOTER_PROGRAM_ID);
     const [escrow, _bump] = deriveEscrow(locker, userWallet.publicKey, LOCKED_VOTER_PROGRAM_ID);
 
+    // the escrow checkpoints are kept after the withdrawal, so they are not created again
     await voterProgram.methods
       .newEscrow()
       .accounts({
@@ -738,6 +803,8 @@ describe("Locked voter", () => {
       .toggleMaxLock(true)
       .accounts({
         escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
         locker,
         escrowOwner: userWallet.publicKey,
       })
@@ -769,6 +836,8 @@ describe("Locked voter", () => {
           .accounts({
             destinationTokens: userATA,
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             escrowOwner: voterProgram.provider.publicKey,
             escrowTokens: escrowATA,
             locker,
@@ -785,6 +854,8 @@ describe("Locked voter", () => {
       .toggleMaxLock(false)
       .accounts({
         escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
         locker,
         escrowOwner: userWallet.publicKey,
       })
@@ -819,6 +890,8 @@ describe("Locked voter", () => {
       .accounts({
         destinationTokens: userATA,
         escrow,
+        escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+        slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
         escrowOwner: voterProgram.provider.publicKey,
         escrowTokens: escrowATA,
         locker,
@@ -15,6 +15,10 @@ import {
     createSmartWalletProgram,
     createLockedVoterProgram,
     deriveEscrow,
+    deriveEscrowCheckpoints,
+    createEscrowCheckpointsIx,
+    createLockerSlopeChangesIx,
+    deriveLockerSlopeChanges,
     deriveGovern,
     deriveLocker,
     deriveSmartWallet,
@@ -115,6 +119,9 @@ describe("Partial unstaking", () => {
                 payer: voterProgram.provider.publicKey,
                 systemProgram: web3.SystemProgram.programId,
             })
+            .postInstructions([
+                await createLockerSlopeChangesIx(locker, voterProgram),
+            ])
             .rpc();
     }
 
@@ -204,12 +211,15 @@ describe("Partial unstaking", () => {
                 payer: wallet.publicKey,
                 systemProgram: web3.SystemProgram.programId,
             })
+            .postInstructions([await createEscrowCheckpointsIx(escrow, voterProgram)])
             .rpc();
 
         await voterProgram.methods
             .extendLockDuration(maxStakeDuration)
             .accounts({
                 escrow,
+                escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
                 escrowOwner: wallet.publicKey,
                 locker,
             })
@@ -232,6 +242,8 @@ describe("Partial unstaking", () => {
             .increaseLockedAmount(lockAmount)
             .accounts({
                 escrow,
+                escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
                 escrowTokens: escrowATA,
                 locker,
                 payer: voterProgram.provider.publicKey,
@@ -259,6 +271,8 @@ describe("Partial unstaking", () => {
         // open partial unstaking
         await voterProgram.methods.openPartialUnstaking(partialUnstakingAmount, memo).accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             locker,
             partialUnstake: partialUnstakeKP.publicKey,
             owner: userKeypair.publicKey,
@@ -281,6 +295,8 @@ describe("Partial unstaking", () => {
         // merge 
         await voterProgram.methods.mergePartialUnstaking().accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             locker,
             partialUnstake: partialUnstakeKP.publicKey,
             owner: userKeypair.publicKey,
@@ -307,6 +323,8 @@ describe("Partial unstaking", () => {
         // open partial unstaking
         await voterProgram.methods.openPartialUnstaking(partialUnstakingAmount, "").accounts({
             escrow,
+            escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+            slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
             locker,
             partialUnstake: partialUnstakeKP.publicKey,
             owner: userKeypair.publicKey,
@@ -382,6 +400,8 @@ describe("Partial unstaking", () => {
             .accounts({
                 destinationTokens: userATA,
                 escrow,
+                escrowCheckpoints: deriveEscrowCheckpoints(escrow, LOCKED_VOTER_PROGRAM_ID)[0],
+                slopeChanges: deriveLockerSlopeChanges(locker, LOCKED_VOTER_PROGRAM_ID)[0],
                 escrowOwner: voterProgram.provider.publicKey,
                 escrowTokens: escrowATA,
                 locker,
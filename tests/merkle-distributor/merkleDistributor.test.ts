@@ -23,6 +23,7 @@ import {
   deriveEscrow,
   deriveGovern,
   deriveLocker,
+  deriveLockerSlopeChanges,
   deriveSmartWallet,
   getOrCreateATA,
   invokeAndAssertError,
@@ -276,6 +277,7 @@ describe("merkle-distributor", () => {
             claimant: userOneWallet.publicKey,
             escrow,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             escrowTokens,
             voterProgram: MET_VOTER_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
@@ -330,6 +332,7 @@ describe("merkle-distributor", () => {
             claimant: userOneWallet.publicKey,
             escrow,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             escrowTokens,
             voterProgram: MET_VOTER_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
@@ -382,6 +385,7 @@ describe("merkle-distributor", () => {
             claimant: userOneWallet.publicKey,
             escrow,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             escrowTokens,
             voterProgram: MET_VOTER_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
@@ -449,6 +453,7 @@ describe("merkle-distributor", () => {
         claimant: userOneWallet.publicKey,
         escrow,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         escrowTokens,
         voterProgram: MET_VOTER_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
@@ -556,6 +561,7 @@ describe("merkle-distributor", () => {
             claimant: userOneWallet.publicKey,
             escrow,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             escrowTokens,
             voterProgram: MET_VOTER_PROGRAM_ID,
             systemProgram: web3.SystemProgram.programId,
@@ -605,6 +611,7 @@ describe("merkle-distributor", () => {
         claimant: userWallet.publicKey,
         escrow,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         escrowTokens,
         voterProgram: MET_VOTER_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
@@ -673,6 +680,7 @@ describe("merkle-distributor", () => {
         claimant: userThreeWallet.publicKey,
         escrow,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         escrowTokens,
         voterProgram: MET_VOTER_PROGRAM_ID,
         systemProgram: web3.SystemProgram.programId,
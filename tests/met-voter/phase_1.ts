@@ -30,6 +30,9 @@ import {
   getOrCreateATA,
   invokeAndAssertError,
   sleep,
+  createMetVoterLockerSlopeChangesIx,
+  deriveLockerSlopeChanges,
+  deriveProposalParams,
 } from "../utils";
 import { TOKEN_PROGRAM_ID, createMint, mintTo, getAssociatedTokenAddressSync } from "@solana/spl-token";
 import { expect } from "chai";
@@ -243,6 +246,9 @@ describe("Locked voter", () => {
         payer: voterProgram.provider.publicKey,
         systemProgram: web3.SystemProgram.programId,
       })
+      .postInstructions([
+        await createMetVoterLockerSlopeChangesIx(locker, voterProgram),
+      ])
       .rpc();
 
     const lockerState = await voterProgram.account.locker.fetch(locker);
@@ -578,6 +584,7 @@ describe("Locked voter", () => {
           escrowTokens: escrowATA,
           tokenVault: mdATA,
           locker,
+          slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           systemProgram: web3.SystemProgram.programId,
           tokenProgram: TOKEN_PROGRAM_ID,
           voterProgram: MET_VOTER_PROGRAM_ID,
@@ -634,6 +641,7 @@ describe("Locked voter", () => {
         escrow,
         escrowTokens: escrowATA,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         payer: voterProgram.provider.publicKey,
         sourceTokens: rewardATA,
         tokenProgram: TOKEN_PROGRAM_ID,
@@ -679,6 +687,7 @@ describe("Locked voter", () => {
             escrowOwner: voterProgram.provider.publicKey,
             escrowTokens: escrowATA,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             payer: voterProgram.provider.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
@@ -703,6 +712,7 @@ describe("Locked voter", () => {
             escrow,
             escrowOwner: wallet.publicKey,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           })
           .rpc();
       },
@@ -725,6 +735,7 @@ describe("Locked voter", () => {
             escrow,
             escrowOwner: wallet.publicKey,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           })
           .rpc();
       },
@@ -746,6 +757,7 @@ describe("Locked voter", () => {
             governProgram: GOVERN_PROGRAM_ID,
             locker,
             proposal,
+            proposalParams: deriveProposalParams(proposal)[0],
             smartWallet: wallet.publicKey,
           })
           .rpc();
@@ -766,7 +778,9 @@ describe("Locked voter", () => {
             escrow,
             escrowOwner: wallet.publicKey,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             proposal,
+            proposalParams: deriveProposalParams(proposal)[0],
           })
           .rpc();
       },
@@ -816,6 +830,11 @@ describe("Locked voter", () => {
           isWritable: true,
           pubkey: proposal,
         },
+        {
+          isSigner: false,
+          isWritable: false,
+          pubkey: deriveProposalParams(proposal)[0],
+        },
         {
           isSigner: false,
           isWritable: false,
@@ -1067,6 +1086,7 @@ describe("Locked voter", () => {
           escrowOwner: voterProgram.provider.publicKey,
           escrowTokens: escrowATA,
           locker,
+          slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           payer: voterProgram.provider.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
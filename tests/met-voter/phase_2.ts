@@ -25,6 +25,9 @@ import {
   getOrCreateVote,
   invokeAndAssertError,
   sleep,
+  createMetVoterLockerSlopeChangesIx,
+  deriveLockerSlopeChanges,
+  deriveProposalParams,
 } from "../utils";
 import { expect } from "chai";
 
@@ -124,6 +127,9 @@ describe("Locked voter", () => {
         payer: voterProgram.provider.publicKey,
         systemProgram: web3.SystemProgram.programId,
       })
+      .postInstructions([
+        await createMetVoterLockerSlopeChangesIx(locker, voterProgram),
+      ])
       .rpc();
 
     while (true) {
@@ -268,6 +274,7 @@ describe("Locked voter", () => {
             escrow,
             escrowOwner: userWallet.publicKey,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           })
           .rpc();
       },
@@ -290,6 +297,7 @@ describe("Locked voter", () => {
             escrow,
             escrowOwner: userWallet.publicKey,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           })
           .rpc();
       },
@@ -314,6 +322,7 @@ describe("Locked voter", () => {
           escrow,
           escrowOwner: userWallet.publicKey,
           locker,
+          slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         })
         .rpc();
 
@@ -350,6 +359,7 @@ describe("Locked voter", () => {
           escrow,
           escrowTokens: escrowATA,
           locker,
+          slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           payer: voterProgram.provider.publicKey,
           sourceTokens: rewardATA,
           tokenProgram: TOKEN_PROGRAM_ID,
@@ -381,6 +391,7 @@ describe("Locked voter", () => {
             escrow,
             escrowOwner: userWallet.publicKey,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           })
           .rpc();
       },
@@ -433,7 +444,9 @@ describe("Locked voter", () => {
         governor: govern,
         governProgram: GOVERN_PROGRAM_ID,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         proposal,
+        proposalParams: deriveProposalParams(proposal)[0],
         escrow,
         escrowOwner: wallet.publicKey,
       })
@@ -661,6 +674,7 @@ describe("Locked voter", () => {
           escrowOwner: voterProgram.provider.publicKey,
           escrowTokens: escrowATA,
           locker,
+          slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
           payer: voterProgram.provider.publicKey,
           tokenProgram: TOKEN_PROGRAM_ID,
         })
@@ -692,6 +706,7 @@ describe("Locked voter", () => {
       .accounts({
         escrow,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         escrowOwner: userWallet.publicKey,
       })
       .rpc();
@@ -725,6 +740,7 @@ describe("Locked voter", () => {
             escrowOwner: voterProgram.provider.publicKey,
             escrowTokens: escrowATA,
             locker,
+            slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
             payer: voterProgram.provider.publicKey,
             tokenProgram: TOKEN_PROGRAM_ID,
           })
@@ -739,6 +755,7 @@ describe("Locked voter", () => {
       .accounts({
         escrow,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         escrowOwner: userWallet.publicKey,
       })
       .rpc();
@@ -775,6 +792,7 @@ describe("Locked voter", () => {
         escrowOwner: voterProgram.provider.publicKey,
         escrowTokens: escrowATA,
         locker,
+        slopeChanges: deriveLockerSlopeChanges(locker, MET_VOTER_PROGRAM_ID)[0],
         payer: voterProgram.provider.publicKey,
         tokenProgram: TOKEN_PROGRAM_ID,
       })
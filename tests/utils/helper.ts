@@ -99,6 +99,85 @@ export function deriveEscrow(
   );
 }
 
+export function deriveEscrowCheckpoints(
+  escrow: web3.PublicKey,
+  voterProgram: web3.PublicKey,
+) {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("EscrowCheckpoints"), escrow.toBytes()],
+    voterProgram
+  );
+}
+
+export function deriveDelegatedVote(
+  proposal: web3.PublicKey,
+  escrow: web3.PublicKey,
+  voterProgram: web3.PublicKey,
+) {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("DelegatedVote"), proposal.toBytes(), escrow.toBytes()],
+    voterProgram
+  );
+}
+
+export function deriveLockerSlopeChanges(
+  locker: web3.PublicKey,
+  voterProgram: web3.PublicKey,
+) {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("LockerSlopeChanges"), locker.toBytes()],
+    voterProgram
+  );
+}
+
+export async function createEscrowCheckpointsIx(
+  escrow: web3.PublicKey,
+  voterProgram: Program<LockedVoter>
+) {
+  const [escrowCheckpoints, _bump] = deriveEscrowCheckpoints(escrow, voterProgram.programId);
+  return voterProgram.methods
+    .newEscrowCheckpoints()
+    .accounts({
+      escrow,
+      escrowCheckpoints,
+      payer: voterProgram.provider.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .instruction();
+}
+
+export async function createLockerSlopeChangesIx(
+  locker: web3.PublicKey,
+  voterProgram: Program<LockedVoter>
+) {
+  const [slopeChanges, _bump] = deriveLockerSlopeChanges(locker, voterProgram.programId);
+  return voterProgram.methods
+    .newLockerSlopeChanges()
+    .accounts({
+      locker,
+      slopeChanges,
+      payer: voterProgram.provider.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .instruction();
+}
+
+export async function createMetVoterLockerSlopeChangesIx(
+  locker: web3.PublicKey,
+  voterProgram: Program<MetVoter>
+) {
+  const [slopeChanges, _bump] = deriveLockerSlopeChanges(locker, voterProgram.programId);
+  return voterProgram.methods
+    .newLockerSlopeChanges()
+    .accounts({
+      locker,
+      slopeChanges,
+      payer: voterProgram.provider.publicKey,
+      systemProgram: web3.SystemProgram.programId,
+    })
+    .instruction();
+}
+
 export function deriveTransaction(smartWallet: web3.PublicKey, txNo: BN) {
   return web3.PublicKey.findProgramAddressSync(
     [
@@ -121,6 +200,13 @@ export function deriveProposal(governor: web3.PublicKey, proposalCount: BN) {
   );
 }
 
+export function deriveProposalParams(proposal: web3.PublicKey) {
+  return web3.PublicKey.findProgramAddressSync(
+    [Buffer.from("ProposalParams"), proposal.toBytes()],
+    GOVERN_PROGRAM_ID
+  );
+}
+
 export function deriveProposalMeta(proposal: web3.PublicKey) {
   return web3.PublicKey.findProgramAddressSync(
     [Buffer.from("ProposalMeta"), proposal.toBytes()],
@@ -152,8 +238,16 @@ export async function createProposal(
       payer: governProgram.provider.publicKey,
       smartWallet: governState.smartWallet,
       proposal,
+      proposalParams: deriveProposalParams(proposal)[0],
       proposer: governProgram.provider.publicKey,
       systemProgram: web3.SystemProgram.programId,
+      locker: null,
+      depositMint: null,
+      depositVault: null,
+      proposerTokenAccount: null,
+      tokenProgram: null,
+      proposalPolicy: null,
+      proposalClass: null,
     })
     .rpc();
 
@@ -180,8 +274,16 @@ export async function createOptionProposal(
       smartWallet: governState.smartWallet,
       payer: governProgram.provider.publicKey,
       proposal,
+      proposalParams: deriveProposalParams(proposal)[0],
       proposer: governProgram.provider.publicKey,
       systemProgram: web3.SystemProgram.programId,
+      locker: null,
+      depositMint: null,
+      depositVault: null,
+      proposerTokenAccount: null,
+      tokenProgram: null,
+      proposalPolicy: null,
+      proposalClass: null,
     })
     .rpc();
 
@@ -359,6 +461,8 @@ export async function createGovernor(
       votingPeriod,
       quorumVotes,
       timelockDelaySeconds,
+      approvalThresholdBps: 0,
+      quorumBps: 0,
     })
     .accounts({
       base: baseKeypair.publicKey,
@@ -409,6 +513,9 @@ export async function createMetLocker(
       payer: voterProgram.provider.publicKey,
       systemProgram: web3.SystemProgram.programId,
     })
+    .postInstructions([
+      await createMetVoterLockerSlopeChangesIx(locker, voterProgram),
+    ])
     .rpc();
 
   console.log("Create locker tx", tx);
@@ -446,6 +553,9 @@ export async function createLocker(
       payer: voterProgram.provider.publicKey,
       systemProgram: web3.SystemProgram.programId,
     })
+    .postInstructions([
+      await createLockerSlopeChangesIx(locker, voterProgram),
+    ])
     .rpc();
 
   console.log("Create locker tx", tx);